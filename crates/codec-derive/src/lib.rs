@@ -2,14 +2,147 @@
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::{self, Data, Fields, Ident};
+use syn::{
+    self,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Data, DataEnum, Expr, Fields, GenericArgument, Ident, Index, Lit, Member, PathArguments, Token,
+    Type,
+};
 
+/// Rust function signature (`fn transfer(to: Address, value: U256)`), return type is ignored
+struct RustSignature {
+    name: Ident,
+    inputs: Punctuated<(Ident, Type), Token![,]>,
+}
+
+impl Parse for RustSignature {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![fn]>()?;
+        let name = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let inputs = content.parse_terminated(
+            |arg: ParseStream| {
+                let name = arg.parse()?;
+                arg.parse::<Token![:]>()?;
+                Ok((name, arg.parse()?))
+            },
+            Token![,],
+        )?;
+        if input.parse::<Option<Token![->]>>()?.is_some() {
+            input.parse::<Type>()?;
+        }
+        Ok(Self { name, inputs })
+    }
+}
+
+fn array_length(expr: &Expr) -> syn::Result<String> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Int(value) => Ok(value.base10_digits().to_string()),
+            _ => Err(syn::Error::new_spanned(
+                expr,
+                "array length must be an integer",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "array length must be a literal",
+        )),
+    }
+}
+
+/// Maps Rust type to Solidity type the same way `SolidityCodec` does, custom types are not
+/// supported because macro can't see their fields
+fn sol_type_name(ty: &Type) -> syn::Result<String> {
+    let unsupported = || syn::Error::new_spanned(ty, "type is not supported in selectors");
+    match ty {
+        Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(sol_type_name)
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(format!("({})", elems.join(",")))
+        }
+        Type::Array(array) => Ok(format!(
+            "{}[{}]",
+            sol_type_name(&array.elem)?,
+            array_length(&array.len)?
+        )),
+        Type::Reference(reference) => sol_type_name(&reference.elem),
+        Type::Path(path) => {
+            let segment = path.path.segments.last().ok_or_else(unsupported)?;
+            let generics = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+                _ => Vec::new(),
+            };
+            let name = segment.ident.to_string();
+            let bits = |prefix: &str| {
+                name.strip_prefix(prefix)
+                    .filter(|bits| !bits.is_empty() && bits.chars().all(|c| c.is_ascii_digit()))
+                    .map(|bits| bits.to_string())
+            };
+            Ok(match (name.as_str(), generics.as_slice()) {
+                ("bool", []) => "bool".to_string(),
+                ("Address", []) => "address".to_string(),
+                ("Bytes", []) => "bytes".to_string(),
+                ("String", []) => "string".to_string(),
+                ("Vec", [GenericArgument::Type(elem)]) => format!("{}[]", sol_type_name(elem)?),
+                ("Box", [GenericArgument::Type(elem)]) => sol_type_name(elem)?,
+                ("FixedBytes", [GenericArgument::Const(len)]) => {
+                    format!("bytes{}", array_length(len)?)
+                }
+                ("Uint", [GenericArgument::Const(bits), _]) => {
+                    format!("uint{}", array_length(bits)?)
+                }
+                ("Signed", [GenericArgument::Const(bits), _]) => {
+                    format!("int{}", array_length(bits)?)
+                }
+                (_, []) if bits("u").is_some() => format!("uint{}", bits("u").unwrap()),
+                (_, []) if bits("i").is_some() => format!("int{}", bits("i").unwrap()),
+                // alloy aliases, f.e. `U256` or `B256`
+                (_, []) if bits("U").is_some() => format!("uint{}", bits("U").unwrap()),
+                (_, []) if bits("I").is_some() => format!("int{}", bits("I").unwrap()),
+                (_, []) if bits("B").is_some() => {
+                    let bits = bits("B").unwrap().parse::<usize>().unwrap();
+                    format!("bytes{}", bits / 8)
+                }
+                _ => return Err(unsupported()),
+            })
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// Calculates 4-byte method id (big-endian u32).
+///
+/// Rust signature (`fn transfer(to: Address, value: U256)`) is converted into a Solidity
+/// signature (`transfer(address,uint256)`), so the result matches Solidity selectors.
+/// For other input tokens hash is calculated over the token string itself (including quotes),
+/// it's kept for the existing method ids.
 #[proc_macro]
 pub fn derive_keccak256_id(token: TokenStream) -> TokenStream {
     use crypto_hashes::digest::Digest;
     use crypto_hashes::sha3::Keccak256;
     let mut hash = Keccak256::new();
-    hash.update(token.to_string());
+    if let Ok(signature) = syn::parse::<RustSignature>(token.clone()) {
+        let params = signature
+            .inputs
+            .iter()
+            .map(|(_, ty)| sol_type_name(ty))
+            .collect::<syn::Result<Vec<_>>>();
+        let params = match params {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        hash.update(format!("{}({})", signature.name, params.join(",")));
+    } else {
+        hash.update(token.to_string());
+    }
     let mut dst = [0u8; 4];
     dst.copy_from_slice(hash.finalize().as_slice()[0..4].as_ref());
     let method_id: u32 = u32::from_be_bytes(dst);
@@ -25,76 +158,898 @@ fn impl_derive_codec(ast: &syn::DeriveInput) -> TokenStream {
     } else {
         quote! { fluentbase_codec }
     };
-    let data_struct = match &ast.data {
-        Data::Struct(data_struct) => data_struct,
-        _ => panic!("only structs are supported"),
+    let attrs = match parse_codec_attrs(ast) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut output = proc_macro2::TokenStream::from(match &ast.data {
+        Data::Struct(data_struct) => impl_derive_struct(
+            &crate_name,
+            ast,
+            &data_struct.fields,
+            attrs.version.as_ref(),
+        ),
+        Data::Enum(data_enum) => impl_derive_enum(&crate_name, ast, data_enum),
+        Data::Union(_) => panic!("unions are not supported"),
+    });
+    if attrs.view {
+        output.extend(match &ast.data {
+            Data::Struct(data_struct) => impl_view_struct(
+                &crate_name,
+                ast,
+                &data_struct.fields,
+                attrs.version.as_ref(),
+            ),
+            Data::Enum(data_enum) => impl_view_enum(&crate_name, ast, data_enum),
+            Data::Union(_) => unreachable!(),
+        });
+    }
+    if attrs.reader {
+        if let Data::Struct(data_struct) = &ast.data {
+            output.extend(impl_reader_struct(
+                &crate_name,
+                ast,
+                &data_struct.fields,
+                attrs.version.as_ref(),
+            ));
+        }
+    }
+    if attrs.schema {
+        output.extend(impl_schema(&crate_name, ast, attrs.version.as_ref()));
+    }
+    if attrs.solidity {
+        output.extend(match &ast.data {
+            Data::Struct(data_struct) => {
+                impl_solidity_struct(&crate_name, ast, &data_struct.fields)
+            }
+            Data::Enum(data_enum) => impl_solidity_enum(&crate_name, ast, data_enum),
+            Data::Union(_) => unreachable!(),
+        });
+    }
+    TokenStream::from(output)
+}
+
+/// Adds `'view` lifetime to the generics, type params of views must implement `EncoderView`
+fn view_generics(
+    crate_name: &proc_macro2::TokenStream,
+    generics: &syn::Generics,
+    with_bounds: bool,
+) -> syn::Generics {
+    let mut generics = generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    generics.params.insert(0, syn::parse_quote! { 'view });
+    if with_bounds {
+        let where_clause = generics.make_where_clause();
+        for ident in type_params {
+            where_clause
+                .predicates
+                .push(syn::parse_quote! { #ident: #crate_name::EncoderView<'view> });
+        }
+    }
+    generics
+}
+
+/// Accessor name of the field inside `{Struct}Ref` (`a` for named fields and `field0` for
+/// unnamed ones)
+fn view_accessor(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("field{}", index.index),
+    }
+}
+
+/// Struct view is a `{Struct}Ref<'view>` that stores input buffer and offset of the struct,
+/// fields are decoded by accessors only when requested
+fn impl_view_struct(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    fields: &Fields,
+    version: Option<&StructVersion>,
+) -> proc_macro2::TokenStream {
+    let fields = struct_fields(fields);
+    let accessors = fields.iter().enumerate().map(|(i, (member, ty, _))| {
+        let accessor = view_accessor(member);
+        let field_offsets = fields.iter().take(i).map(|(_, ty, _)| {
+            quote! {
+                <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE
+            }
+        });
+        // fields missing in versioned structs can't be viewed, so they are reported as errors
+        let view = match version {
+            Some(version) => {
+                let field_versions = version.field_versions(crate_name, &fields);
+                quote! {
+                    let header = #crate_name::ExtensibleHeader::try_decode(&#crate_name::BufferDecoder::new(self.buffer), self.offset)?;
+                    header.check_version(#field_versions)?;
+                    let field_offset = 0 #( + #field_offsets )*;
+                    let field_size = <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE;
+                    if !header.has_field(field_offset + field_size) {
+                        return Err(#crate_name::CodecError::OutOfBounds {
+                            offset: field_offset,
+                            length: field_size,
+                        });
+                    }
+                    <#ty as #crate_name::EncoderView<'view>>::view(self.buffer, header.fields_offset + field_offset)
+                }
+            }
+            None => quote! {
+                <#ty as #crate_name::EncoderView<'view>>::view(self.buffer, self.offset #( + #field_offsets )*)
+            },
+        };
+        quote! {
+            pub fn #accessor(&self) -> core::result::Result<<#ty as #crate_name::EncoderView<'view>>::View, #crate_name::CodecError>
+            where
+                #ty: #crate_name::EncoderView<'view>,
+            {
+                #view
+            }
+        }
+    });
+    let vis = &ast.vis;
+    let struct_name = &ast.ident;
+    let ref_name = format_ident!("{}Ref", struct_name);
+    let (_, type_generics, _) = ast.generics.split_for_impl();
+    let def_generics = view_generics(crate_name, &ast.generics, false);
+    let (_, _, def_where_clause) = def_generics.split_for_impl();
+    let impl_generics = view_generics(crate_name, &ast.generics, true);
+    let (view_impl_generics, view_type_generics, view_where_clause) =
+        impl_generics.split_for_impl();
+    quote! {
+        #[allow(dead_code)]
+        #vis struct #ref_name #def_generics #def_where_clause {
+            buffer: &'view [u8],
+            offset: usize,
+            _marker: core::marker::PhantomData<fn() -> #struct_name #type_generics>,
+        }
+        impl #view_impl_generics Clone for #ref_name #view_type_generics #view_where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl #view_impl_generics Copy for #ref_name #view_type_generics #view_where_clause {}
+        #[allow(dead_code)]
+        impl #view_impl_generics #ref_name #view_type_generics #view_where_clause {
+            #( #accessors )*
+        }
+        impl #view_impl_generics #crate_name::EncoderView<'view> for #struct_name #type_generics #view_where_clause {
+            type View = #ref_name #view_type_generics;
+            fn view(buffer: &'view [u8], field_offset: usize) -> core::result::Result<Self::View, #crate_name::CodecError> {
+                // make sure that header fits, dynamic fields are checked by accessors
+                #crate_name::BufferDecoder::new(buffer).try_read_slice(field_offset, <Self as #crate_name::Encoder<Self>>::HEADER_SIZE)?;
+                Ok(#ref_name {
+                    buffer,
+                    offset: field_offset,
+                    _marker: core::marker::PhantomData,
+                })
+            }
+        }
+    }
+}
+
+/// Struct reader is a `{Struct}Reader<R>` that reads only requested fields from any
+/// `ByteSource`: header for static fields and header with body for dynamic ones. Header of a
+/// versioned struct is read and validated once in `new`
+fn impl_reader_struct(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    fields: &Fields,
+    version: Option<&StructVersion>,
+) -> proc_macro2::TokenStream {
+    let vis = &ast.vis;
+    let struct_name = &ast.ident;
+    let reader_name = format_ident!("{}Reader", struct_name);
+    let i_struct_name = format_ident!("I{}", struct_name);
+    let (_, type_generics, where_clause) = ast.generics.split_for_impl();
+    let fields = struct_fields(fields);
+    let readers = fields.iter().map(|(member, ty, type_name)| {
+        let accessor = view_accessor(member);
+        let size_accessor = format_ident!("{}_size", accessor);
+        let location_accessor = format_ident!("{}_location", accessor);
+        let field_encoder = quote! {
+            <#struct_name #type_generics as #i_struct_name>::#type_name
+        };
+        // fields missing in versioned structs are read as default values, and present fields are
+        // read relative to the fields block, since it can be placed anywhere in the input
+        let field_offset = |missing: proc_macro2::TokenStream| match version {
+            Some(_) => quote! {
+                let field_offset = #field_encoder::FIELD_OFFSET - #crate_name::ExtensibleHeader::SIZE;
+                if !self.header.has_field(field_offset + #field_encoder::FIELD_SIZE) {
+                    return #missing;
+                }
+                let field_offset = self.header.fields_offset + field_offset;
+            },
+            None => quote! {
+                let field_offset = #field_encoder::FIELD_OFFSET;
+            },
+        };
+        let body_field_offset = field_offset(quote! { Ok(Default::default()) });
+        let header_field_offset = field_offset(quote! { (0, 0) });
+        quote! {
+            #[inline(always)]
+            pub fn #accessor(&self) -> core::result::Result<#ty, #crate_name::CodecError>
+            where
+                #ty: Default,
+            {
+                #body_field_offset
+                let mut result: #ty = Default::default();
+                #field_encoder::read_field_body_at(&self.source, field_offset, &mut result)?;
+                Ok(result)
+            }
+            /// Length of the field body, it's always 0 for static fields
+            #[inline(always)]
+            pub fn #size_accessor(&self) -> u32
+            where
+                #ty: Default,
+            {
+                self.#location_accessor().1 as u32
+            }
+            /// Offset and length of the field body in the source, they're always 0 for static
+            /// fields and fields missing in the source
+            #[inline(always)]
+            pub fn #location_accessor(&self) -> (usize, usize)
+            where
+                #ty: Default,
+            {
+                #header_field_offset
+                let mut result: #ty = Default::default();
+                #field_encoder::read_field_header_at(&self.source, field_offset, &mut result)
+            }
+        }
+    });
+    let (header_field, read_header, header_init) = match version {
+        Some(version) => {
+            let field_versions = version.field_versions(crate_name, &fields);
+            (
+                quote! { header: #crate_name::ExtensibleHeader, },
+                quote! {
+                    let header = #crate_name::ExtensibleHeader::read(&source, 0);
+                    header.check_version(#field_versions)?;
+                },
+                quote! { header, },
+            )
+        }
+        None => (quote! {}, quote! {}, quote! {}),
+    };
+    let mut generics = ast.generics.clone();
+    generics
+        .params
+        .push(syn::parse_quote! { __R: #crate_name::ByteSource });
+    let (reader_impl_generics, reader_type_generics, _) = generics.split_for_impl();
+    quote! {
+        #[allow(dead_code)]
+        #vis struct #reader_name #generics #where_clause {
+            source: __R,
+            #header_field
+            _marker: core::marker::PhantomData<fn() -> #struct_name #type_generics>,
+        }
+        #[allow(dead_code)]
+        impl #reader_impl_generics #reader_name #reader_type_generics #where_clause {
+            pub fn new(source: __R) -> core::result::Result<Self, #crate_name::CodecError> {
+                #read_header
+                Ok(Self {
+                    source,
+                    #header_init
+                    _marker: core::marker::PhantomData,
+                })
+            }
+            #( #readers )*
+        }
+    }
+}
+
+/// Enum view is a `{Enum}Ref<'view>` with the same variants, where each field is replaced with
+/// its view. Enums without fields are viewed as themselves.
+fn impl_view_enum(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    data_enum: &DataEnum,
+) -> proc_macro2::TokenStream {
+    let vis = &ast.vis;
+    let enum_name = &ast.ident;
+    let fieldless = data_enum
+        .variants
+        .iter()
+        .all(|variant| variant.fields.is_empty());
+    let ref_name = format_ident!("{}Ref", enum_name);
+    let ref_path = if fieldless {
+        quote! { Self }
+    } else {
+        quote! { #ref_name }
+    };
+    let mut ref_variants = Vec::new();
+    let mut view_variants = Vec::new();
+    for (i, variant) in data_enum.variants.iter().enumerate() {
+        let variant_index = i as u32;
+        let variant_name = &variant.ident;
+        let fields = struct_fields(&variant.fields);
+        let bindings = (0..fields.len())
+            .map(|i| format_ident!("field{}", i))
+            .collect::<Vec<_>>();
+        let members = fields
+            .iter()
+            .map(|(member, _, _)| member)
+            .collect::<Vec<_>>();
+        let types = fields.iter().map(|(_, ty, _)| *ty).collect::<Vec<_>>();
+        let views = types
+            .iter()
+            .map(|ty| quote! { <#ty as #crate_name::EncoderView<'view>>::View })
+            .collect::<Vec<_>>();
+        ref_variants.push(match &variant.fields {
+            Fields::Named(_) => quote! { #variant_name { #( #members: #views ),* } },
+            Fields::Unnamed(_) => quote! { #variant_name ( #( #views ),* ) },
+            Fields::Unit => quote! { #variant_name },
+        });
+        view_variants.push(quote! {
+            #variant_index => {
+                let mut body_offset = 0;
+                #(
+                    let #bindings = <#types as #crate_name::EncoderView<'view>>::view(body, body_offset)?;
+                    body_offset += <#types as #crate_name::Encoder<#types>>::HEADER_SIZE;
+                )*
+                #ref_path::#variant_name { #( #members: #bindings ),* }
+            }
+        });
+    }
+    let (_, type_generics, _) = ast.generics.split_for_impl();
+    let impl_generics = view_generics(crate_name, &ast.generics, true);
+    let (view_impl_generics, view_type_generics, view_where_clause) =
+        impl_generics.split_for_impl();
+    let (view_type, ref_definition) = if fieldless {
+        (quote! { Self }, quote! {})
+    } else {
+        (
+            quote! { #ref_name #view_type_generics },
+            quote! {
+                #[allow(dead_code)]
+                #vis enum #ref_name #view_impl_generics #view_where_clause {
+                    #( #ref_variants, )*
+                }
+            },
+        )
     };
-    let named_fields = match &data_struct.fields {
-        Fields::Named(named_fields) => named_fields,
-        _ => panic!("only named fields are supported"),
+    quote! {
+        #ref_definition
+        impl #view_impl_generics #crate_name::EncoderView<'view> for #enum_name #type_generics #view_where_clause {
+            type View = #view_type;
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn view(buffer: &'view [u8], field_offset: usize) -> core::result::Result<Self::View, #crate_name::CodecError> {
+                let decoder = #crate_name::BufferDecoder::new(buffer);
+                let variant_index = decoder.try_read_u32(field_offset)?;
+                let body = decoder.try_read_bytes(field_offset + 4)?;
+                Ok(match variant_index {
+                    #( #view_variants )*
+                    _ => return Err(#crate_name::CodecError::UnknownVariant(variant_index)),
+                })
+            }
+        }
+    }
+}
+
+/// Options from `#[codec(...)]` attributes
+#[derive(Default)]
+struct CodecAttrs {
+    /// `#[codec(solidity)]` - implement Solidity ABI encoding
+    solidity: bool,
+    /// `#[codec(version = N)]` - encode struct as extensible (see `ExtensibleHeader`)
+    version: Option<StructVersion>,
+    /// `#[codec(view)]` - generate borrowed `{Name}Ref` view, all field types must implement
+    /// `EncoderView`
+    view: bool,
+    /// `#[codec(reader)]` - generate lazy `{Name}Reader` over a `ByteSource` (structs only)
+    reader: bool,
+    /// `#[codec(schema)]` - implement `CodecSchema`, all field types must implement it too
+    schema: bool,
+}
+
+/// Version of the extensible struct and versions that introduced its fields, set by
+/// `#[codec(since = N)]` on the first field of the version (fields without it are from the same
+/// version as the previous field)
+struct StructVersion {
+    version: u32,
+    fields: Vec<u32>,
+}
+
+impl StructVersion {
+    /// Field ends (relative to the fields block) with versions for
+    /// `ExtensibleHeader::check_version`
+    fn field_versions(
+        &self,
+        crate_name: &proc_macro2::TokenStream,
+        fields: &[(Member, &Type, Ident)],
+    ) -> proc_macro2::TokenStream {
+        let field_versions = fields.iter().enumerate().map(|(i, _)| {
+            let header_sizes = fields.iter().take(i + 1).map(|(_, ty, _)| {
+                quote! {
+                    <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE
+                }
+            });
+            let since = self.fields[i];
+            quote! {
+                (0 #( + #header_sizes )*, #since)
+            }
+        });
+        quote! {
+            &[#( #field_versions ),*]
+        }
+    }
+}
+
+/// Parses `#[codec(since = N)]` of the struct fields, versions must not decrease and can't be
+/// greater than the struct version
+fn parse_field_versions(fields: &Fields, version: u32) -> syn::Result<Vec<u32>> {
+    let mut since = 1;
+    let mut result = Vec::new();
+    for field in fields {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("codec"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("since") {
+                    let lit = meta.value()?.parse::<syn::LitInt>()?;
+                    let value: u32 = lit.base10_parse()?;
+                    if value < since || value > version {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "field version must be between the version of the previous field and the struct version",
+                        ));
+                    }
+                    since = value;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported codec field attribute"))
+                }
+            })?;
+        }
+        result.push(since);
+    }
+    Ok(result)
+}
+
+/// Parses `#[codec(...)]` attributes
+fn parse_codec_attrs(ast: &syn::DeriveInput) -> syn::Result<CodecAttrs> {
+    let mut attrs = CodecAttrs::default();
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("codec"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("solidity") {
+                attrs.solidity = true;
+                Ok(())
+            } else if meta.path.is_ident("view") {
+                attrs.view = true;
+                Ok(())
+            } else if meta.path.is_ident("reader") {
+                attrs.reader = true;
+                Ok(())
+            } else if meta.path.is_ident("schema") {
+                attrs.schema = true;
+                Ok(())
+            } else if meta.path.is_ident("version") {
+                let lit = meta.value()?.parse::<syn::LitInt>()?;
+                let version = lit.base10_parse()?;
+                if version == 0 {
+                    return Err(syn::Error::new_spanned(lit, "version must start from 1"));
+                }
+                attrs.version = Some(StructVersion {
+                    version,
+                    fields: Vec::new(),
+                });
+                Ok(())
+            } else {
+                Err(meta.error("unsupported codec attribute"))
+            }
+        })?;
+    }
+    match (&mut attrs.version, &ast.data) {
+        (Some(version), Data::Struct(data_struct)) => {
+            version.fields = parse_field_versions(&data_struct.fields, version.version)?;
+        }
+        (Some(_), _) => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "only structs can be versioned",
+            ));
+        }
+        (None, Data::Struct(data_struct)) => {
+            // `since` can't be used without struct version
+            parse_field_versions(&data_struct.fields, 0)?;
+        }
+        (None, _) => {}
+    }
+    if attrs.reader && !matches!(ast.data, Data::Struct(_)) {
+        return Err(syn::Error::new_spanned(
+            &ast.ident,
+            "only structs can have readers",
+        ));
+    }
+    Ok(attrs)
+}
+
+/// Struct is encoded as a Solidity tuple, so it's dynamic if at least one of its fields is
+/// dynamic. Function params (`abi_encode`) are encoded the same way as the tuple itself.
+fn impl_solidity_struct(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    fields: &Fields,
+) -> proc_macro2::TokenStream {
+    let fields = struct_fields(fields);
+    let types = fields.iter().map(|(_, ty, _)| *ty).collect::<Vec<_>>();
+    let members = fields
+        .iter()
+        .map(|(member, _, _)| member)
+        .collect::<Vec<_>>();
+    let sol_types = types.iter().enumerate().map(|(i, ty)| {
+        let separator = if i > 0 {
+            quote! { out.push(','); }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #separator
+            <#ty as #crate_name::SolidityCodec>::sol_type(out);
+        }
+    });
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #crate_name::SolidityCodec for #struct_name #type_generics #where_clause {
+            const IS_DYNAMIC: bool = false #( || <#types as #crate_name::SolidityCodec>::IS_DYNAMIC )*;
+            const HEAD_SIZE: usize = if Self::IS_DYNAMIC {
+                #crate_name::solidity::WORD_SIZE
+            } else {
+                0 #( + <#types as #crate_name::SolidityCodec>::HEAD_SIZE )*
+            };
+            fn sol_type(out: &mut #crate_name::solidity::String) {
+                out.push('(');
+                #( #sol_types )*
+                out.push(')');
+            }
+            #[allow(unused_mut)]
+            fn sol_encode(&self, out: &mut #crate_name::solidity::Vec<u8>) {
+                let mut encoder = #crate_name::SolidityTupleEncoder::new(
+                    0 #( + <#types as #crate_name::SolidityCodec>::HEAD_SIZE )*,
+                );
+                #( encoder.encode_field(&self.#members); )*
+                encoder.finalize(out);
+            }
+            #[allow(unused_mut, unused_variables)]
+            fn sol_decode(input: &[u8], offset: usize) -> core::result::Result<Self, #crate_name::CodecError> {
+                let mut decoder = #crate_name::SolidityTupleDecoder::new(input, offset);
+                Ok(Self {
+                    #( #members: decoder.decode_field()?, )*
+                })
+            }
+            fn abi_encode(&self) -> #crate_name::solidity::Vec<u8> {
+                let mut result = #crate_name::solidity::Vec::new();
+                self.sol_encode(&mut result);
+                result
+            }
+            fn abi_decode(input: &[u8]) -> core::result::Result<Self, #crate_name::CodecError> {
+                Self::sol_decode(input, 0)
+            }
+        }
+    }
+}
+
+/// Only fieldless enums are supported, they are encoded as `uint8` (like Solidity enums)
+fn impl_solidity_enum(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    data_enum: &DataEnum,
+) -> proc_macro2::TokenStream {
+    if let Some(variant) = data_enum
+        .variants
+        .iter()
+        .find(|variant| !variant.fields.is_empty())
+    {
+        return syn::Error::new_spanned(variant, "Solidity ABI supports only enums without fields")
+            .to_compile_error();
+    }
+    let variants = data_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let indices = (0..variants.len()).map(|i| i as u8).collect::<Vec<_>>();
+    let enum_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #crate_name::SolidityCodec for #enum_name #type_generics #where_clause {
+            const IS_DYNAMIC: bool = false;
+            const HEAD_SIZE: usize = #crate_name::solidity::WORD_SIZE;
+            fn sol_type(out: &mut #crate_name::solidity::String) {
+                out.push_str("uint8");
+            }
+            fn sol_encode(&self, out: &mut #crate_name::solidity::Vec<u8>) {
+                let variant_index: u8 = match self {
+                    #( Self::#variants => #indices, )*
+                };
+                <u8 as #crate_name::SolidityCodec>::sol_encode(&variant_index, out);
+            }
+            fn sol_decode(input: &[u8], offset: usize) -> core::result::Result<Self, #crate_name::CodecError> {
+                match <u8 as #crate_name::SolidityCodec>::sol_decode(input, offset)? {
+                    #( #indices => Ok(Self::#variants), )*
+                    variant_index => Err(#crate_name::CodecError::UnknownVariant(variant_index as u32)),
+                }
+            }
+        }
+    }
+}
+
+/// Schemas of the fields with names (`a` or `0`) and offsets relative to the struct or variant
+/// body, fields of versioned structs also have versions they were added in
+fn schema_fields(
+    crate_name: &proc_macro2::TokenStream,
+    fields: &[(Member, &Type, Ident)],
+    version: Option<&StructVersion>,
+) -> Vec<proc_macro2::TokenStream> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, (member, ty, _))| {
+            let name = match member {
+                Member::Named(ident) => ident.unraw().to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+            let field_offsets = fields.iter().take(i).map(|(_, ty, _)| {
+                quote! {
+                    <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE
+                }
+            });
+            let since = match version {
+                Some(version) => {
+                    let since = version.fields[i];
+                    quote! { Some(#since) }
+                }
+                None => quote! { None },
+            };
+            quote! {
+                #crate_name::schema::FieldSchema {
+                    name: #crate_name::schema::String::from(#name),
+                    offset: 0 #( + #field_offsets )*,
+                    since: #since,
+                    schema: <#ty as #crate_name::CodecSchema>::schema(),
+                }
+            }
+        })
+        .collect()
+}
+
+/// Implements `CodecSchema`, type params must implement `CodecSchema` too
+fn impl_schema(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    version: Option<&StructVersion>,
+) -> proc_macro2::TokenStream {
+    let name = ast.ident.unraw().to_string();
+    let schema = match &ast.data {
+        Data::Struct(data_struct) => {
+            let fields = schema_fields(crate_name, &struct_fields(&data_struct.fields), version);
+            let version = match version {
+                Some(version) => {
+                    let version = version.version;
+                    quote! { Some(#version) }
+                }
+                None => quote! { None },
+            };
+            quote! {
+                #crate_name::schema::TypeSchema::Struct(#crate_name::schema::StructSchema {
+                    name: #crate_name::schema::String::from(#name),
+                    version: #version,
+                    fields: #crate_name::schema::Vec::from([#( #fields ),*]),
+                })
+            }
+        }
+        Data::Enum(data_enum) => {
+            let variants = data_enum.variants.iter().map(|variant| {
+                let variant_name = variant.ident.unraw().to_string();
+                let fields = schema_fields(crate_name, &struct_fields(&variant.fields), None);
+                quote! {
+                    #crate_name::schema::VariantSchema {
+                        name: #crate_name::schema::String::from(#variant_name),
+                        fields: #crate_name::schema::Vec::from([#( #fields ),*]),
+                    }
+                }
+            });
+            quote! {
+                #crate_name::schema::TypeSchema::Enum(#crate_name::schema::EnumSchema {
+                    name: #crate_name::schema::String::from(#name),
+                    variants: #crate_name::schema::Vec::from([#( #variants ),*]),
+                })
+            }
+        }
+        Data::Union(_) => unreachable!(),
     };
-    let header_sizes = named_fields.named.iter().map(|field| {
-        let ty = &field.ty;
+    let mut generics = ast.generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for ident in type_params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote! { #ident: #crate_name::CodecSchema });
+    }
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #crate_name::CodecSchema for #struct_name #type_generics #where_clause {
+            fn schema() -> #crate_name::schema::TypeSchema {
+                #schema
+            }
+        }
+    }
+}
+
+/// Returns member (`a` or `0`) and type of each field, and name of the field's type inside
+/// `I{Struct}` trait (`A` for named fields and `Field0` for unnamed ones)
+fn struct_fields(fields: &Fields) -> Vec<(Member, &Type, Ident)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => {
+                let type_name = ident.to_string().to_case(Case::Pascal);
+                (
+                    Member::Named(ident.clone()),
+                    &field.ty,
+                    Ident::new(type_name.as_str(), ident.span()),
+                )
+            }
+            None => (
+                Member::Unnamed(Index::from(i)),
+                &field.ty,
+                format_ident!("Field{}", i),
+            ),
+        })
+        .collect()
+}
+
+/// Struct fields are encoded one by one. Versioned struct (`#[codec(version = N)]`) has a header
+/// with version, offset and length of the fields block (see `ExtensibleHeader`), fields that
+/// don't fit into the block are decoded as default values.
+fn impl_derive_struct(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    fields: &Fields,
+    version: Option<&StructVersion>,
+) -> TokenStream {
+    let fields = struct_fields(fields);
+    let header_sizes = fields
+        .iter()
+        .map(|(_, ty, _)| {
+            quote! {
+                <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE
+            }
+        })
+        .collect::<Vec<_>>();
+    let encode_types = fields.iter().map(|(member, ty, _)| {
         quote! {
-            <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE
+            self.#member.encode(encoder, field_offset);
+            field_offset += <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE;
         }
     });
-    let encode_types = named_fields.named.iter().map(|field| {
-        let ident = field.ident.as_ref().unwrap();
-        let ty = &field.ty;
+    // versioned structs check that the field is present in the fields block
+    let if_present = |ty: &Type, decode: proc_macro2::TokenStream, member: &Member| {
+        if version.is_some() {
+            quote! {
+                if header.has_field(field_offset - header.fields_offset + <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE) {
+                    #decode
+                } else {
+                    result.#member = Default::default();
+                }
+            }
+        } else {
+            decode
+        }
+    };
+    let decode_types = fields.iter().map(|(member, ty, _)| {
+        let decode = if_present(
+            ty,
+            quote! {
+                <#ty as #crate_name::Encoder<#ty>>::decode_body(decoder, field_offset, &mut result.#member);
+            },
+            member,
+        );
         quote! {
-            self.#ident.encode(encoder, field_offset);
+            #decode
             field_offset += <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE;
         }
     });
-    let decode_types = named_fields.named.iter().map(|field| {
-        let ident = field.ident.as_ref().unwrap();
-        let ty = &field.ty;
+    let try_decode_types = fields.iter().map(|(member, ty, _)| {
+        let decode = if_present(
+            ty,
+            quote! {
+                <#ty as #crate_name::Encoder<#ty>>::try_decode_body(decoder, field_offset, &mut result.#member)?;
+            },
+            member,
+        );
         quote! {
-            <#ty as #crate_name::Encoder<#ty>>::decode_body(decoder, field_offset, &mut result.#ident);
+            #decode
             field_offset += <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE;
         }
     });
-    let impl_types = named_fields.named.iter().map(|field| {
-        let ident = field.ident.as_ref().unwrap();
-        let span = ident.span();
-        let ident = ident.to_string().to_case(Case::Pascal);
-        let ident = Ident::new(ident.as_str(), span);
+    let impl_types = fields.iter().map(|(_, _, type_name)| {
         quote! {
-            type #ident;
+            type #type_name;
         }
     });
-    let field_names = named_fields.named.clone();
-    let impl_defs = named_fields.named.iter().enumerate().map(|(i, field)| {
-        let ident = field.ident.as_ref().unwrap();
-        let span = ident.span();
-        let ident = ident.to_string().to_case(Case::Pascal);
-        let ident = ident.to_string().to_case(Case::Pascal);
-        let ident = Ident::new(ident.as_str(), span);
-        let sum_of_field_offsets = field_names.iter().take(i).map(|field| {
-            let ty = &field.ty;
-            quote! {
-                <#ty as #crate_name::Encoder<#ty>>::HEADER_SIZE
-            }
-        });
-        let ty = &field.ty;
+    // fields block of a top-level versioned struct starts right after the header
+    let first_field_offset = match version {
+        Some(_) => quote! { #crate_name::ExtensibleHeader::SIZE },
+        None => quote! { 0 },
+    };
+    let impl_defs = fields.iter().enumerate().map(|(i, (_, ty, type_name))| {
+        let sum_of_field_offsets = &header_sizes[..i];
         quote! {
-            type #ident = #crate_name::FieldEncoder<#ty, { 0 #( +#sum_of_field_offsets )* }>;
+            type #type_name = #crate_name::FieldEncoder<#ty, { #first_field_offset #( +#sum_of_field_offsets )* }>;
         }
     });
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
     let i_struct_name = format_ident!("I{}", ast.ident);
-    let output = quote! {
-        impl #impl_generics #crate_name::Encoder<#struct_name #type_generics> for #struct_name #type_generics #where_clause {
+    let codec_impl = match version {
+        Some(version) => {
+            let field_versions = version.field_versions(crate_name, &fields);
+            let version = version.version;
+            quote! {
+                const HEADER_SIZE: usize = #crate_name::ExtensibleHeader::SIZE;
+                #[allow(unused_mut, unused_variables)]
+                fn encode<W: #crate_name::WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
+                    let mut field_offset = #crate_name::ExtensibleHeader::encode(encoder, field_offset, #version, 0 #( + #header_sizes)*);
+                    #( #encode_types; )*
+                }
+                #[allow(unused_mut, unused_variables)]
+                fn decode_header(decoder: &mut #crate_name::BufferDecoder, field_offset: usize, result: &mut #struct_name #type_generics) -> (usize, usize) {
+                    let header = #crate_name::ExtensibleHeader::decode(decoder, field_offset);
+                    header.check_version(#field_versions).expect("invalid struct version");
+                    let mut field_offset = header.fields_offset;
+                    #( #decode_types; )*
+                    (header.fields_offset, header.fields_length)
+                }
+                #[allow(unused_mut, unused_variables)]
+                fn try_decode_body(decoder: &mut #crate_name::BufferDecoder, field_offset: usize, result: &mut #struct_name #type_generics) -> core::result::Result<(), #crate_name::CodecError> {
+                    let header = #crate_name::ExtensibleHeader::try_decode(decoder, field_offset)?;
+                    header.check_version(#field_versions)?;
+                    if header.fields_length > 0 #( + #header_sizes)* {
+                        // bodies of fields added by newer versions can't be located
+                        decoder.consume_all();
+                    }
+                    let mut field_offset = header.fields_offset;
+                    #( #try_decode_types; )*
+                    Ok(())
+                }
+            }
+        }
+        None => quote! {
             const HEADER_SIZE: usize = 0 #( + #header_sizes)*;
+            #[allow(unused_mut, unused_variables)]
             fn encode<W: #crate_name::WritableBuffer>(&self, encoder: &mut W, mut field_offset: usize) {
                 #( #encode_types; )*
             }
+            #[allow(unused_mut, unused_variables)]
             fn decode_header(decoder: &mut #crate_name::BufferDecoder, mut field_offset: usize, result: &mut #struct_name #type_generics) -> (usize, usize) {
                 #( #decode_types; )*
                 (0, 0)
             }
+            #[allow(unused_mut, unused_variables)]
+            fn try_decode_body(decoder: &mut #crate_name::BufferDecoder, mut field_offset: usize, result: &mut #struct_name #type_generics) -> core::result::Result<(), #crate_name::CodecError> {
+                #( #try_decode_types; )*
+                Ok(())
+            }
+        },
+    };
+    let output = quote! {
+        impl #impl_generics #crate_name::Encoder<#struct_name #type_generics> for #struct_name #type_generics #where_clause {
+            #codec_impl
         }
         pub trait #i_struct_name {
             #( #impl_types )*
@@ -106,7 +1061,113 @@ fn impl_derive_codec(ast: &syn::DeriveInput) -> TokenStream {
     TokenStream::from(output)
 }
 
-#[proc_macro_derive(Codec)]
+/// Enum is encoded as a variant index (u32) followed by offset and length of the variant body,
+/// body contains variant fields encoded the same way as struct fields. Variant index is a
+/// position of the variant in the enum (explicit discriminants are ignored).
+fn impl_derive_enum(
+    crate_name: &proc_macro2::TokenStream,
+    ast: &syn::DeriveInput,
+    data_enum: &DataEnum,
+) -> TokenStream {
+    let enum_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    let mut encode_variants = Vec::new();
+    let mut decode_variants = Vec::new();
+    let mut try_decode_variants = Vec::new();
+    for (i, variant) in data_enum.variants.iter().enumerate() {
+        let variant_index = i as u32;
+        let variant_name = &variant.ident;
+        let fields = struct_fields(&variant.fields);
+        let bindings = (0..fields.len())
+            .map(|i| format_ident!("field{}", i))
+            .collect::<Vec<_>>();
+        let members = fields
+            .iter()
+            .map(|(member, _, _)| member)
+            .collect::<Vec<_>>();
+        let types = fields.iter().map(|(_, ty, _)| *ty).collect::<Vec<_>>();
+        let pattern = quote! {
+            Self::#variant_name { #( #members: #bindings ),* }
+        };
+        encode_variants.push(quote! {
+            #pattern => {
+                let mut body_encoder = #crate_name::BufferEncoder::new(
+                    0 #( + <#types as #crate_name::Encoder<#types>>::HEADER_SIZE )*,
+                    None,
+                );
+                let mut body_offset = 0;
+                #(
+                    #bindings.encode(&mut body_encoder, body_offset);
+                    body_offset += <#types as #crate_name::Encoder<#types>>::HEADER_SIZE;
+                )*
+                (#variant_index, body_encoder.finalize())
+            }
+        });
+        decode_variants.push(quote! {
+            #variant_index => {
+                let mut body_offset = 0;
+                #(
+                    let mut #bindings: #types = Default::default();
+                    <#types as #crate_name::Encoder<#types>>::decode_body(&mut body_decoder, body_offset, &mut #bindings);
+                    body_offset += <#types as #crate_name::Encoder<#types>>::HEADER_SIZE;
+                )*
+                #pattern
+            }
+        });
+        try_decode_variants.push(quote! {
+            #variant_index => {
+                let mut body_offset = 0;
+                #(
+                    let mut #bindings: #types = Default::default();
+                    <#types as #crate_name::Encoder<#types>>::try_decode_body(&mut body_decoder, body_offset, &mut #bindings)?;
+                    body_offset += <#types as #crate_name::Encoder<#types>>::HEADER_SIZE;
+                )*
+                #pattern
+            }
+        });
+    }
+    let output = quote! {
+        impl #impl_generics #crate_name::Encoder<#enum_name #type_generics> for #enum_name #type_generics #where_clause {
+            // u32: variant index + body (offset and length)
+            const HEADER_SIZE: usize = core::mem::size_of::<u32>() * 3;
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn encode<W: #crate_name::WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
+                let (variant_index, body): (u32, _) = match self {
+                    #( #encode_variants )*
+                };
+                encoder.write_u32(field_offset, variant_index);
+                encoder.write_bytes(field_offset + 4, body.as_slice());
+            }
+            fn decode_header(decoder: &mut #crate_name::BufferDecoder, field_offset: usize, _result: &mut #enum_name #type_generics) -> (usize, usize) {
+                decoder.read_bytes_header(field_offset + 4)
+            }
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn decode_body(decoder: &mut #crate_name::BufferDecoder, field_offset: usize, result: &mut #enum_name #type_generics) {
+                let variant_index = decoder.read_u32(field_offset);
+                let body = decoder.read_bytes(field_offset + 4);
+                let mut body_decoder = #crate_name::BufferDecoder::new(body);
+                *result = match variant_index {
+                    #( #decode_variants )*
+                    _ => panic!("unknown enum variant: {}", variant_index),
+                };
+            }
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn try_decode_body(decoder: &mut #crate_name::BufferDecoder, field_offset: usize, result: &mut #enum_name #type_generics) -> core::result::Result<(), #crate_name::CodecError> {
+                let variant_index = decoder.try_read_u32(field_offset)?;
+                let body = decoder.try_read_bytes(field_offset + 4)?;
+                let mut body_decoder = #crate_name::BufferDecoder::new(body);
+                *result = match variant_index {
+                    #( #try_decode_variants )*
+                    _ => return Err(#crate_name::CodecError::UnknownVariant(variant_index)),
+                };
+                Ok(())
+            }
+        }
+    };
+    TokenStream::from(output)
+}
+
+#[proc_macro_derive(Codec, attributes(codec))]
 pub fn codec_macro_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     impl_derive_codec(&ast)
@@ -0,0 +1,140 @@
+use crate::{buffer::WritableBuffer, BufferDecoder, BufferEncoder, CodecError, Encoder};
+use alloc::collections::{BTreeMap, BTreeSet};
+
+/// Ordered map has the same layout as `HashMap`, so both maps with the same entries are
+/// encoded into the same bytes
+impl<K: Default + Sized + Encoder<K> + Ord, V: Default + Sized + Encoder<V>> Encoder<BTreeMap<K, V>>
+    for BTreeMap<K, V>
+{
+    // length + keys (bytes) + values (bytes)
+    const HEADER_SIZE: usize = 4 + 8 + 8;
+
+    fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
+        // encode length
+        encoder.write_u32(field_offset, self.len() as u32);
+        // encode keys, entries are already sorted
+        let mut key_encoder = BufferEncoder::new(K::HEADER_SIZE * self.len(), None);
+        for (i, obj) in self.keys().enumerate() {
+            obj.encode(&mut key_encoder, K::HEADER_SIZE * i);
+        }
+        encoder.write_bytes(field_offset + 4, key_encoder.finalize().as_slice());
+        // encode values
+        let mut value_encoder = BufferEncoder::new(V::HEADER_SIZE * self.len(), None);
+        for (i, obj) in self.values().enumerate() {
+            obj.encode(&mut value_encoder, V::HEADER_SIZE * i);
+        }
+        encoder.write_bytes(field_offset + 12, value_encoder.finalize().as_slice());
+    }
+
+    fn decode_header(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        _result: &mut BTreeMap<K, V>,
+    ) -> (usize, usize) {
+        let (keys_offset, keys_length) = decoder.read_bytes_header(field_offset + 4);
+        let (_, values_length) = decoder.read_bytes_header(field_offset + 12);
+        // sum of keys and values are total body length
+        (keys_offset, keys_length + values_length)
+    }
+
+    fn decode_body(decoder: &mut BufferDecoder, field_offset: usize, result: &mut BTreeMap<K, V>) {
+        // decode length, keys and values
+        let length = decoder.read_u32(field_offset) as usize;
+        let (key_bytes, value_bytes) = decoder.read_bytes2(field_offset + 4, field_offset + 12);
+        // decode keys
+        let mut key_decoder = BufferDecoder::new(key_bytes);
+        let keys = (0..length).map(|i| {
+            let mut result = Default::default();
+            K::decode_body(&mut key_decoder, K::HEADER_SIZE * i, &mut result);
+            result
+        });
+        // decode values
+        let mut value_decoder = BufferDecoder::new(value_bytes);
+        let values = (0..length).map(|i| {
+            let mut result = Default::default();
+            V::decode_body(&mut value_decoder, V::HEADER_SIZE * i, &mut result);
+            result
+        });
+        // zip into map
+        *result = keys.zip(values).collect()
+    }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut BTreeMap<K, V>,
+    ) -> Result<(), CodecError> {
+        // decode length, keys and values
+        let length = decoder.try_read_u32(field_offset)? as usize;
+        let key_bytes = decoder.try_read_array(field_offset + 4, length, K::HEADER_SIZE)?;
+        let value_bytes = decoder.try_read_array(field_offset + 12, length, V::HEADER_SIZE)?;
+        let mut key_decoder = BufferDecoder::new(key_bytes);
+        let mut value_decoder = BufferDecoder::new(value_bytes);
+        result.clear();
+        for i in 0..length {
+            let mut key = K::default();
+            K::try_decode_body(&mut key_decoder, K::HEADER_SIZE * i, &mut key)?;
+            let mut value = V::default();
+            V::try_decode_body(&mut value_decoder, V::HEADER_SIZE * i, &mut value)?;
+            result.insert(key, value);
+        }
+        Ok(())
+    }
+}
+
+/// Ordered set has the same layout as `HashSet`
+impl<T: Default + Sized + Encoder<T> + Ord> Encoder<BTreeSet<T>> for BTreeSet<T> {
+    // length + values (bytes)
+    const HEADER_SIZE: usize = 4 + 8;
+
+    fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
+        // encode length
+        encoder.write_u32(field_offset, self.len() as u32);
+        // encode values, set is already sorted
+        let mut value_encoder = BufferEncoder::new(T::HEADER_SIZE * self.len(), None);
+        for (i, obj) in self.iter().enumerate() {
+            obj.encode(&mut value_encoder, T::HEADER_SIZE * i);
+        }
+        encoder.write_bytes(field_offset + 4, value_encoder.finalize().as_slice());
+    }
+
+    fn decode_header(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        _result: &mut BTreeSet<T>,
+    ) -> (usize, usize) {
+        decoder.read_bytes_header(field_offset + 4)
+    }
+
+    fn decode_body(decoder: &mut BufferDecoder, field_offset: usize, result: &mut BTreeSet<T>) {
+        // decode length and values
+        let length = decoder.read_u32(field_offset) as usize;
+        let value_bytes = decoder.read_bytes(field_offset + 4);
+        let mut value_decoder = BufferDecoder::new(value_bytes);
+        *result = (0..length)
+            .map(|i| {
+                let mut result = Default::default();
+                T::decode_body(&mut value_decoder, T::HEADER_SIZE * i, &mut result);
+                result
+            })
+            .collect()
+    }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut BTreeSet<T>,
+    ) -> Result<(), CodecError> {
+        // decode length and values
+        let length = decoder.try_read_u32(field_offset)? as usize;
+        let value_bytes = decoder.try_read_array(field_offset + 4, length, T::HEADER_SIZE)?;
+        let mut value_decoder = BufferDecoder::new(value_bytes);
+        result.clear();
+        for i in 0..length {
+            let mut value = T::default();
+            T::try_decode_body(&mut value_decoder, T::HEADER_SIZE * i, &mut value)?;
+            result.insert(value);
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
+use crate::CodecError;
 use alloc::vec::Vec;
 use byteorder::{ByteOrder, LittleEndian};
+use core::cell::Cell;
 use paste::paste;
 
 pub trait WritableBuffer {
@@ -11,7 +13,12 @@ pub trait WritableBuffer {
     fn write_u32(&mut self, field_offset: usize, value: u32) -> usize;
     fn write_i64(&mut self, field_offset: usize, value: i64) -> usize;
     fn write_u64(&mut self, field_offset: usize, value: u64) -> usize;
+    fn write_i128(&mut self, field_offset: usize, value: i128) -> usize;
+    fn write_u128(&mut self, field_offset: usize, value: u128) -> usize;
     fn write_bytes(&mut self, field_offset: usize, bytes: &[u8]) -> usize;
+    /// Writes header of `length` zero bytes (like `write_bytes`) and returns offset of these
+    /// bytes, so they can be filled later
+    fn reserve_bytes(&mut self, field_offset: usize, length: usize) -> usize;
 }
 
 macro_rules! encode_le_int {
@@ -72,6 +79,8 @@ impl<const N: usize> WritableBuffer for FixedEncoder<N> {
     encode_le_int!(i32);
     encode_le_int!(u64);
     encode_le_int!(i64);
+    encode_le_int!(u128);
+    encode_le_int!(i128);
 
     fn write_bytes(&mut self, field_offset: usize, bytes: &[u8]) -> usize {
         let data_offset = self.len();
@@ -84,6 +93,15 @@ impl<const N: usize> WritableBuffer for FixedEncoder<N> {
         self.body_length += bytes.len();
         8
     }
+
+    fn reserve_bytes(&mut self, field_offset: usize, length: usize) -> usize {
+        let data_offset = self.len();
+        self.write_u32(field_offset + 0, data_offset as u32);
+        self.write_u32(field_offset + 4, length as u32);
+        // buffer is already filled with zeros
+        self.body_length += length;
+        data_offset
+    }
 }
 
 #[derive(Default)]
@@ -119,6 +137,8 @@ impl WritableBuffer for BufferEncoder {
     encode_le_int!(i32);
     encode_le_int!(u64);
     encode_le_int!(i64);
+    encode_le_int!(u128);
+    encode_le_int!(i128);
 
     fn write_bytes(&mut self, field_offset: usize, bytes: &[u8]) -> usize {
         let data_offset = self.buffer.len();
@@ -130,18 +150,64 @@ impl WritableBuffer for BufferEncoder {
         self.buffer.extend(bytes);
         8
     }
+
+    fn reserve_bytes(&mut self, field_offset: usize, length: usize) -> usize {
+        let data_offset = self.buffer.len();
+        self.write_u32(field_offset + 0, data_offset as u32);
+        self.write_u32(field_offset + 4, length as u32);
+        self.buffer.resize(data_offset + length, 0);
+        data_offset
+    }
+}
+
+/// Source of encoded bytes that can be read at any offset, f.e. memory buffer or contract
+/// input/output available through syscalls
+pub trait ByteSource {
+    fn read(&self, target: &mut [u8], offset: usize);
+}
+
+impl ByteSource for [u8] {
+    fn read(&self, target: &mut [u8], offset: usize) {
+        // bytes outside of the slice are read as zeros, the same way `sys_read` does for input
+        let available = self.len().saturating_sub(offset).min(target.len());
+        if available > 0 {
+            target[..available].copy_from_slice(&self[offset..(offset + available)]);
+        }
+        target[available..].fill(0);
+    }
+}
+
+impl<T: ByteSource + ?Sized> ByteSource for &T {
+    fn read(&self, target: &mut [u8], offset: usize) {
+        (**self).read(target, offset)
+    }
 }
 
 #[derive(Default)]
 pub struct BufferDecoder<'a> {
     buffer: &'a [u8],
+    // offset of the first byte of the buffer in the encoded input, offsets are always absolute
+    base: usize,
+    // the furthest byte touched by `try_*` reads, used to detect trailing data
+    consumed: Cell<usize>,
 }
 
 macro_rules! decode_le_int {
     ($typ:ty) => {
         paste! {
             pub fn [<read_ $typ>](&self, field_offset: usize) -> $typ {
-                LittleEndian::[<read_ $typ>](&self.buffer[field_offset..])
+                LittleEndian::[<read_ $typ>](&self.buffer[(field_offset - self.base)..])
+            }
+        }
+    };
+}
+
+macro_rules! try_decode_le_int {
+    ($typ:ty) => {
+        paste! {
+            pub fn [<try_read_ $typ>](&self, field_offset: usize) -> Result<$typ, CodecError> {
+                let bytes = self.try_read_slice(field_offset, core::mem::size_of::<$typ>())?;
+                Ok(LittleEndian::[<read_ $typ>](bytes))
             }
         }
     };
@@ -149,14 +215,24 @@ macro_rules! decode_le_int {
 
 impl<'a> BufferDecoder<'a> {
     pub fn new(input: &'a [u8]) -> Self {
-        Self { buffer: input }
+        Self::new_at(input, 0)
+    }
+
+    /// Creates a decoder for a part of the encoded input that starts at `base`, offsets passed to
+    /// the decoder and stored in headers are still relative to the whole input
+    pub fn new_at(input: &'a [u8], base: usize) -> Self {
+        Self {
+            buffer: input,
+            base,
+            consumed: Cell::new(0),
+        }
     }
 
     pub fn read_i8(&mut self, field_offset: usize) -> i8 {
-        self.buffer[field_offset] as i8
+        self.buffer[field_offset - self.base] as i8
     }
     pub fn read_u8(&mut self, field_offset: usize) -> u8 {
-        self.buffer[field_offset]
+        self.buffer[field_offset - self.base]
     }
 
     decode_le_int!(i16);
@@ -165,6 +241,8 @@ impl<'a> BufferDecoder<'a> {
     decode_le_int!(u32);
     decode_le_int!(i64);
     decode_le_int!(u64);
+    decode_le_int!(i128);
+    decode_le_int!(u128);
 
     pub fn read_bytes_header(&self, field_offset: usize) -> (usize, usize) {
         let bytes_offset = self.read_u32(field_offset + 0) as usize;
@@ -174,6 +252,7 @@ impl<'a> BufferDecoder<'a> {
 
     pub fn read_bytes(&self, field_offset: usize) -> &[u8] {
         let (bytes_offset, bytes_length) = self.read_bytes_header(field_offset);
+        let bytes_offset = bytes_offset - self.base;
         &self.buffer[bytes_offset..(bytes_offset + bytes_length)]
     }
 
@@ -183,6 +262,79 @@ impl<'a> BufferDecoder<'a> {
             self.read_bytes(field2_offset),
         )
     }
+
+    /// Returns the end of the furthest range read by `try_*` functions (relative to the buffer)
+    pub fn consumed(&self) -> usize {
+        self.consumed.get()
+    }
+
+    /// Marks the whole buffer as consumed, it's used when input contains data that can't be
+    /// located by the decoder (f.e. bodies of fields unknown to an older struct version)
+    pub fn consume_all(&self) {
+        self.consumed.set(self.buffer.len());
+    }
+
+    pub fn try_read_slice(&self, offset: usize, length: usize) -> Result<&'a [u8], CodecError> {
+        let start = offset
+            .checked_sub(self.base)
+            .ok_or(CodecError::OutOfBounds { offset, length })?;
+        let end = start
+            .checked_add(length)
+            .ok_or(CodecError::LengthOverflow)?;
+        let bytes = self
+            .buffer
+            .get(start..end)
+            .ok_or(CodecError::OutOfBounds { offset, length })?;
+        if end > self.consumed.get() {
+            self.consumed.set(end);
+        }
+        Ok(bytes)
+    }
+
+    pub fn try_read_i8(&self, field_offset: usize) -> Result<i8, CodecError> {
+        Ok(self.try_read_slice(field_offset, 1)?[0] as i8)
+    }
+    pub fn try_read_u8(&self, field_offset: usize) -> Result<u8, CodecError> {
+        Ok(self.try_read_slice(field_offset, 1)?[0])
+    }
+
+    try_decode_le_int!(i16);
+    try_decode_le_int!(u16);
+    try_decode_le_int!(i32);
+    try_decode_le_int!(u32);
+    try_decode_le_int!(i64);
+    try_decode_le_int!(u64);
+    try_decode_le_int!(i128);
+    try_decode_le_int!(u128);
+
+    pub fn try_read_bytes_header(&self, field_offset: usize) -> Result<(usize, usize), CodecError> {
+        let bytes_offset = self.try_read_u32(field_offset)? as usize;
+        let bytes_length = self.try_read_u32(field_offset + 4)? as usize;
+        Ok((bytes_offset, bytes_length))
+    }
+
+    pub fn try_read_bytes(&self, field_offset: usize) -> Result<&'a [u8], CodecError> {
+        let (bytes_offset, bytes_length) = self.try_read_bytes_header(field_offset)?;
+        self.try_read_slice(bytes_offset, bytes_length)
+    }
+
+    /// Reads bytes of an array with `count` elements and makes sure that the body is large
+    /// enough to fit all element headers, it protects from allocating memory for fake counts
+    pub fn try_read_array(
+        &self,
+        field_offset: usize,
+        count: usize,
+        item_size: usize,
+    ) -> Result<&'a [u8], CodecError> {
+        let bytes = self.try_read_bytes(field_offset)?;
+        let length = count
+            .checked_mul(item_size)
+            .ok_or(CodecError::LengthOverflow)?;
+        if length > bytes.len() {
+            return Err(CodecError::OutOfBounds { offset: 0, length });
+        }
+        Ok(bytes)
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::{BufferDecoder, Encoder, WritableBuffer};
+use crate::{BufferDecoder, CodecError, Encoder, WritableBuffer};
 use fluentbase_codec_derive::Codec;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
@@ -23,6 +23,20 @@ impl Encoder<EmptyVec> for EmptyVec {
         debug_assert_eq!(count, 0);
         decoder.read_bytes_header(field_offset + 4)
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        _result: &mut EmptyVec,
+    ) -> Result<(), CodecError> {
+        // empty vector can't have elements, so its body must be empty too
+        let count = decoder.try_read_u32(field_offset)? as usize;
+        let bytes = decoder.try_read_bytes(field_offset + 4)?;
+        if count != 0 || !bytes.is_empty() {
+            return Err(CodecError::TrailingData(bytes.len()));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
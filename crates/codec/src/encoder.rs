@@ -1,5 +1,8 @@
-use crate::buffer::{BufferDecoder, BufferEncoder, FixedEncoder, WritableBuffer};
-use alloc::vec::Vec;
+use crate::{
+    buffer::{BufferDecoder, BufferEncoder, ByteSource, FixedEncoder, WritableBuffer},
+    CodecError,
+};
+use alloc::{vec, vec::Vec};
 use core::marker::PhantomData;
 
 pub trait Encoder<T: Sized> {
@@ -31,14 +34,55 @@ pub trait Encoder<T: Sized> {
     fn decode_body(decoder: &mut BufferDecoder, field_offset: usize, result: &mut T) {
         Self::decode_header(decoder, field_offset, result);
     }
+
+    /// Same as `decode_body`, but validates input instead of panicking on malformed data
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut T,
+    ) -> Result<(), CodecError>;
+
+    /// Decodes value from an untrusted buffer, buffer must contain exactly one encoded value
+    fn try_decode(buffer: &[u8]) -> Result<T, CodecError>
+    where
+        T: Default,
+    {
+        if buffer.len() < Self::HEADER_SIZE {
+            return Err(CodecError::OutOfBounds {
+                offset: 0,
+                length: Self::HEADER_SIZE,
+            });
+        }
+        let mut decoder = BufferDecoder::new(buffer);
+        let mut result = T::default();
+        Self::try_decode_body(&mut decoder, 0, &mut result)?;
+        // header is always a part of the value, even if some of its fields are not read
+        let consumed = decoder.consumed().max(Self::HEADER_SIZE);
+        if buffer.len() > consumed {
+            return Err(CodecError::TrailingData(buffer.len() - consumed));
+        }
+        Ok(result)
+    }
 }
 
+/// Max header size of a field that can be read from a `ByteSource` by `FieldEncoder::read_field_*`
+pub const MAX_READ_FIELD_SIZE: usize = 256;
+
 pub struct FieldEncoder<T: Sized + Encoder<T>, const FIELD_OFFSET: usize>(PhantomData<T>);
 
 impl<T: Sized + Encoder<T>, const FIELD_OFFSET: usize> FieldEncoder<T, FIELD_OFFSET> {
     pub const FIELD_OFFSET: usize = FIELD_OFFSET;
     pub const FIELD_SIZE: usize = T::HEADER_SIZE;
 
+    // field size checked at compile time to fit into the stack buffer of `read_field_*`
+    const READ_FIELD_SIZE: usize = {
+        assert!(
+            T::HEADER_SIZE <= MAX_READ_FIELD_SIZE,
+            "field header is too big to be read from a byte source"
+        );
+        T::HEADER_SIZE
+    };
+
     pub fn decode_field_header(buffer: &[u8], result: &mut T) -> (usize, usize) {
         Self::decode_field_header_at(buffer, Self::FIELD_OFFSET, result)
     }
@@ -60,4 +104,71 @@ impl<T: Sized + Encoder<T>, const FIELD_OFFSET: usize> FieldEncoder<T, FIELD_OFF
         let mut buffer_decoder = BufferDecoder::new(buffer);
         T::decode_body(&mut buffer_decoder, field_offset, result)
     }
+
+    /// Reads and decodes only the field header from the source, returns offset and length of
+    /// the field body (zeros for static fields)
+    pub fn read_field_header<R: ByteSource + ?Sized>(source: &R, result: &mut T) -> (usize, usize) {
+        Self::read_field_header_at(source, Self::FIELD_OFFSET, result)
+    }
+
+    pub fn read_field_header_at<R: ByteSource + ?Sized>(
+        source: &R,
+        field_offset: usize,
+        result: &mut T,
+    ) -> (usize, usize) {
+        let mut header = [0u8; MAX_READ_FIELD_SIZE];
+        let header = &mut header[..Self::READ_FIELD_SIZE];
+        source.read(header, field_offset);
+        Self::decode_field_header_at(header, 0, result)
+    }
+
+    /// Reads the field header and, for dynamic fields, its body from the source, other fields
+    /// are not read. Offset and length of the body are untrusted, so the body is decoded with
+    /// bounds checks
+    pub fn read_field_body<R: ByteSource + ?Sized>(
+        source: &R,
+        result: &mut T,
+    ) -> Result<(), CodecError> {
+        Self::read_field_body_at(source, Self::FIELD_OFFSET, result)
+    }
+
+    pub fn read_field_body_at<R: ByteSource + ?Sized>(
+        source: &R,
+        field_offset: usize,
+        result: &mut T,
+    ) -> Result<(), CodecError> {
+        let mut header = [0u8; MAX_READ_FIELD_SIZE];
+        let header = &mut header[..Self::READ_FIELD_SIZE];
+        source.read(header, field_offset);
+        let (offset, length) = Self::decode_field_header_at(header, 0, result);
+        if length == 0 {
+            return Ok(());
+        }
+        // only the body is read, the header is placed right after it, so the decoder that starts
+        // at the body offset can resolve absolute offsets stored in the header
+        let header_offset = offset
+            .checked_add(length)
+            .ok_or(CodecError::LengthOverflow)?;
+        let buffer_length = length
+            .checked_add(Self::FIELD_SIZE)
+            .ok_or(CodecError::LengthOverflow)?;
+        let mut buffer = vec![0u8; buffer_length];
+        source.read(&mut buffer[..length], offset);
+        buffer[length..].copy_from_slice(header);
+        let mut buffer_decoder = BufferDecoder::new_at(&buffer, offset);
+        T::try_decode_body(&mut buffer_decoder, header_offset, result)
+    }
+
+    pub fn try_decode_field_body(buffer: &[u8], result: &mut T) -> Result<(), CodecError> {
+        Self::try_decode_field_body_at(buffer, Self::FIELD_OFFSET, result)
+    }
+
+    pub fn try_decode_field_body_at(
+        buffer: &[u8],
+        field_offset: usize,
+        result: &mut T,
+    ) -> Result<(), CodecError> {
+        let mut buffer_decoder = BufferDecoder::new(buffer);
+        T::try_decode_body(&mut buffer_decoder, field_offset, result)
+    }
 }
@@ -0,0 +1,42 @@
+use core::fmt::{self, Display, Formatter};
+
+/// Errors returned by fallible decoding (`Encoder::try_decode`), input is treated as untrusted
+/// and every offset, length and value is validated before use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecError {
+    /// Requested range is outside the input buffer
+    OutOfBounds { offset: usize, length: usize },
+    /// Offset plus length (or elements count times element size) doesn't fit into `usize`
+    LengthOverflow,
+    /// Boolean (or option flag) is neither 0 nor 1
+    InvalidBool(u8),
+    /// Input has unused bytes after the decoded value
+    TrailingData(usize),
+    /// Enum variant index doesn't match any variant
+    UnknownVariant(u32),
+    /// String is not a valid UTF-8
+    InvalidUtf8,
+    /// Solidity ABI word has non-zero padding, value doesn't fit into the type or unused high
+    /// bits of `Uint` are set
+    InvalidPadding,
+    /// Version of the extensible struct is 0 or lower than the version that introduced the
+    /// fields present in the input
+    InvalidVersion(u32),
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::OutOfBounds { offset, length } => {
+                write!(f, "out of bounds: offset={}, length={}", offset, length)
+            }
+            CodecError::LengthOverflow => write!(f, "length overflow"),
+            CodecError::InvalidBool(value) => write!(f, "invalid bool value: {}", value),
+            CodecError::TrailingData(length) => write!(f, "trailing data: {} bytes", length),
+            CodecError::UnknownVariant(index) => write!(f, "unknown enum variant: {}", index),
+            CodecError::InvalidUtf8 => write!(f, "invalid utf-8"),
+            CodecError::InvalidPadding => write!(f, "invalid padding"),
+            CodecError::InvalidVersion(version) => write!(f, "invalid struct version: {}", version),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::{buffer::WritableBuffer, BufferDecoder, Encoder};
-use alloy_primitives::{Address, Bytes, FixedBytes, Uint};
+use crate::{buffer::WritableBuffer, BufferDecoder, CodecError, Encoder};
+use alloy_primitives::{Address, Bytes, FixedBytes, Signed, Uint};
 
 impl Encoder<Bytes> for Bytes {
     const HEADER_SIZE: usize = core::mem::size_of::<u32>() * 2;
@@ -20,6 +20,16 @@ impl Encoder<Bytes> for Bytes {
         let bytes = decoder.read_bytes(field_offset);
         *result = Bytes::copy_from_slice(bytes);
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Bytes,
+    ) -> Result<(), CodecError> {
+        let bytes = decoder.try_read_bytes(field_offset)?;
+        *result = Bytes::copy_from_slice(bytes);
+        Ok(())
+    }
 }
 
 impl<const N: usize> Encoder<FixedBytes<N>> for FixedBytes<N> {
@@ -35,6 +45,15 @@ impl<const N: usize> Encoder<FixedBytes<N>> for FixedBytes<N> {
         <[u8; N]>::decode_body(decoder, field_offset, &mut result.0);
         (0, 0)
     }
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut FixedBytes<N>,
+    ) -> Result<(), CodecError> {
+        let bytes = decoder.try_read_slice(field_offset, N)?;
+        result.0.copy_from_slice(bytes);
+        Ok(())
+    }
 }
 
 macro_rules! impl_evm_fixed {
@@ -56,25 +75,94 @@ macro_rules! impl_evm_fixed {
                 );
                 (0, 0)
             }
+            fn try_decode_body(
+                decoder: &mut BufferDecoder,
+                field_offset: usize,
+                result: &mut $typ,
+            ) -> Result<(), CodecError> {
+                FixedBytes::<{ Self::HEADER_SIZE }>::try_decode_body(
+                    decoder,
+                    field_offset,
+                    &mut result.0,
+                )
+            }
         }
     };
 }
 
 impl_evm_fixed!(Address);
 
+/// Unsigned integer is encoded as `Uint::BYTES` little-endian bytes, so `U160` takes 20 bytes
+/// (not 3 limbs)
 impl<const BITS: usize, const LIMBS: usize> Encoder<Uint<BITS, LIMBS>> for Uint<BITS, LIMBS> {
     const HEADER_SIZE: usize = Self::BYTES;
     fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
-        self.as_limbs().encode(encoder, field_offset)
+        let limbs = self.as_limbs();
+        let full_limbs = Self::BYTES / 8;
+        for (i, limb) in limbs[..full_limbs].iter().enumerate() {
+            encoder.write_u64(field_offset + i * 8, *limb);
+        }
+        // the last limb is partially used, only its used bytes are written
+        for i in 0..(Self::BYTES % 8) {
+            encoder.write_u8(
+                field_offset + full_limbs * 8 + i,
+                (limbs[full_limbs] >> (i * 8)) as u8,
+            );
+        }
     }
     fn decode_header(
         decoder: &mut BufferDecoder,
         field_offset: usize,
         result: &mut Uint<BITS, LIMBS>,
     ) -> (usize, usize) {
-        unsafe {
-            <[u64; LIMBS]>::decode_header(decoder, field_offset, result.as_limbs_mut());
+        Self::try_decode_body(decoder, field_offset, result).expect("uint is out of bounds");
+        (0, 0)
+    }
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Uint<BITS, LIMBS>,
+    ) -> Result<(), CodecError> {
+        let bytes = decoder.try_read_slice(field_offset, Self::BYTES)?;
+        let mut limbs = [0u64; LIMBS];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            let mut limb_bytes = [0u8; 8];
+            limb_bytes[..chunk.len()].copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(limb_bytes);
         }
+        // unused high bits of the last byte must be zero
+        if LIMBS > 0 && limbs[LIMBS - 1] > Self::MASK {
+            return Err(CodecError::InvalidPadding);
+        }
+        *result = Uint::from_limbs(limbs);
+        Ok(())
+    }
+}
+
+/// Signed integer is encoded as its two's complement `Uint`
+impl<const BITS: usize, const LIMBS: usize> Encoder<Signed<BITS, LIMBS>> for Signed<BITS, LIMBS> {
+    const HEADER_SIZE: usize = Uint::<BITS, LIMBS>::BYTES;
+    fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
+        self.into_raw().encode(encoder, field_offset)
+    }
+    fn decode_header(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Signed<BITS, LIMBS>,
+    ) -> (usize, usize) {
+        let mut raw = Uint::ZERO;
+        Uint::<BITS, LIMBS>::decode_header(decoder, field_offset, &mut raw);
+        *result = Signed::from_raw(raw);
         (0, 0)
     }
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Signed<BITS, LIMBS>,
+    ) -> Result<(), CodecError> {
+        let mut raw = Uint::ZERO;
+        Uint::<BITS, LIMBS>::try_decode_body(decoder, field_offset, &mut raw)?;
+        *result = Signed::from_raw(raw);
+        Ok(())
+    }
 }
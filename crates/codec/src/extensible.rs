@@ -0,0 +1,85 @@
+use crate::{buffer::WritableBuffer, BufferDecoder, ByteSource, CodecError};
+
+/// Header of an extensible struct (`#[derive(Codec)] #[codec(version = N)]`): struct version
+/// followed by offset and length of the fields block.
+///
+/// Fields block has the same layout as a regular struct, and since fields can be only appended,
+/// the length tells which fields are present. Fields missing in the input (encoded by an older
+/// version) are decoded as default values, and fields unknown to the decoder (encoded by a newer
+/// version) are skipped. Fields added after the first version are marked with
+/// `#[codec(since = N)]`, so decoder can reject a header whose version is older than its fields.
+/// For a top-level struct fields block starts right after the header, so field offsets are still
+/// static.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensibleHeader {
+    pub version: u32,
+    pub fields_offset: usize,
+    pub fields_length: usize,
+}
+
+impl ExtensibleHeader {
+    // u32: version + fields (offset and length)
+    pub const SIZE: usize = core::mem::size_of::<u32>() * 3;
+
+    /// Writes header and reserves `fields_length` bytes for the fields, returns offset of the
+    /// fields block
+    pub fn encode<W: WritableBuffer>(
+        encoder: &mut W,
+        field_offset: usize,
+        version: u32,
+        fields_length: usize,
+    ) -> usize {
+        encoder.write_u32(field_offset, version);
+        encoder.reserve_bytes(field_offset + 4, fields_length)
+    }
+
+    pub fn decode(decoder: &BufferDecoder, field_offset: usize) -> Self {
+        let version = decoder.read_u32(field_offset);
+        let (fields_offset, fields_length) = decoder.read_bytes_header(field_offset + 4);
+        Self {
+            version,
+            fields_offset,
+            fields_length,
+        }
+    }
+
+    /// Same as `decode`, but also makes sure that the fields block fits into the input
+    pub fn try_decode(decoder: &BufferDecoder, field_offset: usize) -> Result<Self, CodecError> {
+        let version = decoder.try_read_u32(field_offset)?;
+        let (fields_offset, fields_length) = decoder.try_read_bytes_header(field_offset + 4)?;
+        decoder.try_read_slice(fields_offset, fields_length)?;
+        Ok(Self {
+            version,
+            fields_offset,
+            fields_length,
+        })
+    }
+
+    /// Reads header of the struct encoded at `field_offset`
+    pub fn read<R: ByteSource + ?Sized>(source: &R, field_offset: usize) -> Self {
+        let mut buffer = [0u8; Self::SIZE];
+        source.read(&mut buffer, field_offset);
+        Self::decode(&BufferDecoder::new(&buffer), 0)
+    }
+
+    /// Checks if the field that ends at `field_end` (relative to the fields block) is present
+    pub fn has_field(&self, field_end: usize) -> bool {
+        field_end <= self.fields_length
+    }
+
+    /// Checks that version is not 0 and not lower than the version that introduced the last
+    /// present field, `fields` are pairs of field end (relative to the fields block) and version
+    /// the field was added in
+    pub fn check_version(&self, fields: &[(usize, u32)]) -> Result<(), CodecError> {
+        let required = fields
+            .iter()
+            .take_while(|(field_end, _)| self.has_field(*field_end))
+            .map(|(_, since)| *since)
+            .max()
+            .unwrap_or(1);
+        if self.version == 0 || self.version < required {
+            return Err(CodecError::InvalidVersion(self.version));
+        }
+        Ok(())
+    }
+}
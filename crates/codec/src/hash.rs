@@ -1,4 +1,4 @@
-use crate::{buffer::WritableBuffer, BufferDecoder, BufferEncoder, Encoder};
+use crate::{buffer::WritableBuffer, BufferDecoder, BufferEncoder, CodecError, Encoder};
 use alloc::vec::Vec;
 use core::hash::Hash;
 use hashbrown::{HashMap, HashSet};
@@ -65,6 +65,29 @@ impl<K: Default + Sized + Encoder<K> + Eq + Hash + Ord, V: Default + Sized + Enc
         // zip into map
         *result = keys.zip(values).collect()
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut HashMap<K, V>,
+    ) -> Result<(), CodecError> {
+        // decode length, keys and values
+        let length = decoder.try_read_u32(field_offset)? as usize;
+        let key_bytes = decoder.try_read_array(field_offset + 4, length, K::HEADER_SIZE)?;
+        let value_bytes = decoder.try_read_array(field_offset + 12, length, V::HEADER_SIZE)?;
+        let mut key_decoder = BufferDecoder::new(key_bytes);
+        let mut value_decoder = BufferDecoder::new(value_bytes);
+        result.clear();
+        result.reserve(length);
+        for i in 0..length {
+            let mut key = K::default();
+            K::try_decode_body(&mut key_decoder, K::HEADER_SIZE * i, &mut key)?;
+            let mut value = V::default();
+            V::try_decode_body(&mut value_decoder, V::HEADER_SIZE * i, &mut value)?;
+            result.insert(key, value);
+        }
+        Ok(())
+    }
 }
 
 impl<T: Default + Sized + Encoder<T> + Eq + Hash + Ord> Encoder<HashSet<T>> for HashSet<T> {
@@ -112,4 +135,23 @@ impl<T: Default + Sized + Encoder<T> + Eq + Hash + Ord> Encoder<HashSet<T>> for
         // zip into map
         *result = values.collect()
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut HashSet<T>,
+    ) -> Result<(), CodecError> {
+        // decode length and values
+        let length = decoder.try_read_u32(field_offset)? as usize;
+        let value_bytes = decoder.try_read_array(field_offset + 4, length, T::HEADER_SIZE)?;
+        let mut value_decoder = BufferDecoder::new(value_bytes);
+        result.clear();
+        result.reserve(length);
+        for i in 0..length {
+            let mut value = T::default();
+            T::try_decode_body(&mut value_decoder, T::HEADER_SIZE * i, &mut value)?;
+            result.insert(value);
+        }
+        Ok(())
+    }
 }
@@ -4,22 +4,34 @@ extern crate alloc;
 extern crate core;
 
 pub use crate::{
-    buffer::{BufferDecoder, BufferEncoder, WritableBuffer},
+    buffer::{BufferDecoder, BufferEncoder, ByteSource, WritableBuffer},
     empty::EmptyVec,
     encoder::{Encoder, FieldEncoder},
+    error::CodecError,
+    extensible::ExtensibleHeader,
+    schema::CodecSchema,
+    solidity::{SolidityCodec, SolidityTupleDecoder, SolidityTupleEncoder},
+    view::{EncoderView, ListView, MapView},
 };
 
+mod btree;
 mod buffer;
 mod empty;
 mod encoder;
+mod error;
 mod evm;
+mod extensible;
 mod hash;
 mod macros;
 mod primitive;
+pub mod schema;
 mod serde;
+pub mod solidity;
+mod string;
 #[cfg(test)]
 mod tests;
 mod tuple;
 mod vec;
+mod view;
 
 pub use fluentbase_codec_derive::Codec;
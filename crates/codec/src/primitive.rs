@@ -1,4 +1,5 @@
-use crate::{buffer::WritableBuffer, BufferDecoder, Encoder};
+use crate::{buffer::WritableBuffer, BufferDecoder, CodecError, Encoder};
+use alloc::boxed::Box;
 
 impl Encoder<u8> for u8 {
     const HEADER_SIZE: usize = core::mem::size_of::<u8>();
@@ -13,6 +14,14 @@ impl Encoder<u8> for u8 {
         *result = decoder.read_u8(field_offset);
         (0, 0)
     }
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut u8,
+    ) -> Result<(), CodecError> {
+        *result = decoder.try_read_u8(field_offset)?;
+        Ok(())
+    }
 }
 impl Encoder<bool> for bool {
     const HEADER_SIZE: usize = core::mem::size_of::<bool>();
@@ -27,10 +36,22 @@ impl Encoder<bool> for bool {
         *result = decoder.read_u8(field_offset) != 0;
         (0, 0)
     }
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut bool,
+    ) -> Result<(), CodecError> {
+        *result = match decoder.try_read_u8(field_offset)? {
+            0 => false,
+            1 => true,
+            value => return Err(CodecError::InvalidBool(value)),
+        };
+        Ok(())
+    }
 }
 
 macro_rules! impl_le_int {
-    ($typ:ty, $write_fn:ident, $read_fn:ident) => {
+    ($typ:ty, $write_fn:ident, $read_fn:ident, $try_read_fn:ident) => {
         impl Encoder<$typ> for $typ {
             const HEADER_SIZE: usize = core::mem::size_of::<$typ>();
             fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
@@ -44,16 +65,26 @@ macro_rules! impl_le_int {
                 *result = decoder.$read_fn(field_offset);
                 (0, 0)
             }
+            fn try_decode_body(
+                decoder: &mut BufferDecoder,
+                field_offset: usize,
+                result: &mut $typ,
+            ) -> Result<(), CodecError> {
+                *result = decoder.$try_read_fn(field_offset)?;
+                Ok(())
+            }
         }
     };
 }
 
-impl_le_int!(u16, write_u16, read_u16);
-impl_le_int!(u32, write_u32, read_u32);
-impl_le_int!(u64, write_u64, read_u64);
-impl_le_int!(i16, write_i16, read_i16);
-impl_le_int!(i32, write_i32, read_i32);
-impl_le_int!(i64, write_i64, read_i64);
+impl_le_int!(u16, write_u16, read_u16, try_read_u16);
+impl_le_int!(u32, write_u32, read_u32, try_read_u32);
+impl_le_int!(u64, write_u64, read_u64, try_read_u64);
+impl_le_int!(i16, write_i16, read_i16, try_read_i16);
+impl_le_int!(i32, write_i32, read_i32, try_read_i32);
+impl_le_int!(i64, write_i64, read_i64, try_read_i64);
+impl_le_int!(u128, write_u128, read_u128, try_read_u128);
+impl_le_int!(i128, write_i128, read_i128, try_read_i128);
 
 impl<T: Sized + Encoder<T>, const N: usize> Encoder<[T; N]> for [T; N] {
     const HEADER_SIZE: usize = T::HEADER_SIZE * N;
@@ -74,6 +105,17 @@ impl<T: Sized + Encoder<T>, const N: usize> Encoder<[T; N]> for [T; N] {
         });
         (0, 0)
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut [T; N],
+    ) -> Result<(), CodecError> {
+        for (i, item) in result.iter_mut().enumerate() {
+            T::try_decode_body(decoder, field_offset + i * T::HEADER_SIZE, item)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T: Sized + Encoder<T> + Default> Encoder<Option<T>> for Option<T> {
@@ -117,4 +159,50 @@ impl<T: Sized + Encoder<T> + Default> Encoder<Option<T>> for Option<T> {
             None
         };
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Option<T>,
+    ) -> Result<(), CodecError> {
+        *result = match decoder.try_read_u8(field_offset)? {
+            0 => None,
+            1 => {
+                let mut result_inner: T = Default::default();
+                T::try_decode_body(decoder, field_offset + 1, &mut result_inner)?;
+                Some(result_inner)
+            }
+            value => return Err(CodecError::InvalidBool(value)),
+        };
+        Ok(())
+    }
+}
+
+/// Boxed value is encoded the same way as the value itself
+impl<T: Sized + Encoder<T>> Encoder<Box<T>> for Box<T> {
+    const HEADER_SIZE: usize = T::HEADER_SIZE;
+
+    fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
+        self.as_ref().encode(encoder, field_offset);
+    }
+
+    fn decode_header(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Box<T>,
+    ) -> (usize, usize) {
+        T::decode_header(decoder, field_offset, result)
+    }
+
+    fn decode_body(decoder: &mut BufferDecoder, field_offset: usize, result: &mut Box<T>) {
+        T::decode_body(decoder, field_offset, result)
+    }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Box<T>,
+    ) -> Result<(), CodecError> {
+        T::try_decode_body(decoder, field_offset, result)
+    }
 }
@@ -0,0 +1,641 @@
+//! Machine-readable description of the encoding layout.
+//!
+//! Every type that can be encoded describes itself with `CodecSchema`, `#[derive(Codec)]`
+//! generates it for structs and enums marked with `#[codec(schema)]`. Schema can be exported as
+//! JSON to generate off-chain clients, and `TypeSchema::decode` is a reference decoder that
+//! decodes any buffer using only the schema.
+use crate::{BufferDecoder, CodecError, EmptyVec, ExtensibleHeader};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+};
+pub use alloc::{string::String, vec::Vec};
+use alloy_primitives::{Address, Bytes, FixedBytes, Signed, Uint, I256, U256};
+use core::fmt::Write;
+use hashbrown::{HashMap, HashSet};
+
+pub trait CodecSchema {
+    fn schema() -> TypeSchema;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSchema {
+    Bool,
+    /// Little-endian unsigned integer (`u8`..`u64` and `Uint<BITS, LIMBS>`)
+    UInt(usize),
+    /// Little-endian signed integer
+    Int(usize),
+    Address,
+    FixedBytes(usize),
+    /// Offset and length of the body
+    Bytes,
+    /// Offset and length of UTF-8 bytes
+    String,
+    /// Number of elements followed by offset and length of the elements
+    Vec(Box<TypeSchema>),
+    /// Elements are stored one by one inside the header
+    Array(Box<TypeSchema>, usize),
+    /// Flag (0 or 1) followed by the value (default value if flag is 0)
+    Option(Box<TypeSchema>),
+    Tuple(Vec<TypeSchema>),
+    /// Number of entries followed by keys and values (both as offset and length), keys are sorted
+    Map(Box<TypeSchema>, Box<TypeSchema>),
+    /// Number of elements followed by offset and length of the sorted elements
+    Set(Box<TypeSchema>),
+    EmptyVec,
+    Struct(StructSchema),
+    Enum(EnumSchema),
+}
+
+/// Fields are stored one by one, `offset` is relative to the beginning of the struct. Versioned
+/// struct is stored as `ExtensibleHeader` and offsets are relative to its fields block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructSchema {
+    pub name: String,
+    pub version: Option<u32>,
+    pub fields: Vec<FieldSchema>,
+}
+
+/// Enum is stored as a variant index (u32) followed by offset and length of the variant body,
+/// field offsets of the variant are relative to the body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumSchema {
+    pub name: String,
+    pub variants: Vec<VariantSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSchema {
+    pub name: String,
+    pub fields: Vec<FieldSchema>,
+}
+
+/// Unnamed fields are named by their position (`0`, `1`, ...), fields of versioned structs
+/// have the version they were added in (`#[codec(since = N)]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    pub name: String,
+    pub offset: usize,
+    pub since: Option<u32>,
+    pub schema: TypeSchema,
+}
+
+/// Value decoded by `TypeSchema::decode`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Bool(bool),
+    UInt(U256),
+    Int(I256),
+    Address(Address),
+    /// `Bytes` and `FixedBytes`
+    Bytes(Vec<u8>),
+    String(String),
+    /// Vectors, arrays and sets
+    List(Vec<Value>),
+    Option(Option<Box<Value>>),
+    Tuple(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Struct(Vec<(String, Value)>),
+    Enum {
+        variant: String,
+        fields: Vec<(String, Value)>,
+    },
+}
+
+impl TypeSchema {
+    pub fn header_size(&self) -> usize {
+        match self {
+            TypeSchema::Bool => 1,
+            // the same as `Uint::<BITS, LIMBS>::BYTES`, integers are not padded to limbs
+            TypeSchema::UInt(bits) | TypeSchema::Int(bits) => (bits + 7) / 8,
+            TypeSchema::Address => Address::len_bytes(),
+            TypeSchema::FixedBytes(length) => *length,
+            TypeSchema::Bytes | TypeSchema::String => 8,
+            TypeSchema::Vec(_) | TypeSchema::Set(_) | TypeSchema::EmptyVec => 12,
+            TypeSchema::Array(item, length) => item.header_size() * length,
+            TypeSchema::Option(item) => 1 + item.header_size(),
+            TypeSchema::Tuple(items) => items.iter().map(TypeSchema::header_size).sum(),
+            TypeSchema::Map(_, _) => 20,
+            TypeSchema::Struct(schema) if schema.version.is_some() => ExtensibleHeader::SIZE,
+            TypeSchema::Struct(schema) => fields_size(&schema.fields),
+            TypeSchema::Enum(_) => 12,
+        }
+    }
+
+    /// Decodes value from an untrusted buffer, it has the same checks as `Encoder::try_decode`
+    pub fn decode(&self, buffer: &[u8]) -> Result<Value, CodecError> {
+        let header_size = self.header_size();
+        if buffer.len() < header_size {
+            return Err(CodecError::OutOfBounds {
+                offset: 0,
+                length: header_size,
+            });
+        }
+        let decoder = BufferDecoder::new(buffer);
+        let result = self.decode_at(&decoder, 0)?;
+        let consumed = decoder.consumed().max(header_size);
+        if buffer.len() > consumed {
+            return Err(CodecError::TrailingData(buffer.len() - consumed));
+        }
+        Ok(result)
+    }
+
+    /// Decodes value that starts at `field_offset`
+    pub fn decode_at(
+        &self,
+        decoder: &BufferDecoder,
+        field_offset: usize,
+    ) -> Result<Value, CodecError> {
+        Ok(match self {
+            TypeSchema::Bool => match decoder.try_read_u8(field_offset)? {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                value => return Err(CodecError::InvalidBool(value)),
+            },
+            TypeSchema::UInt(bits) => Value::UInt(decode_uint(decoder, field_offset, *bits)?),
+            TypeSchema::Int(bits) => {
+                let value = decode_uint(decoder, field_offset, *bits)?;
+                // sign extend two's complement value from `bits` to 256 bits
+                let value = if *bits > 0 && value.bit(bits - 1) {
+                    value | (U256::MAX << *bits)
+                } else {
+                    value
+                };
+                Value::Int(I256::from_raw(value))
+            }
+            TypeSchema::Address => {
+                let bytes = decoder.try_read_slice(field_offset, Address::len_bytes())?;
+                Value::Address(Address::from_slice(bytes))
+            }
+            TypeSchema::FixedBytes(length) => {
+                Value::Bytes(decoder.try_read_slice(field_offset, *length)?.to_vec())
+            }
+            TypeSchema::Bytes => Value::Bytes(decoder.try_read_bytes(field_offset)?.to_vec()),
+            TypeSchema::String => {
+                let bytes = decoder.try_read_bytes(field_offset)?;
+                let value = core::str::from_utf8(bytes).map_err(|_| CodecError::InvalidUtf8)?;
+                Value::String(value.into())
+            }
+            TypeSchema::Vec(item) | TypeSchema::Set(item) => {
+                let length = decoder.try_read_u32(field_offset)? as usize;
+                Value::List(decode_list(decoder, field_offset + 4, length, item)?)
+            }
+            TypeSchema::Array(item, length) => Value::List(
+                (0..*length)
+                    .map(|i| item.decode_at(decoder, field_offset + i * item.header_size()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            TypeSchema::Option(item) => match decoder.try_read_u8(field_offset)? {
+                0 => Value::Option(None),
+                1 => Value::Option(Some(Box::new(item.decode_at(decoder, field_offset + 1)?))),
+                value => return Err(CodecError::InvalidBool(value)),
+            },
+            TypeSchema::Tuple(items) => {
+                let mut offset = field_offset;
+                let mut values = Vec::with_capacity(items.len());
+                for item in items {
+                    values.push(item.decode_at(decoder, offset)?);
+                    offset += item.header_size();
+                }
+                Value::Tuple(values)
+            }
+            TypeSchema::Map(key, value) => {
+                let length = decoder.try_read_u32(field_offset)? as usize;
+                let keys = decode_list(decoder, field_offset + 4, length, key)?;
+                let values = decode_list(decoder, field_offset + 12, length, value)?;
+                Value::Map(keys.into_iter().zip(values).collect())
+            }
+            TypeSchema::EmptyVec => {
+                let count = decoder.try_read_u32(field_offset)? as usize;
+                let bytes = decoder.try_read_bytes(field_offset + 4)?;
+                if count != 0 || !bytes.is_empty() {
+                    return Err(CodecError::TrailingData(bytes.len()));
+                }
+                Value::List(Vec::new())
+            }
+            TypeSchema::Struct(schema) if schema.version.is_some() => {
+                let header = ExtensibleHeader::try_decode(decoder, field_offset)?;
+                let field_versions = schema
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let field_end = field.offset + field.schema.header_size();
+                        (field_end, field.since.unwrap_or(1))
+                    })
+                    .collect::<Vec<_>>();
+                header.check_version(&field_versions)?;
+                if header.fields_length > fields_size(&schema.fields) {
+                    // bodies of fields added by newer versions can't be located
+                    decoder.consume_all();
+                }
+                // fields can be only appended, so missing fields are at the end and omitted
+                let present = schema
+                    .fields
+                    .iter()
+                    .take_while(|field| header.has_field(field.offset + field.schema.header_size()))
+                    .count();
+                let fields = &schema.fields[..present];
+                Value::Struct(decode_fields(decoder, header.fields_offset, fields)?)
+            }
+            TypeSchema::Struct(schema) => {
+                Value::Struct(decode_fields(decoder, field_offset, &schema.fields)?)
+            }
+            TypeSchema::Enum(schema) => {
+                let variant_index = decoder.try_read_u32(field_offset)?;
+                let body = decoder.try_read_bytes(field_offset + 4)?;
+                let variant = schema
+                    .variants
+                    .get(variant_index as usize)
+                    .ok_or(CodecError::UnknownVariant(variant_index))?;
+                Value::Enum {
+                    variant: variant.name.clone(),
+                    fields: decode_fields(&BufferDecoder::new(body), 0, &variant.fields)?,
+                }
+            }
+        })
+    }
+
+    /// Exports schema as JSON, every type has `type` and `header_size` keys, f.e.
+    /// `{"type":"vec","header_size":12,"item":{"type":"uint","header_size":4,"bits":32}}`
+    pub fn to_json(&self) -> String {
+        let mut result = String::new();
+        self.write_json(&mut result);
+        result
+    }
+
+    fn write_json(&self, out: &mut String) {
+        let type_name = match self {
+            TypeSchema::Bool => "bool",
+            TypeSchema::UInt(_) => "uint",
+            TypeSchema::Int(_) => "int",
+            TypeSchema::Address => "address",
+            TypeSchema::FixedBytes(_) => "fixed_bytes",
+            TypeSchema::Bytes => "bytes",
+            TypeSchema::String => "string",
+            TypeSchema::Vec(_) => "vec",
+            TypeSchema::Array(_, _) => "array",
+            TypeSchema::Option(_) => "option",
+            TypeSchema::Tuple(_) => "tuple",
+            TypeSchema::Map(_, _) => "map",
+            TypeSchema::Set(_) => "set",
+            TypeSchema::EmptyVec => "empty_vec",
+            TypeSchema::Struct(_) => "struct",
+            TypeSchema::Enum(_) => "enum",
+        };
+        _ = write!(
+            out,
+            "{{\"type\":\"{}\",\"header_size\":{}",
+            type_name,
+            self.header_size()
+        );
+        match self {
+            TypeSchema::UInt(bits) | TypeSchema::Int(bits) => {
+                _ = write!(out, ",\"bits\":{}", bits);
+            }
+            TypeSchema::FixedBytes(length) => {
+                _ = write!(out, ",\"length\":{}", length);
+            }
+            TypeSchema::Vec(item) | TypeSchema::Option(item) | TypeSchema::Set(item) => {
+                out.push_str(",\"item\":");
+                item.write_json(out);
+            }
+            TypeSchema::Array(item, length) => {
+                _ = write!(out, ",\"length\":{},\"item\":", length);
+                item.write_json(out);
+            }
+            TypeSchema::Tuple(items) => {
+                out.push_str(",\"items\":[");
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write_json(out);
+                }
+                out.push(']');
+            }
+            TypeSchema::Map(key, value) => {
+                out.push_str(",\"key\":");
+                key.write_json(out);
+                out.push_str(",\"value\":");
+                value.write_json(out);
+            }
+            TypeSchema::Struct(schema) => {
+                write_json_string(out, ",\"name\":", &schema.name);
+                if let Some(version) = schema.version {
+                    _ = write!(out, ",\"version\":{}", version);
+                }
+                write_json_fields(out, &schema.fields);
+            }
+            TypeSchema::Enum(schema) => {
+                write_json_string(out, ",\"name\":", &schema.name);
+                out.push_str(",\"variants\":[");
+                for (i, variant) in schema.variants.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_json_string(out, "{\"name\":", &variant.name);
+                    _ = write!(out, ",\"index\":{}", i);
+                    write_json_fields(out, &variant.fields);
+                    out.push('}');
+                }
+                out.push(']');
+            }
+            _ => {}
+        }
+        out.push('}');
+    }
+}
+
+fn fields_size(fields: &[FieldSchema]) -> usize {
+    fields
+        .iter()
+        .map(|field| field.offset + field.schema.header_size())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Decodes little-endian integer of `bits` width, unused high bits must be zero
+fn decode_uint(
+    decoder: &BufferDecoder,
+    field_offset: usize,
+    bits: usize,
+) -> Result<U256, CodecError> {
+    let bytes = decoder.try_read_slice(field_offset, (bits + 7) / 8)?;
+    let value = U256::try_from_le_slice(bytes).ok_or(CodecError::InvalidPadding)?;
+    if value.bit_len() > bits {
+        return Err(CodecError::InvalidPadding);
+    }
+    Ok(value)
+}
+
+/// Decodes `length` elements referenced by offset and length at `field_offset`
+fn decode_list(
+    decoder: &BufferDecoder,
+    field_offset: usize,
+    length: usize,
+    item: &TypeSchema,
+) -> Result<Vec<Value>, CodecError> {
+    let bytes = decoder.try_read_array(field_offset, length, item.header_size())?;
+    let item_decoder = BufferDecoder::new(bytes);
+    (0..length)
+        .map(|i| item.decode_at(&item_decoder, i * item.header_size()))
+        .collect()
+}
+
+fn decode_fields(
+    decoder: &BufferDecoder,
+    field_offset: usize,
+    fields: &[FieldSchema],
+) -> Result<Vec<(String, Value)>, CodecError> {
+    fields
+        .iter()
+        .map(|field| {
+            let value = field
+                .schema
+                .decode_at(decoder, field_offset + field.offset)?;
+            Ok((field.name.clone(), value))
+        })
+        .collect()
+}
+
+fn write_json_string(out: &mut String, prefix: &str, value: &str) {
+    out.push_str(prefix);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_json_fields(out: &mut String, fields: &[FieldSchema]) {
+    out.push_str(",\"fields\":[");
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_json_string(out, "{\"name\":", &field.name);
+        _ = write!(out, ",\"offset\":{}", field.offset);
+        if let Some(since) = field.since {
+            _ = write!(out, ",\"since\":{}", since);
+        }
+        out.push_str(",\"schema\":");
+        field.schema.write_json(out);
+        out.push('}');
+    }
+    out.push(']');
+}
+
+impl Value {
+    /// Exports value as JSON: integers that don't fit into 53 bits are written as decimal
+    /// strings, bytes and addresses as `0x`-prefixed hex strings, maps as arrays of `[key, value]`
+    /// pairs and enums as `{"variant": name, "fields": {...}}`
+    pub fn to_json(&self) -> String {
+        let mut result = String::new();
+        self.write_json(&mut result);
+        result
+    }
+
+    fn write_json(&self, out: &mut String) {
+        const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+        match self {
+            Value::Bool(value) => {
+                _ = write!(out, "{}", value);
+            }
+            Value::UInt(value) if *value <= U256::from(MAX_SAFE_INTEGER) => {
+                _ = write!(out, "{}", value);
+            }
+            Value::UInt(value) => {
+                _ = write!(out, "\"{}\"", value);
+            }
+            Value::Int(value) if value.unsigned_abs() <= U256::from(MAX_SAFE_INTEGER) => {
+                _ = write!(out, "{}", value);
+            }
+            Value::Int(value) => {
+                _ = write!(out, "\"{}\"", value);
+            }
+            Value::Address(value) => write_json_hex(out, value.as_slice()),
+            Value::Bytes(value) => write_json_hex(out, value),
+            Value::String(value) => write_json_string(out, "", value),
+            Value::List(values) | Value::Tuple(values) => {
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    value.write_json(out);
+                }
+                out.push(']');
+            }
+            Value::Option(None) => out.push_str("null"),
+            Value::Option(Some(value)) => value.write_json(out),
+            Value::Map(entries) => {
+                out.push('[');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push('[');
+                    key.write_json(out);
+                    out.push(',');
+                    value.write_json(out);
+                    out.push(']');
+                }
+                out.push(']');
+            }
+            Value::Struct(fields) => write_json_object(out, fields),
+            Value::Enum { variant, fields } => {
+                write_json_string(out, "{\"variant\":", variant);
+                out.push_str(",\"fields\":");
+                write_json_object(out, fields);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_json_hex(out: &mut String, bytes: &[u8]) {
+    out.push_str("\"0x");
+    for byte in bytes {
+        _ = write!(out, "{:02x}", byte);
+    }
+    out.push('"');
+}
+
+fn write_json_object(out: &mut String, fields: &[(String, Value)]) {
+    out.push('{');
+    for (i, (name, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_json_string(out, "", name);
+        out.push(':');
+        value.write_json(out);
+    }
+    out.push('}');
+}
+
+macro_rules! impl_schema {
+    ($($typ:ty => $schema:expr),* $(,)?) => {
+        $(
+            impl CodecSchema for $typ {
+                fn schema() -> TypeSchema {
+                    $schema
+                }
+            }
+        )*
+    };
+}
+
+impl_schema!(
+    bool => TypeSchema::Bool,
+    u8 => TypeSchema::UInt(8),
+    u16 => TypeSchema::UInt(16),
+    u32 => TypeSchema::UInt(32),
+    u64 => TypeSchema::UInt(64),
+    i16 => TypeSchema::Int(16),
+    i32 => TypeSchema::Int(32),
+    i64 => TypeSchema::Int(64),
+    u128 => TypeSchema::UInt(128),
+    i128 => TypeSchema::Int(128),
+    Address => TypeSchema::Address,
+    Bytes => TypeSchema::Bytes,
+    String => TypeSchema::String,
+    EmptyVec => TypeSchema::EmptyVec,
+);
+
+impl<const BITS: usize, const LIMBS: usize> CodecSchema for Uint<BITS, LIMBS> {
+    fn schema() -> TypeSchema {
+        TypeSchema::UInt(BITS)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> CodecSchema for Signed<BITS, LIMBS> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Int(BITS)
+    }
+}
+
+impl<const N: usize> CodecSchema for FixedBytes<N> {
+    fn schema() -> TypeSchema {
+        TypeSchema::FixedBytes(N)
+    }
+}
+
+impl<T: CodecSchema> CodecSchema for Vec<T> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Vec(Box::new(T::schema()))
+    }
+}
+
+impl<T: CodecSchema, const N: usize> CodecSchema for [T; N] {
+    fn schema() -> TypeSchema {
+        TypeSchema::Array(Box::new(T::schema()), N)
+    }
+}
+
+impl<T: CodecSchema> CodecSchema for Option<T> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Option(Box::new(T::schema()))
+    }
+}
+
+/// Boxed value has the same layout as the value itself, recursive types are not supported
+impl<T: CodecSchema> CodecSchema for Box<T> {
+    fn schema() -> TypeSchema {
+        T::schema()
+    }
+}
+
+macro_rules! impl_schema_tuple {
+    ($($typ:ident),+) => {
+        impl<$($typ: CodecSchema),+> CodecSchema for ($($typ,)+) {
+            fn schema() -> TypeSchema {
+                TypeSchema::Tuple(Vec::from([$($typ::schema()),+]))
+            }
+        }
+    };
+}
+
+impl_schema_tuple!(A1);
+impl_schema_tuple!(A1, A2);
+impl_schema_tuple!(A1, A2, A3);
+impl_schema_tuple!(A1, A2, A3, A4);
+impl_schema_tuple!(A1, A2, A3, A4, A5);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6, A7);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_schema_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+
+impl<K: CodecSchema, V: CodecSchema> CodecSchema for HashMap<K, V> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Map(Box::new(K::schema()), Box::new(V::schema()))
+    }
+}
+
+impl<T: CodecSchema> CodecSchema for HashSet<T> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Set(Box::new(T::schema()))
+    }
+}
+
+impl<K: CodecSchema, V: CodecSchema> CodecSchema for BTreeMap<K, V> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Map(Box::new(K::schema()), Box::new(V::schema()))
+    }
+}
+
+impl<T: CodecSchema> CodecSchema for BTreeSet<T> {
+    fn schema() -> TypeSchema {
+        TypeSchema::Set(Box::new(T::schema()))
+    }
+}
@@ -0,0 +1,692 @@
+//! Solidity ABI encoding (head/tail layout with 32-byte big-endian words).
+//!
+//! It's an alternative encoding for the same types that makes contracts compatible with EVM
+//! contracts and off-chain tools, structs opt in with `#[derive(Codec)] #[codec(solidity)]`.
+use crate::CodecError;
+use alloc::boxed::Box;
+pub use alloc::{string::String, vec::Vec};
+use alloy_primitives::{Address, Bytes, FixedBytes, Signed, Uint};
+
+pub const WORD_SIZE: usize = 32;
+
+pub trait SolidityCodec: Sized {
+    /// Dynamic types are stored in the tail and referenced by an offset from the head
+    const IS_DYNAMIC: bool;
+    /// Number of bytes occupied in the head of an enclosing tuple
+    const HEAD_SIZE: usize;
+
+    /// Appends Solidity type name used in function signatures (f.e. `uint256` or `(bool,bytes)`)
+    fn sol_type(out: &mut String);
+
+    /// Encodes value itself, dynamic values are encoded without an offset
+    fn sol_encode(&self, out: &mut Vec<u8>);
+
+    /// Decodes value that starts at `offset`, offsets inside value are relative to `offset`
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError>;
+
+    fn sol_type_name() -> String {
+        let mut result = String::new();
+        Self::sol_type(&mut result);
+        result
+    }
+
+    /// Encodes value as function params (or return values), f.e. struct fields are encoded as
+    /// separate params, other types are encoded as a single param
+    fn abi_encode(&self) -> Vec<u8> {
+        let mut encoder = SolidityTupleEncoder::new(Self::HEAD_SIZE);
+        encoder.encode_field(self);
+        let mut result = Vec::new();
+        encoder.finalize(&mut result);
+        result
+    }
+
+    fn abi_decode(input: &[u8]) -> Result<Self, CodecError> {
+        SolidityTupleDecoder::new(input, 0).decode_field()
+    }
+}
+
+/// Encodes tuple (struct fields or array elements), static fields are written into the head and
+/// dynamic fields are appended to the tail with an offset written into the head
+pub struct SolidityTupleEncoder {
+    head_size: usize,
+    head: Vec<u8>,
+    tail: Vec<u8>,
+}
+
+impl SolidityTupleEncoder {
+    pub fn new(head_size: usize) -> Self {
+        Self {
+            head_size,
+            head: Vec::with_capacity(head_size),
+            tail: Vec::new(),
+        }
+    }
+
+    pub fn encode_field<T: SolidityCodec>(&mut self, value: &T) {
+        if T::IS_DYNAMIC {
+            write_usize(&mut self.head, self.head_size + self.tail.len());
+            value.sol_encode(&mut self.tail);
+        } else {
+            value.sol_encode(&mut self.head);
+        }
+    }
+
+    pub fn finalize(self, out: &mut Vec<u8>) {
+        debug_assert_eq!(self.head.len(), self.head_size);
+        out.extend(self.head);
+        out.extend(self.tail);
+    }
+}
+
+pub struct SolidityTupleDecoder<'a> {
+    input: &'a [u8],
+    offset: usize,
+    head_offset: usize,
+}
+
+impl<'a> SolidityTupleDecoder<'a> {
+    pub fn new(input: &'a [u8], offset: usize) -> Self {
+        Self {
+            input,
+            offset,
+            head_offset: 0,
+        }
+    }
+
+    pub fn decode_field<T: SolidityCodec>(&mut self) -> Result<T, CodecError> {
+        let field_offset = checked_offset(self.offset, self.head_offset)?;
+        self.head_offset += T::HEAD_SIZE;
+        if T::IS_DYNAMIC {
+            let data_offset = read_usize(self.input, field_offset)?;
+            T::sol_decode(self.input, checked_offset(self.offset, data_offset)?)
+        } else {
+            T::sol_decode(self.input, field_offset)
+        }
+    }
+}
+
+fn checked_offset(offset: usize, length: usize) -> Result<usize, CodecError> {
+    offset.checked_add(length).ok_or(CodecError::LengthOverflow)
+}
+
+pub fn read_word(input: &[u8], offset: usize) -> Result<&[u8; WORD_SIZE], CodecError> {
+    let end = checked_offset(offset, WORD_SIZE)?;
+    input
+        .get(offset..end)
+        .and_then(|word| word.try_into().ok())
+        .ok_or(CodecError::OutOfBounds {
+            offset,
+            length: WORD_SIZE,
+        })
+}
+
+fn write_usize(out: &mut Vec<u8>, value: usize) {
+    (value as u64).sol_encode(out);
+}
+
+fn read_usize(input: &[u8], offset: usize) -> Result<usize, CodecError> {
+    let word = read_word(input, offset)?;
+    if word[..WORD_SIZE - 8].iter().any(|v| *v != 0) {
+        return Err(CodecError::LengthOverflow);
+    }
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&word[WORD_SIZE - 8..]);
+    usize::try_from(u64::from_be_bytes(value)).map_err(|_| CodecError::LengthOverflow)
+}
+
+/// Makes sure that `count` elements of `item_size` can fit into input, it protects from
+/// allocating memory for fake lengths
+fn check_length(
+    input: &[u8],
+    offset: usize,
+    count: usize,
+    item_size: usize,
+) -> Result<(), CodecError> {
+    let length = count
+        .checked_mul(item_size)
+        .ok_or(CodecError::LengthOverflow)?;
+    if checked_offset(offset, length)? > input.len() {
+        return Err(CodecError::OutOfBounds { offset, length });
+    }
+    Ok(())
+}
+
+impl SolidityCodec for bool {
+    const IS_DYNAMIC: bool = false;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str("bool");
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        (*self as u8).sol_encode(out);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        match u8::sol_decode(input, offset)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(CodecError::InvalidBool(value)),
+        }
+    }
+}
+
+macro_rules! impl_sol_int {
+    ($typ:ty, $name:literal) => {
+        #[allow(unused_comparisons)]
+        impl SolidityCodec for $typ {
+            const IS_DYNAMIC: bool = false;
+            const HEAD_SIZE: usize = WORD_SIZE;
+
+            fn sol_type(out: &mut String) {
+                out.push_str($name);
+            }
+
+            fn sol_encode(&self, out: &mut Vec<u8>) {
+                // signed values are sign-extended to 32 bytes
+                let padding = if *self < 0 as $typ { 0xff } else { 0 };
+                let bytes = self.to_be_bytes();
+                out.resize(out.len() + WORD_SIZE - bytes.len(), padding);
+                out.extend_from_slice(&bytes);
+            }
+
+            fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+                let word = read_word(input, offset)?;
+                const SIZE: usize = core::mem::size_of::<$typ>();
+                let mut bytes = [0u8; SIZE];
+                bytes.copy_from_slice(&word[WORD_SIZE - SIZE..]);
+                let value = <$typ>::from_be_bytes(bytes);
+                let padding = if value < 0 as $typ { 0xff } else { 0 };
+                if word[..WORD_SIZE - SIZE]
+                    .iter()
+                    .any(|v| *v != padding)
+                {
+                    return Err(CodecError::InvalidPadding);
+                }
+                Ok(value)
+            }
+        }
+    };
+}
+
+impl_sol_int!(u8, "uint8");
+impl_sol_int!(u16, "uint16");
+impl_sol_int!(u32, "uint32");
+impl_sol_int!(u64, "uint64");
+impl_sol_int!(i8, "int8");
+impl_sol_int!(i16, "int16");
+impl_sol_int!(i32, "int32");
+impl_sol_int!(i64, "int64");
+impl_sol_int!(u128, "uint128");
+impl_sol_int!(i128, "int128");
+
+impl<const BITS: usize, const LIMBS: usize> SolidityCodec for Uint<BITS, LIMBS> {
+    const IS_DYNAMIC: bool = false;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str(&alloc::format!("uint{}", BITS));
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        let bytes = self.to_be_bytes_vec();
+        out.resize(out.len() + WORD_SIZE - bytes.len(), 0);
+        out.extend(bytes);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let word = read_word(input, offset)?;
+        Self::try_from_be_slice(word).ok_or(CodecError::InvalidPadding)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> SolidityCodec for Signed<BITS, LIMBS> {
+    const IS_DYNAMIC: bool = false;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str(&alloc::format!("int{}", BITS));
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        // signed values are sign-extended to 32 bytes
+        let padding = if self.is_negative() { 0xff } else { 0 };
+        let bytes = self.into_raw().to_be_bytes_vec();
+        out.resize(out.len() + WORD_SIZE - bytes.len(), padding);
+        out.extend(bytes);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let word = read_word(input, offset)?;
+        let (padding, bytes) = word.split_at(WORD_SIZE - Uint::<BITS, LIMBS>::BYTES);
+        let value = Uint::try_from_be_slice(bytes)
+            .map(Signed::from_raw)
+            .ok_or(CodecError::InvalidPadding)?;
+        let expected_padding = if value.is_negative() { 0xff } else { 0 };
+        if padding.iter().any(|v| *v != expected_padding) {
+            return Err(CodecError::InvalidPadding);
+        }
+        Ok(value)
+    }
+}
+
+impl SolidityCodec for Address {
+    const IS_DYNAMIC: bool = false;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str("address");
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        out.resize(out.len() + WORD_SIZE - Address::len_bytes(), 0);
+        out.extend_from_slice(self.as_slice());
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let word = read_word(input, offset)?;
+        let (padding, address) = word.split_at(WORD_SIZE - Address::len_bytes());
+        if padding.iter().any(|v| *v != 0) {
+            return Err(CodecError::InvalidPadding);
+        }
+        Ok(Address::from_slice(address))
+    }
+}
+
+impl<const N: usize> SolidityCodec for FixedBytes<N> {
+    const IS_DYNAMIC: bool = false;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str(&alloc::format!("bytes{}", N));
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        // fixed bytes are left-aligned
+        out.extend_from_slice(self.as_slice());
+        out.resize(out.len() + WORD_SIZE - N, 0);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let word = read_word(input, offset)?;
+        if word[N..].iter().any(|v| *v != 0) {
+            return Err(CodecError::InvalidPadding);
+        }
+        Ok(FixedBytes::from_slice(&word[..N]))
+    }
+}
+
+impl SolidityCodec for Bytes {
+    const IS_DYNAMIC: bool = true;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str("bytes");
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        write_usize(out, self.len());
+        out.extend_from_slice(self);
+        // data is padded to 32 bytes
+        let padding = (WORD_SIZE - self.len() % WORD_SIZE) % WORD_SIZE;
+        out.resize(out.len() + padding, 0);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let length = read_usize(input, offset)?;
+        let data_offset = checked_offset(offset, WORD_SIZE)?;
+        check_length(input, data_offset, length, 1)?;
+        Ok(Bytes::copy_from_slice(
+            &input[data_offset..data_offset + length],
+        ))
+    }
+}
+
+impl SolidityCodec for String {
+    const IS_DYNAMIC: bool = true;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        out.push_str("string");
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        // string is encoded as UTF-8 bytes
+        Bytes::copy_from_slice(self.as_bytes()).sol_encode(out);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let bytes = Bytes::sol_decode(input, offset)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| CodecError::InvalidUtf8)
+    }
+}
+
+/// Boxed value has the same encoding as the value itself
+impl<T: SolidityCodec> SolidityCodec for Box<T> {
+    const IS_DYNAMIC: bool = T::IS_DYNAMIC;
+    const HEAD_SIZE: usize = T::HEAD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        T::sol_type(out);
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        self.as_ref().sol_encode(out);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        T::sol_decode(input, offset).map(Box::new)
+    }
+
+    fn abi_encode(&self) -> Vec<u8> {
+        self.as_ref().abi_encode()
+    }
+
+    fn abi_decode(input: &[u8]) -> Result<Self, CodecError> {
+        T::abi_decode(input).map(Box::new)
+    }
+}
+
+impl<T: SolidityCodec> SolidityCodec for Vec<T> {
+    const IS_DYNAMIC: bool = true;
+    const HEAD_SIZE: usize = WORD_SIZE;
+
+    fn sol_type(out: &mut String) {
+        T::sol_type(out);
+        out.push_str("[]");
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        write_usize(out, self.len());
+        let mut encoder = SolidityTupleEncoder::new(T::HEAD_SIZE * self.len());
+        self.iter().for_each(|value| encoder.encode_field(value));
+        encoder.finalize(out);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let length = read_usize(input, offset)?;
+        let data_offset = checked_offset(offset, WORD_SIZE)?;
+        check_length(input, data_offset, length, T::HEAD_SIZE)?;
+        let mut decoder = SolidityTupleDecoder::new(input, data_offset);
+        (0..length).map(|_| decoder.decode_field()).collect()
+    }
+}
+
+impl<T: SolidityCodec, const N: usize> SolidityCodec for [T; N] {
+    const IS_DYNAMIC: bool = T::IS_DYNAMIC;
+    const HEAD_SIZE: usize = if T::IS_DYNAMIC {
+        WORD_SIZE
+    } else {
+        T::HEAD_SIZE * N
+    };
+
+    fn sol_type(out: &mut String) {
+        T::sol_type(out);
+        out.push_str(&alloc::format!("[{}]", N));
+    }
+
+    fn sol_encode(&self, out: &mut Vec<u8>) {
+        let mut encoder = SolidityTupleEncoder::new(T::HEAD_SIZE * N);
+        self.iter().for_each(|value| encoder.encode_field(value));
+        encoder.finalize(out);
+    }
+
+    fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+        let mut decoder = SolidityTupleDecoder::new(input, offset);
+        let values = (0..N)
+            .map(|_| decoder.decode_field())
+            .collect::<Result<Vec<T>, _>>()?;
+        match values.try_into() {
+            Ok(values) => Ok(values),
+            Err(_) => unreachable!("array length mismatch"),
+        }
+    }
+}
+
+/// Tuple is encoded as function params, like struct fields
+macro_rules! impl_sol_tuple {
+    ($first:ident $first_index:tt $(, $typ:ident $index:tt)*) => {
+        impl<$first: SolidityCodec $(, $typ: SolidityCodec)*> SolidityCodec for ($first, $($typ,)*) {
+            const IS_DYNAMIC: bool = $first::IS_DYNAMIC $(|| $typ::IS_DYNAMIC)*;
+            const HEAD_SIZE: usize = if Self::IS_DYNAMIC {
+                WORD_SIZE
+            } else {
+                $first::HEAD_SIZE $(+ $typ::HEAD_SIZE)*
+            };
+
+            fn sol_type(out: &mut String) {
+                out.push('(');
+                $first::sol_type(out);
+                $(
+                    out.push(',');
+                    $typ::sol_type(out);
+                )*
+                out.push(')');
+            }
+
+            fn sol_encode(&self, out: &mut Vec<u8>) {
+                let mut encoder =
+                    SolidityTupleEncoder::new($first::HEAD_SIZE $(+ $typ::HEAD_SIZE)*);
+                encoder.encode_field(&self.$first_index);
+                $( encoder.encode_field(&self.$index); )*
+                encoder.finalize(out);
+            }
+
+            fn sol_decode(input: &[u8], offset: usize) -> Result<Self, CodecError> {
+                let mut decoder = SolidityTupleDecoder::new(input, offset);
+                Ok((decoder.decode_field::<$first>()?, $(decoder.decode_field::<$typ>()?,)*))
+            }
+
+            fn abi_encode(&self) -> Vec<u8> {
+                let mut result = Vec::new();
+                self.sol_encode(&mut result);
+                result
+            }
+
+            fn abi_decode(input: &[u8]) -> Result<Self, CodecError> {
+                Self::sol_decode(input, 0)
+            }
+        }
+    };
+}
+
+impl_sol_tuple!(A1 0);
+impl_sol_tuple!(A1 0, A2 1);
+impl_sol_tuple!(A1 0, A2 1, A3 2);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8, A10 9);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8, A10 9, A11 10);
+impl_sol_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8, A10 9, A11 10, A12 11);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Codec, CodecError, SolidityCodec};
+    use alloy_primitives::{Address, Bytes, FixedBytes, I256, U256};
+    use fluentbase_codec_derive::derive_keccak256_id;
+    use hex_literal::hex;
+
+    #[derive(Default, Debug, PartialEq, Codec)]
+    #[codec(solidity)]
+    struct Sam {
+        a: Bytes,
+        b: bool,
+        c: Vec<U256>,
+    }
+
+    #[derive(Default, Debug, PartialEq, Codec)]
+    #[codec(solidity)]
+    struct F {
+        a: U256,
+        b: Vec<u32>,
+        c: FixedBytes<10>,
+        d: Bytes,
+    }
+
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Codec)]
+    #[codec(solidity)]
+    enum Status {
+        #[default]
+        Active,
+        Paused,
+    }
+
+    #[derive(Default, Debug, PartialEq, Codec)]
+    #[codec(solidity)]
+    struct Transfer(Address, U256, Status);
+
+    #[test]
+    fn test_selectors() {
+        // selectors from the Solidity ABI specification
+        assert_eq!(
+            derive_keccak256_id!(fn baz(x: u32, y: bool) -> bool),
+            0xcdcd77c0
+        );
+        assert_eq!(
+            derive_keccak256_id!(fn sam(a: Bytes, b: bool, c: Vec<U256>)),
+            0xa5643bf2
+        );
+        assert_eq!(
+            derive_keccak256_id!(fn f(a: U256, b: Vec<u32>, c: FixedBytes<10>, d: Bytes)),
+            0x8be65246
+        );
+        assert_eq!(
+            derive_keccak256_id!(fn transfer(to: Address, value: U256)),
+            0xa9059cbb
+        );
+        assert_eq!(Sam::sol_type_name(), "(bytes,bool,uint256[])");
+        assert_eq!(F::sol_type_name(), "(uint256,uint32[],bytes10,bytes)");
+        assert_eq!(Transfer::sol_type_name(), "(address,uint256,uint8)");
+    }
+
+    #[test]
+    fn test_dynamic_struct() {
+        let value = Sam {
+            a: Bytes::from_static(b"dave"),
+            b: true,
+            c: vec![U256::from(1), U256::from(2), U256::from(3)],
+        };
+        let expected = hex!(
+            "0000000000000000000000000000000000000000000000000000000000000060"
+            "0000000000000000000000000000000000000000000000000000000000000001"
+            "00000000000000000000000000000000000000000000000000000000000000a0"
+            "0000000000000000000000000000000000000000000000000000000000000004"
+            "6461766500000000000000000000000000000000000000000000000000000000"
+            "0000000000000000000000000000000000000000000000000000000000000003"
+            "0000000000000000000000000000000000000000000000000000000000000001"
+            "0000000000000000000000000000000000000000000000000000000000000002"
+            "0000000000000000000000000000000000000000000000000000000000000003"
+        );
+        assert!(Sam::IS_DYNAMIC);
+        assert_eq!(value.abi_encode(), expected);
+        assert_eq!(Sam::abi_decode(&expected), Ok(value));
+
+        let value = F {
+            a: U256::from(0x123),
+            b: vec![0x456, 0x789],
+            c: FixedBytes::from_slice(b"1234567890"),
+            d: Bytes::from_static(b"Hello, world!"),
+        };
+        let expected = hex!(
+            "0000000000000000000000000000000000000000000000000000000000000123"
+            "0000000000000000000000000000000000000000000000000000000000000080"
+            "3132333435363738393000000000000000000000000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000e0"
+            "0000000000000000000000000000000000000000000000000000000000000002"
+            "0000000000000000000000000000000000000000000000000000000000000456"
+            "0000000000000000000000000000000000000000000000000000000000000789"
+            "000000000000000000000000000000000000000000000000000000000000000d"
+            "48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+        );
+        assert_eq!(value.abi_encode(), expected);
+        assert_eq!(F::abi_decode(&expected), Ok(value));
+    }
+
+    #[test]
+    fn test_static_struct() {
+        let value = Transfer(Address::repeat_byte(0x11), U256::MAX, Status::Paused);
+        assert!(!Transfer::IS_DYNAMIC);
+        assert_eq!(Transfer::HEAD_SIZE, 96);
+        let encoded = value.abi_encode();
+        assert_eq!(encoded.len(), 96);
+        assert_eq!(encoded[..12], [0u8; 12]);
+        assert_eq!(encoded[95], 1);
+        assert_eq!(Transfer::abi_decode(&encoded), Ok(value));
+        // signed integers are sign-extended
+        assert_eq!((-1i32).abi_encode(), [0xff; 32]);
+        assert_eq!(i32::abi_decode(&[0xff; 32]), Ok(-1));
+    }
+
+    #[test]
+    fn test_extended_types() {
+        assert_eq!(
+            <(bool, String, Box<u8>, i128, I256)>::sol_type_name(),
+            "(bool,string,uint8,int128,int256)"
+        );
+        // string is encoded as UTF-8 bytes
+        let value = String::from("dave");
+        assert_eq!(value.abi_encode(), Bytes::from_static(b"dave").abi_encode());
+        assert_eq!(String::abi_decode(&value.abi_encode()), Ok(value));
+        // signed integers are sign-extended
+        assert_eq!((-1i128).abi_encode(), [0xff; 32]);
+        assert_eq!(I256::MINUS_ONE.abi_encode(), [0xff; 32]);
+        assert_eq!(I256::abi_decode(&[0xff; 32]), Ok(I256::MINUS_ONE));
+        let mut malicious = [0xff; 32];
+        malicious[0] = 0;
+        assert_eq!(
+            i128::abi_decode(&malicious),
+            Err(CodecError::InvalidPadding)
+        );
+        // tuples are encoded as function params
+        let value = (true, String::from("dave"), 3u8);
+        let encoded = value.abi_encode();
+        assert_eq!(encoded.len(), 5 * 32);
+        assert_eq!(encoded[63], 0x60);
+        assert_eq!(<(bool, String, u8)>::abi_decode(&encoded), Ok(value));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let encoded = Sam {
+            a: Bytes::from_static(b"dave"),
+            b: true,
+            c: vec![U256::from(1)],
+        }
+        .abi_encode();
+        // invalid bool
+        let mut malicious = encoded.clone();
+        malicious[63] = 2;
+        assert_eq!(Sam::abi_decode(&malicious), Err(CodecError::InvalidBool(2)));
+        // dirty high bits
+        let mut malicious = encoded.clone();
+        malicious[32] = 1;
+        assert_eq!(Sam::abi_decode(&malicious), Err(CodecError::InvalidPadding));
+        // offset and length point outside of the input
+        let mut malicious = encoded.clone();
+        malicious[31] = 0xff;
+        assert!(matches!(
+            Sam::abi_decode(&malicious),
+            Err(CodecError::OutOfBounds { .. })
+        ));
+        let mut malicious = encoded.clone();
+        malicious[24..32].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(matches!(
+            Sam::abi_decode(&malicious),
+            Err(CodecError::OutOfBounds { .. } | CodecError::LengthOverflow)
+        ));
+        assert!(matches!(
+            Sam::abi_decode(&encoded[..encoded.len() - 1]),
+            Err(CodecError::OutOfBounds { .. })
+        ));
+        // unknown enum variant
+        assert_eq!(
+            Status::abi_decode(&U256::from(2).abi_encode()),
+            Err(CodecError::UnknownVariant(2))
+        );
+    }
+}
@@ -1,6 +1,12 @@
-use super::{BufferDecoder, BufferEncoder, Encoder};
-use alloy_primitives::Bytes;
+use super::{
+    schema::{TypeSchema, Value},
+    BufferDecoder, BufferEncoder, ByteSource, CodecError, CodecSchema, Encoder, EncoderView,
+    FieldEncoder,
+};
+use crate::Codec;
+use alloy_primitives::{Address, Bytes, Signed, Uint, I256, U256};
 use hashbrown::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[test]
 fn test_vec() {
@@ -275,3 +281,613 @@ fn test_complex_tuple() {
     Tuple::decode_body(&mut decoder, 0, &mut result);
     assert_eq!(result, original_data)
 }
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+#[codec(view, schema)]
+struct Point(u32, u64);
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+struct Wrapper(Vec<u8>);
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+struct Unit;
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+#[codec(view, schema)]
+enum Message {
+    #[default]
+    Empty,
+    Transfer(u64, Vec<u8>),
+    Point(Point),
+    Named {
+        id: u32,
+        data: Bytes,
+        inner: Option<u16>,
+    },
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+enum Output<T: Default + Encoder<T>> {
+    Ok(T),
+    #[default]
+    Err,
+}
+
+fn encode_decode<T: Default + Encoder<T>>(value: &T) -> T {
+    let encoded = value.encode_to_vec(0);
+    let mut buffer_decoder = BufferDecoder::new(encoded.as_slice());
+    let mut result = Default::default();
+    T::decode_body(&mut buffer_decoder, 0, &mut result);
+    result
+}
+
+#[test]
+fn test_tuple_struct() {
+    assert_eq!(Point::HEADER_SIZE, 4 + 8);
+    assert_eq!(<Point as IPoint>::Field1::FIELD_OFFSET, 4);
+    let value = Point(100, u64::MAX);
+    assert_eq!(encode_decode(&value), value);
+    // newtype has the same layout as the inner type
+    assert_eq!(Wrapper::HEADER_SIZE, Vec::<u8>::HEADER_SIZE);
+    let value = Wrapper(vec![1, 2, 3]);
+    assert_eq!(value.encode_to_vec(0), value.0.encode_to_vec(0));
+    assert_eq!(encode_decode(&value), value);
+    assert_eq!(Unit::HEADER_SIZE, 0);
+    assert_eq!(encode_decode(&Unit), Unit);
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(Message::HEADER_SIZE, 12);
+    let values = vec![
+        Message::Empty,
+        Message::Transfer(7, vec![1, 2, 3]),
+        Message::Point(Point(1, 2)),
+        Message::Named {
+            id: 0xbadcab1e,
+            data: Bytes::from_static("Hello, World".as_bytes()),
+            inner: Some(3),
+        },
+    ];
+    for value in values.iter() {
+        assert_eq!(&encode_decode(value), value);
+    }
+    // discriminant is a variant index
+    let encoded = Message::Point(Point(1, 2)).encode_to_vec(0);
+    assert_eq!(BufferDecoder::new(&encoded).read_u32(0), 2);
+    // nested enums
+    assert_eq!(encode_decode(&values), values);
+    let value: Output<Message> = Output::Ok(values[3].clone());
+    assert_eq!(encode_decode(&value), value);
+    assert_eq!(encode_decode(&Output::<u32>::Err), Output::Err);
+}
+
+#[test]
+fn test_try_decode() {
+    let values = vec![
+        Message::Empty,
+        Message::Transfer(7, vec![1, 2, 3]),
+        Message::Named {
+            id: 1,
+            data: Bytes::from_static("Hello, World".as_bytes()),
+            inner: None,
+        },
+    ];
+    let encoded = values.encode_to_vec(0);
+    assert_eq!(Vec::<Message>::try_decode(&encoded), Ok(values));
+    let values = HashMap::from([
+        (vec![0, 1, 2], HashSet::from([3u8, 4])),
+        (vec![], HashSet::new()),
+    ]);
+    let encoded = values.encode_to_vec(0);
+    assert_eq!(
+        HashMap::<Vec<i32>, HashSet<u8>>::try_decode(&encoded),
+        Ok(values)
+    );
+    assert_eq!(Option::<Point>::try_decode(&[0; 13]), Ok(None));
+}
+
+#[test]
+fn test_try_decode_malformed() {
+    let encoded = vec![1u32, 2, 3].encode_to_vec(0);
+    // truncated header and body
+    assert_eq!(
+        Vec::<u32>::try_decode(&encoded[..8]),
+        Err(CodecError::OutOfBounds {
+            offset: 0,
+            length: 12
+        })
+    );
+    assert_eq!(
+        Vec::<u32>::try_decode(&encoded[..20]),
+        Err(CodecError::OutOfBounds {
+            offset: 12,
+            length: 12
+        })
+    );
+    // elements count doesn't match body length
+    let mut malicious = encoded.clone();
+    malicious[0..4].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        Vec::<u32>::try_decode(&malicious),
+        Err(CodecError::OutOfBounds { .. })
+    ));
+    // body offset points outside of the buffer
+    let mut malicious = encoded.clone();
+    malicious[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        Vec::<u32>::try_decode(&malicious),
+        Err(CodecError::OutOfBounds { .. })
+    ));
+    // trailing data
+    let mut malicious = encoded.clone();
+    malicious.push(0);
+    assert_eq!(
+        Vec::<u32>::try_decode(&malicious),
+        Err(CodecError::TrailingData(1))
+    );
+    // invalid bool and option flag
+    assert_eq!(bool::try_decode(&[2]), Err(CodecError::InvalidBool(2)));
+    assert_eq!(
+        Option::<u32>::try_decode(&[7, 0, 0, 0, 0]),
+        Err(CodecError::InvalidBool(7))
+    );
+    // unknown enum variant
+    let mut malicious = Message::Empty.encode_to_vec(0);
+    malicious[0..4].copy_from_slice(&9u32.to_le_bytes());
+    assert_eq!(
+        Message::try_decode(&malicious),
+        Err(CodecError::UnknownVariant(9))
+    );
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+#[codec(view, reader, schema)]
+struct Transaction {
+    nonce: u64,
+    data: Bytes,
+    access_list: Vec<(Point, Vec<u32>)>,
+    message: Message,
+}
+
+#[test]
+fn test_view() {
+    let value = Transaction {
+        nonce: 7,
+        data: Bytes::from_static("Hello, World".as_bytes()),
+        access_list: vec![(Point(1, 2), vec![3, 4]), (Point(5, 6), vec![])],
+        message: Message::Transfer(100, vec![1, 2, 3]),
+    };
+    let encoded = value.encode_to_vec(0);
+    let view = Transaction::view(&encoded, 0).unwrap();
+    assert_eq!(view.nonce(), Ok(7));
+    // dynamic fields are borrowed from the input buffer
+    let data = view.data().unwrap();
+    assert_eq!(data, value.data.as_ref());
+    assert!(encoded.as_ptr_range().contains(&data.as_ptr()));
+    // nested dynamic fields are decoded on access
+    let access_list = view.access_list().unwrap();
+    assert_eq!(access_list.len(), 2);
+    let (point, keys) = access_list.get(0).unwrap();
+    assert_eq!((point.field0(), point.field1()), (Ok(1), Ok(2)));
+    assert_eq!(
+        keys.iter().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![3u32, 4u32])
+    );
+    assert!(access_list.get(1).unwrap().1.is_empty());
+    assert!(access_list.get(2).is_err());
+    match view.message().unwrap() {
+        MessageRef::Transfer(amount, data) => {
+            assert_eq!(amount, 100);
+            assert_eq!(
+                data.iter().collect::<Result<Vec<_>, _>>(),
+                Ok(vec![1u8, 2, 3])
+            );
+        }
+        _ => unreachable!("unexpected variant"),
+    }
+    // malformed input is reported on access
+    assert!(Transaction::view(&encoded[..8], 0).is_err());
+    let mut malicious = encoded.clone();
+    malicious[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+    let view = Transaction::view(&malicious, 0).unwrap();
+    assert_eq!(view.nonce(), Ok(7));
+    assert!(matches!(view.data(), Err(CodecError::OutOfBounds { .. })));
+}
+
+/// Views and schemas are not generated without `#[codec(view)]` and `#[codec(schema)]`, so
+/// fields don't have to implement `EncoderView` and `CodecSchema`
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+struct Celsius(i16);
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+enum Reading {
+    #[default]
+    Missing,
+    Temperature(Celsius),
+}
+
+#[test]
+fn test_without_view_and_schema() {
+    let value = Reading::Temperature(Celsius(-5));
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(Reading::try_decode(&encoded), Ok(value));
+}
+
+#[test]
+fn test_reader() {
+    let value = Transaction {
+        nonce: 7,
+        data: Bytes::from_static("Hello, World".as_bytes()),
+        access_list: vec![(Point(1, 2), vec![3, 4])],
+        message: Message::Transfer(100, vec![1, 2, 3]),
+    };
+    let encoded = value.encode_to_vec(0);
+    let reader = TransactionReader::new(encoded.as_slice()).unwrap();
+    assert_eq!(reader.nonce(), Ok(7));
+    assert_eq!(reader.nonce_size(), 0);
+    assert_eq!(reader.data(), Ok(value.data.clone()));
+    assert_eq!(reader.data_size(), 12);
+    assert_eq!(reader.data_location(), (Transaction::HEADER_SIZE, 12));
+    assert_eq!(reader.access_list(), Ok(value.access_list.clone()));
+    assert_eq!(reader.message(), Ok(value.message.clone()));
+    // bytes outside of the source are read as zeros
+    let reader = TransactionReader::new(&encoded[..8]).unwrap();
+    assert_eq!(reader.nonce(), Ok(7));
+    assert_eq!(reader.data(), Ok(Bytes::new()));
+    let mut target = [0xffu8; 4];
+    encoded.as_slice().read(&mut target, encoded.len() - 2);
+    assert_eq!(
+        target,
+        [encoded[encoded.len() - 2], encoded[encoded.len() - 1], 0, 0]
+    );
+    encoded.as_slice().read(&mut target, encoded.len() + 10);
+    assert_eq!(target, [0; 4]);
+    // body offset and length are untrusted: body can overlap the header, and a body that is too
+    // small for its elements is an error
+    let mut bytes = Bytes::new();
+    let header: [u8; 8] = [0, 0, 0, 0, 4, 0, 0, 0];
+    FieldEncoder::<Bytes, 0>::read_field_body(header.as_slice(), &mut bytes).unwrap();
+    assert_eq!(bytes, Bytes::from_static(&[0, 0, 0, 0]));
+    let mut values: Vec<u32> = vec![];
+    let header: [u8; 12] = [100, 0, 0, 0, 12, 0, 0, 0, 8, 0, 0, 0];
+    assert_eq!(
+        FieldEncoder::<Vec<u32>, 0>::read_field_body(header.as_slice(), &mut values),
+        Err(CodecError::OutOfBounds {
+            offset: 0,
+            length: 400
+        })
+    );
+    assert!(values.capacity() < 100);
+}
+
+#[test]
+fn test_schema() {
+    assert_eq!(
+        Point::schema().to_json(),
+        concat!(
+            r#"{"type":"struct","header_size":12,"name":"Point","fields":["#,
+            r#"{"name":"0","offset":0,"schema":{"type":"uint","header_size":4,"bits":32}},"#,
+            r#"{"name":"1","offset":4,"schema":{"type":"uint","header_size":8,"bits":64}}]}"#,
+        )
+    );
+    let schema = Transaction::schema();
+    assert_eq!(schema.header_size(), Transaction::HEADER_SIZE);
+    // reference decoder decodes any buffer using only the schema
+    let value = Transaction {
+        nonce: 7,
+        data: Bytes::from_static(&[0xde, 0xad]),
+        access_list: vec![(Point(1, 2), vec![3])],
+        message: Message::Named {
+            id: 5,
+            data: Bytes::new(),
+            inner: None,
+        },
+    };
+    let encoded = value.encode_to_vec(0);
+    let decoded = schema.decode(&encoded).unwrap();
+    assert_eq!(
+        decoded.to_json(),
+        concat!(
+            r#"{"nonce":7,"data":"0xdead","access_list":[[{"0":1,"1":2},[3]]],"#,
+            r#""message":{"variant":"Named","fields":{"id":5,"data":"0x","inner":null}}}"#,
+        )
+    );
+    match decoded {
+        Value::Struct(fields) => {
+            assert_eq!(fields[0], ("nonce".to_string(), Value::UInt(U256::from(7))))
+        }
+        _ => unreachable!("unexpected value"),
+    }
+    // malformed input is rejected the same way as by `try_decode`
+    assert!(matches!(
+        schema.decode(&encoded[..8]),
+        Err(CodecError::OutOfBounds { .. })
+    ));
+    let mut with_trailing_data = encoded.clone();
+    with_trailing_data.push(0);
+    assert_eq!(
+        schema.decode(&with_trailing_data),
+        Err(CodecError::TrailingData(1))
+    );
+    assert!(matches!(
+        TypeSchema::Bool.decode(&[2]),
+        Err(CodecError::InvalidBool(2))
+    ));
+}
+
+#[test]
+fn test_string() {
+    // string is encoded as bytes: offset and length of UTF-8 bytes
+    let value = String::from("Hi");
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(hex::encode(&encoded), "08000000020000004869");
+    assert_eq!(String::try_decode(&encoded), Ok(value));
+    assert_eq!(String::view(&encoded, 0), Ok("Hi"));
+    let mut malicious = encoded.clone();
+    malicious[8] = 0xff;
+    assert_eq!(String::try_decode(&malicious), Err(CodecError::InvalidUtf8));
+}
+
+#[test]
+fn test_btree_map() {
+    // length, offset and length of keys, offset and length of values, then sorted keys and values
+    let values = BTreeMap::from([(2u32, 20u32), (1, 10)]);
+    let encoded = values.encode_to_vec(0);
+    assert_eq!(
+        hex::encode(&encoded),
+        concat!(
+            "02000000", "14000000", "08000000", "1c000000", "08000000", "01000000", "02000000",
+            "0a000000", "14000000",
+        )
+    );
+    // it has the same layout as hash map
+    assert_eq!(
+        HashMap::from([(1u32, 10u32), (2, 20)]).encode_to_vec(0),
+        encoded
+    );
+    assert_eq!(BTreeMap::try_decode(&encoded), Ok(values));
+    let set = BTreeSet::from([3u16, 1, 2]);
+    let encoded = set.encode_to_vec(0);
+    assert_eq!(
+        hex::encode(&encoded),
+        "030000000c00000006000000010002000300"
+    );
+    assert_eq!(HashSet::from([1u16, 2, 3]).encode_to_vec(0), encoded);
+    assert_eq!(BTreeSet::try_decode(&encoded), Ok(set));
+}
+
+#[test]
+fn test_address_map() {
+    let values = BTreeMap::from([
+        (Address::repeat_byte(0x22), U256::from(2)),
+        (Address::repeat_byte(0x11), U256::from(1)),
+    ]);
+    let encoded = values.encode_to_vec(0);
+    // keys are sorted addresses (20 bytes each), values are little-endian words
+    assert_eq!(encoded.len(), 20 + 2 * 20 + 2 * 32);
+    assert_eq!(&encoded[20..40], Address::repeat_byte(0x11).as_slice());
+    assert_eq!(encoded[60], 1);
+    let hash_map: HashMap<Address, U256> = values.clone().into_iter().collect();
+    assert_eq!(hash_map.encode_to_vec(0), encoded);
+    assert_eq!(HashMap::try_decode(&encoded), Ok(hash_map));
+    assert_eq!(BTreeMap::try_decode(&encoded), Ok(values));
+}
+
+#[test]
+fn test_box() {
+    // boxed value has the same layout as the value itself
+    let value = Box::new(Point(1, 2));
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(encoded, Point(1, 2).encode_to_vec(0));
+    assert_eq!(Box::<Point>::try_decode(&encoded), Ok(value));
+}
+
+#[test]
+fn test_large_tuple() {
+    // elements are encoded one by one, dynamic bodies follow the header
+    type Tuple = (u8, u16, Bytes);
+    let value: Tuple = (1, 2, Bytes::from_static(b"ab"));
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(Tuple::HEADER_SIZE, 11);
+    assert_eq!(hex::encode(&encoded), "0102000b000000020000006162");
+    assert_eq!(Tuple::try_decode(&encoded), Ok(value));
+    type Tuple12 = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, Vec<u8>);
+    let value: Tuple12 = (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, vec![12]);
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(Tuple12::HEADER_SIZE, 11 + 12);
+    assert_eq!(Tuple12::try_decode(&encoded), Ok(value));
+    assert_eq!((7u32,).encode_to_vec(0), 7u32.encode_to_vec(0));
+}
+
+#[test]
+fn test_large_ints() {
+    // 128-bit and signed big integers are little-endian (two's complement)
+    let encoded = (-2i128).encode_to_vec(0);
+    assert_eq!(hex::encode(&encoded), format!("fe{}", "ff".repeat(15)));
+    assert_eq!(i128::try_decode(&encoded), Ok(-2));
+    let encoded = u128::MAX.encode_to_vec(0);
+    assert_eq!(encoded, [0xff; 16]);
+    assert_eq!(u128::try_decode(&encoded), Ok(u128::MAX));
+    let value = I256::MINUS_ONE;
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(encoded, [0xff; 32]);
+    assert_eq!(I256::try_decode(&encoded), Ok(value));
+    assert_eq!(I256::schema().decode(&encoded), Ok(Value::Int(value)));
+}
+
+#[test]
+fn test_non_limb_ints() {
+    // integers are encoded as `Uint::BYTES` bytes, not padded to 64-bit limbs
+    type U160 = Uint<160, 3>;
+    type I20 = Signed<20, 1>;
+    let value = (U160::MAX, I20::MINUS_ONE, 7u8);
+    assert_eq!(U160::HEADER_SIZE, U160::BYTES);
+    assert_eq!(I20::HEADER_SIZE, Uint::<20, 1>::BYTES);
+    assert_eq!(U160::schema().header_size(), U160::HEADER_SIZE);
+    assert_eq!(I20::schema().header_size(), I20::HEADER_SIZE);
+    let encoded = value.encode_to_vec(0);
+    assert_eq!(encoded.len(), 20 + 3 + 1);
+    assert_eq!(hex::encode(&encoded[20..]), "ffff0f07");
+    assert_eq!(<(U160, I20, u8)>::try_decode(&encoded), Ok(value));
+    assert_eq!(
+        <(U160, I20, u8)>::schema().decode(&encoded),
+        Ok(Value::Tuple(vec![
+            Value::UInt(U256::from(U160::MAX)),
+            Value::Int(I256::MINUS_ONE),
+            Value::UInt(U256::from(7)),
+        ]))
+    );
+    // unused high bits of the last byte must be zero
+    let mut invalid = encoded.clone();
+    invalid[22] = 0x1f;
+    assert_eq!(
+        <(U160, I20, u8)>::try_decode(&invalid),
+        Err(CodecError::InvalidPadding)
+    );
+    assert_eq!(
+        <(U160, I20, u8)>::schema().decode(&invalid),
+        Err(CodecError::InvalidPadding)
+    );
+    // the same for integers that don't end on a byte boundary
+    type U12 = Uint<12, 1>;
+    assert_eq!(U12::from(0xabc).encode_to_vec(0), [0xbc, 0x0a]);
+    assert_eq!(U12::try_decode(&[0xbc, 0x0a]), Ok(U12::from(0xabc)));
+    assert_eq!(
+        U12::try_decode(&[0xbc, 0x1a]),
+        Err(CodecError::InvalidPadding)
+    );
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+#[codec(version = 1, schema)]
+struct ConfigV1 {
+    owner: u32,
+    data: Bytes,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Codec)]
+#[codec(version = 2, view, reader, schema)]
+struct ConfigV2 {
+    owner: u32,
+    data: Bytes,
+    #[codec(since = 2)]
+    limit: u64,
+    tags: Vec<u32>,
+}
+
+#[test]
+fn test_versioned_struct() {
+    let v1 = ConfigV1 {
+        owner: 1,
+        data: Bytes::from_static(b"abc"),
+    };
+    let old_encoded = v1.encode_to_vec(0);
+    // version, offset and length of the fields block, then fields and their bodies
+    assert_eq!(
+        hex::encode(&old_encoded),
+        concat!("01000000", "0c000000", "0c000000", "01000000", "18000000", "03000000", "616263",)
+    );
+    // field offsets of the top-level struct don't change when fields are appended
+    assert_eq!(
+        <ConfigV1 as IConfigV1>::Data::FIELD_OFFSET,
+        <ConfigV2 as IConfigV2>::Data::FIELD_OFFSET
+    );
+    // newer version decodes missing fields as default values
+    assert_eq!(
+        ConfigV2::try_decode(&old_encoded),
+        Ok(ConfigV2 {
+            owner: 1,
+            data: v1.data.clone(),
+            limit: 0,
+            tags: vec![],
+        })
+    );
+    let reader = ConfigV2Reader::new(old_encoded.as_slice()).unwrap();
+    assert_eq!(reader.data(), Ok(v1.data.clone()));
+    assert_eq!(reader.limit(), Ok(0));
+    assert_eq!(reader.tags_size(), 0);
+    assert_eq!(reader.tags_location(), (0, 0));
+    let view = ConfigV2::view(&old_encoded, 0).unwrap();
+    assert_eq!(view.owner(), Ok(1));
+    assert!(matches!(view.limit(), Err(CodecError::OutOfBounds { .. })));
+    assert_eq!(
+        ConfigV2::schema().decode(&old_encoded).unwrap().to_json(),
+        r#"{"owner":1,"data":"0x616263"}"#
+    );
+    // older version skips unknown fields
+    let v2 = ConfigV2 {
+        owner: 2,
+        data: Bytes::from_static(b"abc"),
+        limit: 100,
+        tags: vec![7],
+    };
+    let new_encoded = v2.encode_to_vec(0);
+    assert_eq!(
+        ConfigV1::try_decode(&new_encoded),
+        Ok(ConfigV1 {
+            owner: 2,
+            data: v2.data.clone(),
+        })
+    );
+    assert_eq!(ConfigV2::try_decode(&new_encoded), Ok(v2.clone()));
+    assert!(ConfigV1::schema().decode(&new_encoded).is_ok());
+    // fields block can be placed anywhere, f.e. after a gap, so it's located by the header
+    let mut shifted = new_encoded.clone();
+    shifted.splice(12..12, [0u8; 4]);
+    for offset in [4, 20, 40] {
+        let value = u32::from_le_bytes(shifted[offset..offset + 4].try_into().unwrap());
+        shifted[offset..offset + 4].copy_from_slice(&(value + 4).to_le_bytes());
+    }
+    assert_eq!(ConfigV2::try_decode(&shifted), Ok(v2.clone()));
+    let reader = ConfigV2Reader::new(shifted.as_slice()).unwrap();
+    assert_eq!(reader.owner(), Ok(2));
+    assert_eq!(reader.data(), Ok(v2.data.clone()));
+    assert_eq!(reader.limit(), Ok(100));
+    assert_eq!(reader.tags(), Ok(v2.tags.clone()));
+    assert_eq!(ConfigV2::view(&shifted, 0).unwrap().limit(), Ok(100));
+}
+
+#[test]
+fn test_struct_version_check() {
+    let v2 = ConfigV2 {
+        owner: 2,
+        data: Bytes::from_static(b"abc"),
+        limit: 100,
+        tags: vec![7],
+    };
+    let mut encoded = v2.encode_to_vec(0);
+    // version 0 is never valid
+    encoded[0] = 0;
+    assert_eq!(
+        ConfigV2::try_decode(&encoded),
+        Err(CodecError::InvalidVersion(0))
+    );
+    assert_eq!(
+        ConfigV1::try_decode(&encoded),
+        Err(CodecError::InvalidVersion(0))
+    );
+    // version 1 can't have fields added in version 2
+    encoded[0] = 1;
+    assert_eq!(
+        ConfigV2::try_decode(&encoded),
+        Err(CodecError::InvalidVersion(1))
+    );
+    assert_eq!(
+        ConfigV2::view(&encoded, 0).unwrap().owner(),
+        Err(CodecError::InvalidVersion(1))
+    );
+    assert!(matches!(
+        ConfigV2Reader::new(encoded.as_slice()),
+        Err(CodecError::InvalidVersion(1))
+    ));
+    assert_eq!(
+        ConfigV2::schema().decode(&encoded),
+        Err(CodecError::InvalidVersion(1))
+    );
+    // older decoder doesn't know about these fields
+    assert!(ConfigV1::try_decode(&encoded).is_ok());
+    // newer version is fine
+    encoded[0] = 3;
+    assert_eq!(ConfigV2::try_decode(&encoded), Ok(v2));
+}
@@ -1,26 +1,85 @@
-use crate::{BufferDecoder, Encoder, WritableBuffer};
+use crate::{BufferDecoder, CodecError, Encoder, WritableBuffer};
 
-impl<A1: Encoder<A1>, A2: Encoder<A2>> Encoder<(A1, A2)> for (A1, A2) {
-    const HEADER_SIZE: usize = A1::HEADER_SIZE + A2::HEADER_SIZE;
-
-    fn encode<W: WritableBuffer>(&self, encoder: &mut W, field_offset: usize) {
-        self.0.encode(encoder, field_offset);
-        self.1.encode(encoder, field_offset + A1::HEADER_SIZE);
+/// Merges body ranges `(offset, length)` of tuple elements, so the result covers bodies of all
+/// dynamic elements
+fn merge_body(body: (usize, usize), element: (usize, usize)) -> (usize, usize) {
+    match (body, element) {
+        (_, (_, 0)) => body,
+        ((_, 0), _) => element,
+        ((offset1, length1), (offset2, length2)) => {
+            let offset = offset1.min(offset2);
+            let end = (offset1 + length1).max(offset2 + length2);
+            (offset, end - offset)
+        }
     }
+}
 
-    fn decode_header(
-        decoder: &mut BufferDecoder,
-        field_offset: usize,
-        result: &mut (A1, A2),
-    ) -> (usize, usize) {
-        let (offset1, _) = A1::decode_header(decoder, field_offset, &mut result.0);
-        let (_, length2) =
-            A2::decode_header(decoder, field_offset + A1::HEADER_SIZE, &mut result.1);
-        (offset1, length2)
-    }
+/// Tuple elements are encoded one by one like struct fields
+macro_rules! impl_tuple {
+    ($($typ:ident $index:tt),+) => {
+        impl<$($typ: Encoder<$typ>),+> Encoder<($($typ,)+)> for ($($typ,)+) {
+            const HEADER_SIZE: usize = 0 $(+ $typ::HEADER_SIZE)+;
 
-    fn decode_body(decoder: &mut BufferDecoder, field_offset: usize, result: &mut (A1, A2)) {
-        A1::decode_body(decoder, field_offset, &mut result.0);
-        A2::decode_body(decoder, field_offset + A1::HEADER_SIZE, &mut result.1);
-    }
+            #[allow(unused_assignments)]
+            fn encode<W: WritableBuffer>(&self, encoder: &mut W, mut field_offset: usize) {
+                $(
+                    self.$index.encode(encoder, field_offset);
+                    field_offset += $typ::HEADER_SIZE;
+                )+
+            }
+
+            #[allow(unused_assignments)]
+            fn decode_header(
+                decoder: &mut BufferDecoder,
+                mut field_offset: usize,
+                result: &mut ($($typ,)+),
+            ) -> (usize, usize) {
+                let mut body = (0, 0);
+                $(
+                    let element = $typ::decode_header(decoder, field_offset, &mut result.$index);
+                    body = merge_body(body, element);
+                    field_offset += $typ::HEADER_SIZE;
+                )+
+                body
+            }
+
+            #[allow(unused_assignments)]
+            fn decode_body(
+                decoder: &mut BufferDecoder,
+                mut field_offset: usize,
+                result: &mut ($($typ,)+),
+            ) {
+                $(
+                    $typ::decode_body(decoder, field_offset, &mut result.$index);
+                    field_offset += $typ::HEADER_SIZE;
+                )+
+            }
+
+            #[allow(unused_assignments)]
+            fn try_decode_body(
+                decoder: &mut BufferDecoder,
+                mut field_offset: usize,
+                result: &mut ($($typ,)+),
+            ) -> Result<(), CodecError> {
+                $(
+                    $typ::try_decode_body(decoder, field_offset, &mut result.$index)?;
+                    field_offset += $typ::HEADER_SIZE;
+                )+
+                Ok(())
+            }
+        }
+    };
 }
+
+impl_tuple!(A1 0);
+impl_tuple!(A1 0, A2 1);
+impl_tuple!(A1 0, A2 1, A3 2);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8, A10 9);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8, A10 9, A11 10);
+impl_tuple!(A1 0, A2 1, A3 2, A4 3, A5 4, A6 5, A7 6, A8 7, A9 8, A10 9, A11 10, A12 11);
@@ -1,4 +1,4 @@
-use crate::{buffer::WritableBuffer, BufferDecoder, BufferEncoder, Encoder};
+use crate::{buffer::WritableBuffer, BufferDecoder, BufferEncoder, CodecError, Encoder};
 use alloc::vec::Vec;
 
 ///
@@ -30,11 +30,12 @@ impl<T: Default + Sized + Encoder<T>> Encoder<Vec<T>> for Vec<T> {
         field_offset: usize,
         result: &mut Vec<T>,
     ) -> (usize, usize) {
-        let count = decoder.read_u32(field_offset) as usize;
+        let (offset, length) = decoder.read_bytes_header(field_offset + 4);
+        // count is untrusted, but every element header must fit into the body
+        let count = (decoder.read_u32(field_offset) as usize).min(length / T::HEADER_SIZE.max(1));
         if count > result.capacity() {
             result.reserve(count - result.capacity());
         }
-        let (offset, length) = decoder.read_bytes_header(field_offset + 4);
         (offset, length)
     }
 
@@ -54,4 +55,22 @@ impl<T: Default + Sized + Encoder<T>> Encoder<Vec<T>> for Vec<T> {
             })
             .collect()
     }
+
+    fn try_decode_body(
+        decoder: &mut BufferDecoder,
+        field_offset: usize,
+        result: &mut Vec<T>,
+    ) -> Result<(), CodecError> {
+        let input_len = decoder.try_read_u32(field_offset)? as usize;
+        let input_bytes = decoder.try_read_array(field_offset + 4, input_len, T::HEADER_SIZE)?;
+        let mut value_decoder = BufferDecoder::new(input_bytes);
+        result.clear();
+        result.reserve(input_len);
+        for i in 0..input_len {
+            let mut value = T::default();
+            T::try_decode_body(&mut value_decoder, T::HEADER_SIZE * i, &mut value)?;
+            result.push(value);
+        }
+        Ok(())
+    }
 }
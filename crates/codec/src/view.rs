@@ -0,0 +1,330 @@
+use crate::{BufferDecoder, CodecError, EmptyVec, Encoder};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use alloy_primitives::{Address, Bytes, FixedBytes, Signed, Uint};
+use core::marker::PhantomData;
+use hashbrown::{HashMap, HashSet};
+
+/// Borrowed view over an encoded value, it reads fields directly from the input buffer without
+/// copying dynamic data.
+///
+/// Primitives are viewed as values, `Bytes` as `&[u8]`, vectors and arrays as `ListView` and
+/// structs as generated `{Struct}Ref<'a>` with field accessors.
+pub trait EncoderView<'a> {
+    type View;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError>;
+}
+
+macro_rules! impl_view_copy {
+    ($($typ:ty),*) => {
+        $(
+            impl<'a> EncoderView<'a> for $typ {
+                type View = $typ;
+
+                fn view(buffer: &'a [u8], field_offset: usize) -> Result<$typ, CodecError> {
+                    let mut decoder = BufferDecoder::new(buffer);
+                    let mut result = Default::default();
+                    <$typ>::try_decode_body(&mut decoder, field_offset, &mut result)?;
+                    Ok(result)
+                }
+            }
+        )*
+    };
+}
+
+impl_view_copy!(u8, bool, u16, u32, u64, u128, i16, i32, i64, i128, Address, EmptyVec);
+
+impl<'a, const N: usize> EncoderView<'a> for FixedBytes<N> {
+    type View = FixedBytes<N>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<FixedBytes<N>, CodecError> {
+        let bytes = BufferDecoder::new(buffer).try_read_slice(field_offset, N)?;
+        Ok(FixedBytes::from_slice(bytes))
+    }
+}
+
+impl<'a, const BITS: usize, const LIMBS: usize> EncoderView<'a> for Uint<BITS, LIMBS> {
+    type View = Uint<BITS, LIMBS>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Uint<BITS, LIMBS>, CodecError> {
+        let mut result = Uint::ZERO;
+        Self::try_decode_body(&mut BufferDecoder::new(buffer), field_offset, &mut result)?;
+        Ok(result)
+    }
+}
+
+impl<'a, const BITS: usize, const LIMBS: usize> EncoderView<'a> for Signed<BITS, LIMBS> {
+    type View = Signed<BITS, LIMBS>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Signed<BITS, LIMBS>, CodecError> {
+        let mut result = Signed::ZERO;
+        Self::try_decode_body(&mut BufferDecoder::new(buffer), field_offset, &mut result)?;
+        Ok(result)
+    }
+}
+
+impl<'a> EncoderView<'a> for Bytes {
+    type View = &'a [u8];
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<&'a [u8], CodecError> {
+        BufferDecoder::new(buffer).try_read_bytes(field_offset)
+    }
+}
+
+impl<'a> EncoderView<'a> for String {
+    type View = &'a str;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<&'a str, CodecError> {
+        let bytes = BufferDecoder::new(buffer).try_read_bytes(field_offset)?;
+        core::str::from_utf8(bytes).map_err(|_| CodecError::InvalidUtf8)
+    }
+}
+
+impl<'a, T: EncoderView<'a>> EncoderView<'a> for Box<T> {
+    type View = T::View;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        T::view(buffer, field_offset)
+    }
+}
+
+impl<'a, T: EncoderView<'a>> EncoderView<'a> for Option<T> {
+    type View = Option<T::View>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        match BufferDecoder::new(buffer).try_read_u8(field_offset)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::view(buffer, field_offset + 1)?)),
+            value => Err(CodecError::InvalidBool(value)),
+        }
+    }
+}
+
+macro_rules! impl_view_tuple {
+    ($($typ:ident),+) => {
+        impl<'a, $($typ: Encoder<$typ> + EncoderView<'a>),+> EncoderView<'a> for ($($typ,)+) {
+            type View = ($($typ::View,)+);
+
+            #[allow(unused_assignments)]
+            fn view(buffer: &'a [u8], mut field_offset: usize) -> Result<Self::View, CodecError> {
+                Ok(($(
+                    {
+                        let view = $typ::view(buffer, field_offset)?;
+                        field_offset += $typ::HEADER_SIZE;
+                        view
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_view_tuple!(A1);
+impl_view_tuple!(A1, A2);
+impl_view_tuple!(A1, A2, A3);
+impl_view_tuple!(A1, A2, A3, A4);
+impl_view_tuple!(A1, A2, A3, A4, A5);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6, A7);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_view_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+
+/// View over encoded elements of a vector or an array, elements are decoded on access
+pub struct ListView<'a, T> {
+    buffer: &'a [u8],
+    offset: usize,
+    len: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Clone for ListView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for ListView<'a, T> {}
+
+impl<'a, T: Encoder<T> + EncoderView<'a>> ListView<'a, T> {
+    /// Creates view over `len` elements starting at `offset`, buffer must fit all the headers
+    pub fn new(buffer: &'a [u8], offset: usize, len: usize) -> Result<Self, CodecError> {
+        let length = len
+            .checked_mul(T::HEADER_SIZE)
+            .ok_or(CodecError::LengthOverflow)?;
+        BufferDecoder::new(buffer).try_read_slice(offset, length)?;
+        Ok(Self {
+            buffer,
+            offset,
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Result<T::View, CodecError> {
+        if index >= self.len {
+            return Err(CodecError::OutOfBounds {
+                offset: index,
+                length: self.len,
+            });
+        }
+        T::view(self.buffer, self.offset + index * T::HEADER_SIZE)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Result<T::View, CodecError>> + 'a
+    where
+        T: 'a,
+    {
+        let list = *self;
+        (0..self.len).map(move |i| list.get(i))
+    }
+}
+
+impl<'a, T: Encoder<T> + EncoderView<'a>> EncoderView<'a> for Vec<T> {
+    type View = ListView<'a, T>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        let decoder = BufferDecoder::new(buffer);
+        let len = decoder.try_read_u32(field_offset)? as usize;
+        let body = decoder.try_read_bytes(field_offset + 4)?;
+        ListView::new(body, 0, len)
+    }
+}
+
+impl<'a, T: Encoder<T> + EncoderView<'a>, const N: usize> EncoderView<'a> for [T; N] {
+    type View = ListView<'a, T>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        ListView::new(buffer, field_offset, N)
+    }
+}
+
+/// View over encoded map, keys are sorted because encoder sorts entries
+pub struct MapView<'a, K, V> {
+    keys: ListView<'a, K>,
+    values: ListView<'a, V>,
+}
+
+impl<'a, K, V> Clone for MapView<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for MapView<'a, K, V> {}
+
+impl<'a, K: Encoder<K> + EncoderView<'a>, V: Encoder<V> + EncoderView<'a>> MapView<'a, K, V> {
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn keys(&self) -> ListView<'a, K> {
+        self.keys
+    }
+
+    pub fn values(&self) -> ListView<'a, V> {
+        self.values
+    }
+
+    pub fn get_index(&self, index: usize) -> Result<(K::View, V::View), CodecError> {
+        Ok((self.keys.get(index)?, self.values.get(index)?))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K::View, V::View), CodecError>> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let map = *self;
+        (0..self.len()).map(move |i| map.get_index(i))
+    }
+}
+
+impl<'a, K: Encoder<K> + EncoderView<'a>, V: Encoder<V> + EncoderView<'a>> MapView<'a, K, V> {
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self, CodecError> {
+        let decoder = BufferDecoder::new(buffer);
+        let len = decoder.try_read_u32(field_offset)? as usize;
+        let keys = decoder.try_read_bytes(field_offset + 4)?;
+        let values = decoder.try_read_bytes(field_offset + 12)?;
+        Ok(MapView {
+            keys: ListView::new(keys, 0, len)?,
+            values: ListView::new(values, 0, len)?,
+        })
+    }
+}
+
+impl<'a, K, V> EncoderView<'a> for HashMap<K, V>
+where
+    K: Encoder<K> + EncoderView<'a>,
+    V: Encoder<V> + EncoderView<'a>,
+{
+    type View = MapView<'a, K, V>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        MapView::view(buffer, field_offset)
+    }
+}
+
+impl<'a, K, V> EncoderView<'a> for BTreeMap<K, V>
+where
+    K: Encoder<K> + EncoderView<'a>,
+    V: Encoder<V> + EncoderView<'a>,
+{
+    type View = MapView<'a, K, V>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        MapView::view(buffer, field_offset)
+    }
+}
+
+/// Set elements are stored the same way as vector elements
+fn view_set<'a, T: Encoder<T> + EncoderView<'a>>(
+    buffer: &'a [u8],
+    field_offset: usize,
+) -> Result<ListView<'a, T>, CodecError> {
+    let decoder = BufferDecoder::new(buffer);
+    let len = decoder.try_read_u32(field_offset)? as usize;
+    let body = decoder.try_read_bytes(field_offset + 4)?;
+    ListView::new(body, 0, len)
+}
+
+impl<'a, T> EncoderView<'a> for HashSet<T>
+where
+    T: Encoder<T> + EncoderView<'a>,
+{
+    type View = ListView<'a, T>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        view_set(buffer, field_offset)
+    }
+}
+
+impl<'a, T> EncoderView<'a> for BTreeSet<T>
+where
+    T: Encoder<T> + EncoderView<'a>,
+{
+    type View = ListView<'a, T>;
+
+    fn view(buffer: &'a [u8], field_offset: usize) -> Result<Self::View, CodecError> {
+        view_set(buffer, field_offset)
+    }
+}
@@ -371,6 +371,10 @@ impl Account {
         LowLevelSDK::jzkt_checkpoint()
     }
 
+    pub fn checkpoint_commit() {
+        LowLevelSDK::jzkt_checkpoint_commit();
+    }
+
     pub fn commit() -> B256 {
         let mut root = B256::ZERO;
         LowLevelSDK::jzkt_commit(root.as_mut_ptr());
@@ -1,15 +1,11 @@
 use fluentbase_sdk::Bytes32;
 
-/// Number of fields
-pub const JZKT_ACCOUNT_FIELDS_COUNT: u32 = 6;
-pub const JZKT_STORAGE_FIELDS_COUNT: u32 = 1;
-
-pub const JZKT_ACCOUNT_BALANCE_FIELD: u32 = 0;
-pub const JZKT_ACCOUNT_NONCE_FIELD: u32 = 1;
-pub const JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD: u32 = 2;
-pub const JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD: u32 = 3;
-pub const JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD: u32 = 4;
-pub const JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD: u32 = 5;
+pub use fluentbase_types::{
+    JZKT_ACCOUNT_BALANCE_FIELD, JZKT_ACCOUNT_FIELDS_COUNT, JZKT_ACCOUNT_NONCE_FIELD,
+    JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD,
+    JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD,
+    JZKT_STORAGE_FIELDS_COUNT,
+};
 
 /// Compression flags for upper fields.
 ///
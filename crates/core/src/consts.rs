@@ -4,12 +4,70 @@ pub const ECL_CONTRACT_ADDRESS: Address = address!("5200000000000000000000000000
 pub const WCL_CONTRACT_ADDRESS: Address = address!("5200000000000000000000000000000000000002");
 
 // precompiles
-pub const PRECOMPILE_BLAKE2_ADDRESS: Address = address!("0000000000000000000000000000000000000001");
-pub const PRECOMPILE_BN128_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
+pub const PRECOMPILE_BLAKE2_ADDRESS: Address = address!("0000000000000000000000000000000000000009");
+pub const PRECOMPILE_BLS12_381_G1_ADD_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000b");
+pub const PRECOMPILE_BLS12_381_G1_MUL_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000c");
+pub const PRECOMPILE_BLS12_381_G1_MSM_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000d");
+pub const PRECOMPILE_BLS12_381_G2_ADD_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000e");
+pub const PRECOMPILE_BLS12_381_G2_MUL_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000f");
+pub const PRECOMPILE_BLS12_381_G2_MSM_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000010");
+pub const PRECOMPILE_BLS12_381_PAIRING_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000011");
+pub const PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000012");
+pub const PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000013");
+pub const PRECOMPILE_BN128_ADD_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000006");
+pub const PRECOMPILE_BN128_MUL_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000007");
+pub const PRECOMPILE_BN128_PAIR_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000008");
 pub const PRECOMPILE_IDENTITY_ADDRESS: Address =
-    address!("0000000000000000000000000000000000000003");
-pub const PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS: Address =
     address!("0000000000000000000000000000000000000004");
+pub const PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000a");
 pub const PRECOMPILE_MODEXP_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
+pub const PRECOMPILE_RIPEMD160_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000003");
 pub const PRECOMPILE_SECP256K1_ADDRESS: Address =
-    address!("0000000000000000000000000000000000000006");
+    address!("0000000000000000000000000000000000000001");
+/// P256VERIFY address from RIP-7212
+pub const PRECOMPILE_SECP256R1_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000100");
+pub const PRECOMPILE_SHA256_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
+
+/// All precompile addresses that are deployed as rWASM contracts at genesis
+pub const PRECOMPILE_ADDRESSES: [Address; 20] = [
+    PRECOMPILE_SECP256K1_ADDRESS,
+    PRECOMPILE_SHA256_ADDRESS,
+    PRECOMPILE_RIPEMD160_ADDRESS,
+    PRECOMPILE_IDENTITY_ADDRESS,
+    PRECOMPILE_MODEXP_ADDRESS,
+    PRECOMPILE_BN128_ADD_ADDRESS,
+    PRECOMPILE_BN128_MUL_ADDRESS,
+    PRECOMPILE_BN128_PAIR_ADDRESS,
+    PRECOMPILE_BLAKE2_ADDRESS,
+    PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
+    PRECOMPILE_BLS12_381_G1_ADD_ADDRESS,
+    PRECOMPILE_BLS12_381_G1_MUL_ADDRESS,
+    PRECOMPILE_BLS12_381_G1_MSM_ADDRESS,
+    PRECOMPILE_BLS12_381_G2_ADD_ADDRESS,
+    PRECOMPILE_BLS12_381_G2_MUL_ADDRESS,
+    PRECOMPILE_BLS12_381_G2_MSM_ADDRESS,
+    PRECOMPILE_BLS12_381_PAIRING_ADDRESS,
+    PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS,
+    PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS,
+    PRECOMPILE_SECP256R1_ADDRESS,
+];
+
+#[inline(always)]
+pub fn is_precompile_address(address: &Address) -> bool {
+    PRECOMPILE_ADDRESSES.contains(address)
+}
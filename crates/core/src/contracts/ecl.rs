@@ -22,12 +22,14 @@ pub fn main() {
     let method_id = input_helper.decode_method_id();
     match method_id {
         EVM_CREATE_METHOD_ID => {
-            let method_input = input_helper.decode_method_input::<EvmCreateMethodInput>();
+            let method_input =
+                unwrap_exit_code(input_helper.decode_method_input::<EvmCreateMethodInput>());
             let address = unwrap_exit_code(_evm_create(method_input));
             LowLevelSDK::sys_write(address.as_slice())
         }
         EVM_CALL_METHOD_ID => {
-            let method_input = input_helper.decode_method_input::<EvmCallMethodInput>();
+            let method_input =
+                unwrap_exit_code(input_helper.decode_method_input::<EvmCallMethodInput>());
             let method_output = _evm_call(method_input);
             if !method_output.output.is_empty() {
                 LowLevelSDK::sys_write(method_output.output.as_ref());
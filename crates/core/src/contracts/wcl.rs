@@ -17,12 +17,14 @@ pub fn main() {
     let method_id = input_helper.decode_method_id();
     match method_id {
         WASM_CREATE_METHOD_ID => {
-            let method_input = input_helper.decode_method_input::<WasmCreateMethodInput>();
+            let method_input =
+                unwrap_exit_code(input_helper.decode_method_input::<WasmCreateMethodInput>());
             let address = unwrap_exit_code(_wasm_create(method_input));
             LowLevelSDK::sys_write(address.as_slice());
         }
         WASM_CALL_METHOD_ID => {
-            let method_input = input_helper.decode_method_input::<WasmCallMethodInput>();
+            let method_input =
+                unwrap_exit_code(input_helper.decode_method_input::<WasmCallMethodInput>());
             let method_output = _wasm_call(method_input);
             if !method_output.output.is_empty() {
                 LowLevelSDK::sys_write(method_output.output.as_ref());
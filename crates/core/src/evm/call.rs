@@ -35,6 +35,7 @@ pub fn _evm_call(input: EvmCallMethodInput) -> EvmCallMethodOutput {
     match Account::transfer(&mut caller_account, &mut callee_account, input.value) {
         Ok(_) => {}
         Err(exit_code) => {
+            Account::rollback(checkpoint);
             return EvmCallMethodOutput::from_exit_code(exit_code).with_gas(input.gas_limit);
         }
     }
@@ -48,7 +49,7 @@ pub fn _evm_call(input: EvmCallMethodInput) -> EvmCallMethodOutput {
 
     // if bytecode is empty then commit result and return empty buffer
     if bytecode.is_empty() {
-        Account::commit();
+        Account::checkpoint_commit();
         return EvmCallMethodOutput::from_exit_code(ExitCode::Ok).with_gas(input.gas_limit);
     }
 
@@ -75,7 +76,7 @@ pub fn _evm_call(input: EvmCallMethodInput) -> EvmCallMethodOutput {
     callee_account.write_to_jzkt();
 
     if matches!(result.result, return_ok!()) {
-        Account::commit();
+        Account::checkpoint_commit();
     } else {
         Account::rollback(checkpoint);
     }
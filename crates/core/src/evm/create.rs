@@ -86,7 +86,7 @@ pub fn _evm_create(input: EvmCreateMethodInput) -> Result<Address, ExitCode> {
         callee_account.address
     ));
 
-    Account::commit();
+    Account::checkpoint_commit();
 
     Ok(callee_account.address)
 }
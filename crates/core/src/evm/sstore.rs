@@ -15,6 +15,7 @@ pub fn _evm_sstore(
         value32_offset as *const [u8; 32],
         32,
     );
+    LowLevelSDK::jzkt_update_storage_slot(storage_key.as_ptr(), address.as_ptr(), slot32_offset);
     Ok(true)
 }
 
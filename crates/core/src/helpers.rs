@@ -5,11 +5,11 @@ use crate::{
     account_types::JZKT_ACCOUNT_BALANCE_FIELD, fluent_host::FluentHost, Account, AccountCheckpoint,
 };
 use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
-use byteorder::{ByteOrder, LittleEndian};
 use core::str::from_utf8;
 use fluentbase_codec::{BufferDecoder, Encoder};
+pub use fluentbase_sdk::storage::calc_storage_key;
 use fluentbase_sdk::{
-    evm::{ContractInput, ExecutionContext, IContractInput},
+    evm::{ContractInput, ExecutionContext},
     Bytes32, CoreInput, EvmCallMethodInput, EvmCreateMethodInput, ICoreInput, LowLevelAPI,
     LowLevelSDK, EVM_CALL_METHOD_ID,
 };
@@ -39,14 +39,7 @@ pub type DefaultEvmSpec = revm_interpreter::primitives::ShanghaiSpec;
 
 #[inline]
 pub(crate) fn get_contract_input_offset_and_len() -> (u32, u32) {
-    let mut header = [0u8; 8];
-    LowLevelSDK::sys_read(
-        &mut header,
-        <ContractInput as IContractInput>::ContractInput::FIELD_OFFSET as u32,
-    );
-    let offset = LittleEndian::read_u32(&header[0..4]);
-    let length = LittleEndian::read_u32(&header[4..8]);
-    (offset, length)
+    ExecutionContext::contract_input_location()
 }
 
 #[inline(always)]
@@ -118,37 +111,6 @@ pub fn debug_log(msg: &str) {
     LowLevelSDK::debug_log(msg_bytes.as_ptr(), msg_bytes.len() as u32)
 }
 
-const DOMAIN: [u8; 32] = [0u8; 32];
-
-#[inline(always)]
-pub fn calc_storage_key(address: &Address, slot32_le_ptr: *const u8) -> [u8; 32] {
-    let mut slot0: [u8; 32] = [0u8; 32];
-    let mut slot1: [u8; 32] = [0u8; 32];
-    // split slot32 into two 16 byte values (slot is always 32 bytes)
-    unsafe {
-        core::ptr::copy(slot32_le_ptr.offset(0), slot0.as_mut_ptr(), 16);
-        core::ptr::copy(slot32_le_ptr.offset(16), slot1.as_mut_ptr(), 16);
-    }
-    // pad address to 32 bytes value (11 bytes to avoid 254 overflow)
-    let mut address32: [u8; 32] = [0u8; 32];
-    address32[11..31].copy_from_slice(address.as_slice());
-    // compute a storage key, where formula is `p(address, p(slot_0, slot_1))`
-    let mut storage_key: [u8; 32] = [0u8; 32];
-    LowLevelSDK::crypto_poseidon2(
-        slot0.as_ptr(),
-        slot1.as_ptr(),
-        DOMAIN.as_ptr(),
-        storage_key.as_mut_ptr(),
-    );
-    LowLevelSDK::crypto_poseidon2(
-        address32.as_ptr(),
-        storage_key.as_ptr(),
-        DOMAIN.as_ptr(),
-        storage_key.as_mut_ptr(),
-    );
-    storage_key
-}
-
 fn contract_input_from_call_inputs(
     gas_limit: u64,
     callee_address: Address,
@@ -379,10 +341,14 @@ impl InputHelper {
         method_id
     }
 
-    pub(crate) fn decode_method_input<T: Encoder<T> + Default>(&self) -> T {
-        let mut core_input = T::default();
-        <CoreInput<T> as ICoreInput>::MethodData::decode_field_body(&self.input, &mut core_input);
-        core_input
+    pub(crate) fn decode_method_input<T: Encoder<T> + Default>(&self) -> Result<T, ExitCode> {
+        // contract input is controlled by the caller, so malformed input must not trap
+        CoreInput::<T>::try_decode(&self.input)
+            .map(|core_input| core_input.method_data)
+            .map_err(|err| {
+                debug_log(&format!("failed to decode method input: {}", err));
+                ExitCode::InputDecodeFailure
+            })
     }
 }
 
@@ -18,14 +18,6 @@ pub mod consts;
 mod contracts;
 pub mod fluent_host;
 pub mod helpers;
-#[cfg(any(
-    feature = "precompile_blake2",
-    feature = "precompile_bn128",
-    feature = "precompile_identity",
-    feature = "precompile_kzg_point_evaluation",
-    feature = "precompile_modexp",
-    feature = "precompile_secp256k1",
-))]
 pub mod precompiles;
 pub mod wasm;
 
@@ -36,11 +28,15 @@ macro_rules! export_and_forward {
             feature = "loader",
             feature = "wcl",
             feature = "precompile_blake2",
+            feature = "precompile_bls12_381",
             feature = "precompile_bn128",
             feature = "precompile_identity",
             feature = "precompile_kzg_point_evaluation",
             feature = "precompile_modexp",
+            feature = "precompile_ripemd160",
             feature = "precompile_secp256k1",
+            feature = "precompile_secp256r1",
+            feature = "precompile_sha256",
         ))]
         #[cfg(not(feature = "std"))]
         #[no_mangle]
@@ -54,12 +50,24 @@ macro_rules! export_and_forward {
             contracts::wcl::$fn_name();
             #[cfg(feature = "precompile_blake2")]
             precompiles::blake2::$fn_name();
+            #[cfg(feature = "precompile_bls12_381")]
+            precompiles::bls12_381::$fn_name();
+            #[cfg(feature = "precompile_bn128")]
+            precompiles::bn128::$fn_name();
             #[cfg(feature = "precompile_identity")]
             precompiles::identity::$fn_name();
+            #[cfg(feature = "precompile_kzg_point_evaluation")]
+            precompiles::kzg_point_evaluation::$fn_name();
             #[cfg(feature = "precompile_modexp")]
             precompiles::modexp::$fn_name();
+            #[cfg(feature = "precompile_ripemd160")]
+            precompiles::ripemd160::$fn_name();
             #[cfg(feature = "precompile_secp256k1")]
             precompiles::secp256k1::$fn_name();
+            #[cfg(feature = "precompile_secp256r1")]
+            precompiles::secp256r1::$fn_name();
+            #[cfg(feature = "precompile_sha256")]
+            precompiles::sha256::$fn_name();
         }
     };
 }
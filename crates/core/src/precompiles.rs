@@ -1,6 +1,74 @@
+use alloc::vec::Vec;
+use fluentbase_sdk::{LowLevelAPI, LowLevelSDK};
+use fluentbase_types::ExitCode;
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+#[cfg(feature = "precompile_blake2")]
 pub mod blake2;
+#[cfg(feature = "precompile_bls12_381")]
+pub mod bls12_381;
+#[cfg(feature = "precompile_bn128")]
 pub mod bn128;
+#[cfg(feature = "precompile_identity")]
 pub mod identity;
+#[cfg(feature = "precompile_kzg_point_evaluation")]
 pub mod kzg_point_evaluation;
+#[cfg(feature = "precompile_modexp")]
 pub mod modexp;
+#[cfg(feature = "precompile_ripemd160")]
+pub mod ripemd160;
+#[cfg(feature = "precompile_secp256k1")]
 pub mod secp256k1;
+#[cfg(feature = "precompile_secp256r1")]
+pub mod secp256r1;
+#[cfg(feature = "precompile_sha256")]
+pub mod sha256;
+
+/// Size of the little-endian gas prefix that every precompile contract puts in front of its
+/// output, so the caller can charge the EIP gas instead of the rWASM fuel spent on execution.
+pub const PRECOMPILE_GAS_USED_SIZE: usize = core::mem::size_of::<u64>();
+
+pub fn encode_precompile_output(gas_used: u64, output: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(PRECOMPILE_GAS_USED_SIZE + output.len());
+    result.extend_from_slice(&gas_used.to_le_bytes());
+    result.extend_from_slice(output);
+    result
+}
+
+pub fn decode_precompile_output(output: &[u8]) -> Option<(u64, &[u8])> {
+    if output.len() < PRECOMPILE_GAS_USED_SIZE {
+        return None;
+    }
+    let (gas_used, output) = output.split_at(PRECOMPILE_GAS_USED_SIZE);
+    Some((u64::from_le_bytes(gas_used.try_into().unwrap()), output))
+}
+
+pub(crate) fn write_precompile_result(result: PrecompileResult) {
+    match result {
+        Ok((gas_used, output)) => {
+            LowLevelSDK::sys_write(&encode_precompile_output(gas_used, output.as_ref()));
+        }
+        Err(PrecompileError::OutOfGas) => {
+            LowLevelSDK::sys_halt(ExitCode::OutOfFuel.into_i32());
+        }
+        Err(_) => {
+            LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precompile_output_roundtrip() {
+        let output = encode_precompile_output(3_000, &[0xaa, 0xbb]);
+        assert_eq!(output.len(), PRECOMPILE_GAS_USED_SIZE + 2);
+        assert_eq!(
+            decode_precompile_output(&output),
+            Some((3_000, [0xaa, 0xbb].as_slice()))
+        );
+        assert_eq!(decode_precompile_output(&[0u8; 7]), None);
+    }
+}
@@ -1,26 +1,83 @@
+use crate::precompiles::write_precompile_result;
 use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
 use fluentbase_types::{Bytes, ExitCode};
-use revm_interpreter::primitives::PrecompileError;
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+const F_ROUND: u64 = 1;
+const INPUT_LENGTH: usize = 213;
 
 pub fn deploy() {}
 
+/// Input format (EIP-152):
+/// [4 bytes for rounds][64 bytes for h][128 bytes for m][8 bytes for t_0][8 bytes for t_1]
+/// [1 byte for f]
+pub(crate) fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if input.len() != INPUT_LENGTH {
+        return Err(PrecompileError::Blake2WrongLength);
+    }
+    let f = match input[212] {
+        1 => true,
+        0 => false,
+        _ => return Err(PrecompileError::Blake2WrongFinalIndicatorFlag),
+    };
+    let rounds = u32::from_be_bytes(input[..4].try_into().unwrap());
+    let gas_used = rounds as u64 * F_ROUND;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    // compression function updates state vector in place
+    let mut h = [0u8; 64];
+    h.copy_from_slice(&input[4..68]);
+    LowLevelSDK::crypto_blake2f(
+        rounds,
+        h.as_mut_ptr(),
+        input[68..196].as_ptr(),
+        input[196..212].as_ptr(),
+        f,
+    );
+    Ok((gas_used, Bytes::copy_from_slice(&h)))
+}
+
 pub fn main() {
     let input = ExecutionContext::contract_input();
     let gas_limit = ExecutionContext::contract_gas_limit();
+    write_precompile_result(run(&input, gas_limit));
+}
 
-    let result = revm_precompile::blake2::run(&input, gas_limit);
-    let result = match result {
-        Ok((_, result)) => result,
-        Err(err) => match err {
-            PrecompileError::OutOfGas => {
-                LowLevelSDK::sys_halt(ExitCode::OutOfFuel.into_i32());
-                Bytes::new()
-            }
-            _ => {
-                LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32());
-                Bytes::new()
-            }
-        },
-    };
-    LowLevelSDK::sys_write(result.as_ref());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    /// Test vector 5 from EIP-152
+    const INPUT: &str = "\
+        0000000c48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f\
+        3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e13\
+        19cde05b61626300000000000000000000000000000000000000000000000000\
+        0000000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000000000000000000000000000000000000000000000000000\
+        000000000300000000000000000000000000000001";
+    const OUTPUT: &str = "\
+        ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+        7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923";
+
+    #[test]
+    fn test_blake2f() {
+        let input = Bytes::from(hex::decode(INPUT).unwrap());
+        let (gas, output) = run(&input, 12).unwrap();
+        assert_eq!(gas, 12);
+        assert_eq!(output.as_ref(), hex::decode(OUTPUT).unwrap());
+        assert_eq!(run(&input, 11).unwrap_err(), PrecompileError::OutOfGas);
+        assert_eq!(
+            run(&input.slice(1..), 12).unwrap_err(),
+            PrecompileError::Blake2WrongLength
+        );
+        let mut wrong_flag = input.to_vec();
+        wrong_flag[212] = 2;
+        assert_eq!(
+            run(&wrong_flag.into(), 12).unwrap_err(),
+            PrecompileError::Blake2WrongFinalIndicatorFlag
+        );
+    }
 }
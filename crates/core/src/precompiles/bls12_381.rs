@@ -0,0 +1,340 @@
+use crate::{
+    consts::{
+        PRECOMPILE_BLS12_381_G1_ADD_ADDRESS, PRECOMPILE_BLS12_381_G1_MSM_ADDRESS,
+        PRECOMPILE_BLS12_381_G1_MUL_ADDRESS, PRECOMPILE_BLS12_381_G2_ADD_ADDRESS,
+        PRECOMPILE_BLS12_381_G2_MSM_ADDRESS, PRECOMPILE_BLS12_381_G2_MUL_ADDRESS,
+        PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS, PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS,
+        PRECOMPILE_BLS12_381_PAIRING_ADDRESS,
+    },
+    precompiles::write_precompile_result,
+};
+use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Address, Bytes, ExitCode, BLS12381_MSM_DISCOUNT_TABLE};
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+/// Gas costs according to EIP-2537
+const G1_ADD_GAS_COST: u64 = 500;
+const G1_MUL_GAS_COST: u64 = 12_000;
+const G2_ADD_GAS_COST: u64 = 800;
+const G2_MUL_GAS_COST: u64 = 45_000;
+const PAIRING_BASE_GAS_COST: u64 = 65_000;
+const PAIRING_PER_PAIR_GAS_COST: u64 = 43_000;
+const MAP_FP_TO_G1_GAS_COST: u64 = 5_500;
+const MAP_FP2_TO_G2_GAS_COST: u64 = 75_000;
+
+const G1_POINT_LEN: usize = 128;
+const G2_POINT_LEN: usize = 256;
+const SCALAR_LEN: usize = 32;
+const PADDED_FP_LEN: usize = 64;
+const G1_MSM_ELEMENT_LEN: usize = G1_POINT_LEN + SCALAR_LEN;
+const G2_MSM_ELEMENT_LEN: usize = G2_POINT_LEN + SCALAR_LEN;
+const PAIR_ELEMENT_LEN: usize = G1_POINT_LEN + G2_POINT_LEN;
+
+pub fn deploy() {}
+
+fn msm_gas_cost(k: usize, multiplication_cost: u64) -> u64 {
+    if k == 0 {
+        return 0;
+    }
+    let discount = BLS12381_MSM_DISCOUNT_TABLE[k.min(BLS12381_MSM_DISCOUNT_TABLE.len()) - 1];
+    (k as u64 * discount * multiplication_cost) / 1000
+}
+
+fn map_exit_code(exit_code: i32) -> Result<(), PrecompileError> {
+    if exit_code == ExitCode::Ok.into_i32() {
+        Ok(())
+    } else if exit_code == ExitCode::Bls12381InvalidInputLength.into_i32() {
+        Err(PrecompileError::Other("invalid input length".into()))
+    } else if exit_code == ExitCode::Bls12381InvalidFieldElement.into_i32() {
+        Err(PrecompileError::Other("invalid field element".into()))
+    } else if exit_code == ExitCode::Bls12381InvalidPoint.into_i32() {
+        Err(PrecompileError::Other("invalid point".into()))
+    } else {
+        Err(PrecompileError::Other("bls12-381 operation failed".into()))
+    }
+}
+
+fn check_gas(gas_used: u64, gas_limit: u64) -> Result<(), PrecompileError> {
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    Ok(())
+}
+
+fn check_input_length(input: &[u8], expected: usize) -> Result<(), PrecompileError> {
+    if input.len() != expected {
+        return Err(PrecompileError::Other("invalid input length".into()));
+    }
+    Ok(())
+}
+
+fn check_msm_input_length(input: &[u8], element_len: usize) -> Result<usize, PrecompileError> {
+    if input.is_empty() || input.len() % element_len != 0 {
+        return Err(PrecompileError::Other("invalid input length".into()));
+    }
+    Ok(input.len() / element_len)
+}
+
+fn g1_add(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    check_gas(G1_ADD_GAS_COST, gas_limit)?;
+    check_input_length(input, 2 * G1_POINT_LEN)?;
+    let mut output = [0u8; G1_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_g1_add(
+        input[..G1_POINT_LEN].as_ptr(),
+        input[G1_POINT_LEN..].as_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok((G1_ADD_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn g1_mul(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    check_gas(G1_MUL_GAS_COST, gas_limit)?;
+    check_input_length(input, G1_MSM_ELEMENT_LEN)?;
+    let mut output = [0u8; G1_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_g1_msm(
+        input,
+        output.as_mut_ptr(),
+    ))?;
+    Ok((G1_MUL_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn g1_msm(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let k = check_msm_input_length(input, G1_MSM_ELEMENT_LEN)?;
+    let gas_used = msm_gas_cost(k, G1_MUL_GAS_COST);
+    check_gas(gas_used, gas_limit)?;
+    let mut output = [0u8; G1_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_g1_msm(
+        input,
+        output.as_mut_ptr(),
+    ))?;
+    Ok((gas_used, Bytes::copy_from_slice(&output)))
+}
+
+fn g2_add(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    check_gas(G2_ADD_GAS_COST, gas_limit)?;
+    check_input_length(input, 2 * G2_POINT_LEN)?;
+    let mut output = [0u8; G2_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_g2_add(
+        input[..G2_POINT_LEN].as_ptr(),
+        input[G2_POINT_LEN..].as_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok((G2_ADD_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn g2_mul(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    check_gas(G2_MUL_GAS_COST, gas_limit)?;
+    check_input_length(input, G2_MSM_ELEMENT_LEN)?;
+    let mut output = [0u8; G2_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_g2_msm(
+        input,
+        output.as_mut_ptr(),
+    ))?;
+    Ok((G2_MUL_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn g2_msm(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let k = check_msm_input_length(input, G2_MSM_ELEMENT_LEN)?;
+    let gas_used = msm_gas_cost(k, G2_MUL_GAS_COST);
+    check_gas(gas_used, gas_limit)?;
+    let mut output = [0u8; G2_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_g2_msm(
+        input,
+        output.as_mut_ptr(),
+    ))?;
+    Ok((gas_used, Bytes::copy_from_slice(&output)))
+}
+
+fn pairing(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let k = check_msm_input_length(input, PAIR_ELEMENT_LEN)?;
+    let gas_used = k as u64 * PAIRING_PER_PAIR_GAS_COST + PAIRING_BASE_GAS_COST;
+    check_gas(gas_used, gas_limit)?;
+    let mut output = [0u8; 32];
+    map_exit_code(LowLevelSDK::crypto_bls12381_pairing(
+        input,
+        output.as_mut_ptr(),
+    ))?;
+    Ok((gas_used, Bytes::copy_from_slice(&output)))
+}
+
+fn map_fp_to_g1(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    check_gas(MAP_FP_TO_G1_GAS_COST, gas_limit)?;
+    check_input_length(input, PADDED_FP_LEN)?;
+    let mut output = [0u8; G1_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_map_fp_to_g1(
+        input.as_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok((MAP_FP_TO_G1_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn map_fp2_to_g2(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    check_gas(MAP_FP2_TO_G2_GAS_COST, gas_limit)?;
+    check_input_length(input, 2 * PADDED_FP_LEN)?;
+    let mut output = [0u8; G2_POINT_LEN];
+    map_exit_code(LowLevelSDK::crypto_bls12381_map_fp2_to_g2(
+        input.as_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok((MAP_FP2_TO_G2_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+/// The same bytecode is deployed at all EIP-2537 addresses (0x0b-0x13), so we select the
+/// operation using the address we're executed at.
+pub(crate) fn run(address: &Address, input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if *address == PRECOMPILE_BLS12_381_G1_ADD_ADDRESS {
+        g1_add(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_G1_MUL_ADDRESS {
+        g1_mul(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_G1_MSM_ADDRESS {
+        g1_msm(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_G2_ADD_ADDRESS {
+        g2_add(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_G2_MUL_ADDRESS {
+        g2_mul(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_G2_MSM_ADDRESS {
+        g2_msm(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_PAIRING_ADDRESS {
+        pairing(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS {
+        map_fp_to_g1(input, gas_limit)
+    } else if *address == PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS {
+        map_fp2_to_g2(input, gas_limit)
+    } else {
+        Err(PrecompileError::Other(
+            "unknown bls12-381 precompile".into(),
+        ))
+    }
+}
+
+pub fn main() {
+    let input = ExecutionContext::contract_input();
+    let gas_limit = ExecutionContext::contract_gas_limit();
+    let address = ExecutionContext::contract_address();
+    write_precompile_result(run(&address, &input, gas_limit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    const G1: &str = "\
+        0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+        0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+    const G2: &str = "\
+        00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8\
+        0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+        000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801\
+        000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be";
+    const SCALAR_2: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+
+    fn call(address: Address, input: &str, gas_limit: u64) -> PrecompileResult {
+        run(
+            &address,
+            &Bytes::from(hex::decode(input).unwrap()),
+            gas_limit,
+        )
+    }
+
+    #[test]
+    fn test_g1_operations() {
+        let (gas, double) = call(
+            PRECOMPILE_BLS12_381_G1_ADD_ADDRESS,
+            &[G1, G1].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 500);
+        let (gas, product) = call(
+            PRECOMPILE_BLS12_381_G1_MUL_ADDRESS,
+            &[G1, SCALAR_2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 12_000);
+        assert_eq!(double, product);
+        let (gas, msm) = call(
+            PRECOMPILE_BLS12_381_G1_MSM_ADDRESS,
+            &[G1, SCALAR_2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 14_400);
+        assert_eq!(double, msm);
+        assert!(call(PRECOMPILE_BLS12_381_G1_ADD_ADDRESS, G1, 1_000_000).is_err());
+        assert_eq!(
+            call(PRECOMPILE_BLS12_381_G1_ADD_ADDRESS, &[G1, G1].concat(), 499).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
+
+    #[test]
+    fn test_g2_operations() {
+        let (gas, double) = call(
+            PRECOMPILE_BLS12_381_G2_ADD_ADDRESS,
+            &[G2, G2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 800);
+        let (gas, product) = call(
+            PRECOMPILE_BLS12_381_G2_MUL_ADDRESS,
+            &[G2, SCALAR_2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 45_000);
+        assert_eq!(double, product);
+    }
+
+    #[test]
+    fn test_pairing() {
+        // e(0, G2) == 1
+        let zero_g1 = "00".repeat(G1_POINT_LEN);
+        let (gas, output) = call(
+            PRECOMPILE_BLS12_381_PAIRING_ADDRESS,
+            &[zero_g1.as_str(), G2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 108_000);
+        assert_eq!(output[31], 1);
+        let (_, output) = call(
+            PRECOMPILE_BLS12_381_PAIRING_ADDRESS,
+            &[G1, G2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(output[31], 0);
+        assert!(call(PRECOMPILE_BLS12_381_PAIRING_ADDRESS, "", 1_000_000).is_err());
+    }
+
+    #[test]
+    fn test_map_to_curve() {
+        let (gas, output) = call(
+            PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS,
+            &G1[..128],
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 5_500);
+        assert_eq!(output.len(), G1_POINT_LEN);
+        let (gas, output) = call(
+            PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS,
+            &G2[..256],
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 75_000);
+        assert_eq!(output.len(), G2_POINT_LEN);
+    }
+
+    #[test]
+    fn test_msm_gas_cost() {
+        assert_eq!(msm_gas_cost(0, G1_MUL_GAS_COST), 0);
+        assert_eq!(msm_gas_cost(1, G1_MUL_GAS_COST), 14_400);
+        assert_eq!(msm_gas_cost(2, G1_MUL_GAS_COST), 21_312);
+        assert_eq!(msm_gas_cost(200, G1_MUL_GAS_COST), 200 * 174 * 12);
+    }
+}
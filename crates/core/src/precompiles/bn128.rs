@@ -1,11 +1,313 @@
+use crate::{
+    consts::{
+        PRECOMPILE_BN128_ADD_ADDRESS, PRECOMPILE_BN128_MUL_ADDRESS, PRECOMPILE_BN128_PAIR_ADDRESS,
+    },
+    precompiles::write_precompile_result,
+};
+use core::cmp::min;
+use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Address, Bytes, ExitCode};
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+/// Gas costs according to EIP-1108 (Istanbul)
+const ADD_GAS_COST: u64 = 150;
+const MUL_GAS_COST: u64 = 6_000;
+const PAIR_BASE_GAS_COST: u64 = 45_000;
+const PAIR_PER_POINT_GAS_COST: u64 = 34_000;
+
+const ADD_INPUT_LEN: usize = 128;
+const MUL_INPUT_LEN: usize = 96;
+const PAIR_ELEMENT_LEN: usize = 192;
+
 pub fn deploy() {}
 
+/// Copies input into a fixed size buffer, missing bytes are treated as zeroes
+fn right_pad<const LEN: usize>(input: &[u8]) -> [u8; LEN] {
+    let mut result = [0u8; LEN];
+    let len = min(input.len(), LEN);
+    result[..len].copy_from_slice(&input[..len]);
+    result
+}
+
+/// `ExitCode::from` doesn't cover all codes in `no_std` mode, so we compare raw values instead
+fn map_exit_code(exit_code: i32) -> Result<(), PrecompileError> {
+    if exit_code == ExitCode::Ok.into_i32() {
+        Ok(())
+    } else if exit_code == ExitCode::Bn128FieldPointNotAMember.into_i32() {
+        Err(PrecompileError::Bn128FieldPointNotAMember)
+    } else if exit_code == ExitCode::Bn128AffineGFailedToCreate.into_i32() {
+        Err(PrecompileError::Bn128AffineGFailedToCreate)
+    } else if exit_code == ExitCode::Bn128PairLength.into_i32() {
+        Err(PrecompileError::Bn128PairLength)
+    } else {
+        Err(PrecompileError::Other("bn128 operation failed".into()))
+    }
+}
+
+fn run_add(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    if ADD_GAS_COST > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    let input = right_pad::<ADD_INPUT_LEN>(input);
+    let mut output = [0u8; 64];
+    map_exit_code(LowLevelSDK::crypto_bn128_add(
+        input[..64].as_ptr(),
+        input[64..].as_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok((ADD_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn run_mul(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    if MUL_GAS_COST > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    let input = right_pad::<MUL_INPUT_LEN>(input);
+    let mut output = [0u8; 64];
+    map_exit_code(LowLevelSDK::crypto_bn128_mul(
+        input[..64].as_ptr(),
+        input[64..].as_ptr(),
+        output.as_mut_ptr(),
+    ))?;
+    Ok((MUL_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+fn run_pair(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    let gas_used =
+        (input.len() / PAIR_ELEMENT_LEN) as u64 * PAIR_PER_POINT_GAS_COST + PAIR_BASE_GAS_COST;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    if input.len() % PAIR_ELEMENT_LEN != 0 {
+        return Err(PrecompileError::Bn128PairLength);
+    }
+    let mut output = [0u8; 32];
+    map_exit_code(LowLevelSDK::crypto_bn128_pairing(
+        input.as_ptr(),
+        input.len() as u32,
+        output.as_mut_ptr(),
+    ))?;
+    Ok((gas_used, Bytes::copy_from_slice(&output)))
+}
+
+/// The same bytecode is deployed at 0x06 (ecAdd), 0x07 (ecMul) and 0x08 (ecPairing), so we
+/// select the operation using the address we're executed at.
+pub(crate) fn run(address: &Address, input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if *address == PRECOMPILE_BN128_ADD_ADDRESS {
+        run_add(input, gas_limit)
+    } else if *address == PRECOMPILE_BN128_MUL_ADDRESS {
+        run_mul(input, gas_limit)
+    } else if *address == PRECOMPILE_BN128_PAIR_ADDRESS {
+        run_pair(input, gas_limit)
+    } else {
+        Err(PrecompileError::Other("unknown bn128 precompile".into()))
+    }
+}
+
 pub fn main() {
-    panic!("not implemented yet")
-    // let input = ExecutionContext::contract_input();
-    // let gas_limit = ExecutionContext::contract_gas_limit();
-    //
-    // let pr = revm_precompile::bn128::run(&input, gas_limit);
-    // let res = pr.unwrap();
-    // LowLevelSDK::sys_write(res.1.as_ref());
+    let input = ExecutionContext::contract_input();
+    let gas_limit = ExecutionContext::contract_gas_limit();
+    let address = ExecutionContext::contract_address();
+    write_precompile_result(run(&address, &input, gas_limit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::encode_precompile_output;
+    use fluentbase_codec::Encoder;
+    use fluentbase_sdk::evm::ContractInput;
+    use revm_primitives::hex;
+
+    /// Generator of G1: (1, 2)
+    const G1: &str = "\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        0000000000000000000000000000000000000000000000000000000000000002";
+    /// Negated generator of G1: (1, p - 2)
+    const G1_NEG: &str = "\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+    /// 2 * G1
+    const G1_DOUBLE: &str = "\
+        030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+        15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+    /// Generator of G2 encoded as (x_im, x_re, y_im, y_re), see EIP-197
+    const G2: &str = "\
+        198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+        1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+        090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+        12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+    const ZERO32: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+    fn call(address: Address, input: &str, gas_limit: u64) -> PrecompileResult {
+        run(
+            &address,
+            &Bytes::from(hex::decode(input).unwrap()),
+            gas_limit,
+        )
+    }
+
+    fn bool32(value: bool) -> Bytes {
+        let mut result = [0u8; 32];
+        result[31] = value as u8;
+        Bytes::copy_from_slice(&result)
+    }
+
+    #[test]
+    fn test_bn128_add() {
+        let (gas, output) =
+            call(PRECOMPILE_BN128_ADD_ADDRESS, &[G1, G1].concat(), 1_000_000).unwrap();
+        assert_eq!(gas, 150);
+        assert_eq!(output.as_ref(), hex::decode(G1_DOUBLE).unwrap());
+        // point at infinity is encoded as zero, and missing input is padded with zeros
+        let (_, output) = call(PRECOMPILE_BN128_ADD_ADDRESS, "", 1_000_000).unwrap();
+        assert_eq!(output.as_ref(), [0u8; 64]);
+        let (_, output) = call(PRECOMPILE_BN128_ADD_ADDRESS, G1, 1_000_000).unwrap();
+        assert_eq!(output.as_ref(), hex::decode(G1).unwrap());
+        // (1, 3) is not on the curve
+        let not_on_curve = [&G1[..64], &ZERO32[..63], "3"].concat();
+        assert!(call(PRECOMPILE_BN128_ADD_ADDRESS, &not_on_curve, 1_000_000).is_err());
+        assert_eq!(
+            call(PRECOMPILE_BN128_ADD_ADDRESS, G1, 149).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
+
+    #[test]
+    fn test_bn128_mul() {
+        let scalar = [&ZERO32[..63], "2"].concat();
+        let (gas, output) = call(
+            PRECOMPILE_BN128_MUL_ADDRESS,
+            &[G1, scalar.as_str()].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 6_000);
+        assert_eq!(output.as_ref(), hex::decode(G1_DOUBLE).unwrap());
+        let (_, output) = call(
+            PRECOMPILE_BN128_MUL_ADDRESS,
+            &[G1, ZERO32].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(output.as_ref(), [0u8; 64]);
+        assert_eq!(
+            call(PRECOMPILE_BN128_MUL_ADDRESS, G1, 5_999).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
+
+    #[test]
+    fn test_bn128_pair() {
+        // empty input is a valid pairing check
+        let (gas, output) = call(PRECOMPILE_BN128_PAIR_ADDRESS, "", 1_000_000).unwrap();
+        assert_eq!(gas, 45_000);
+        assert_eq!(output, bool32(true));
+        // e(G1, G2) * e(-G1, G2) == 1
+        let (gas, output) = call(
+            PRECOMPILE_BN128_PAIR_ADDRESS,
+            &[G1, G2, G1_NEG, G2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 45_000 + 2 * 34_000);
+        assert_eq!(output, bool32(true));
+        // e(G1, G2) * e(G1, G2) != 1
+        let (_, output) = call(
+            PRECOMPILE_BN128_PAIR_ADDRESS,
+            &[G1, G2, G1, G2].concat(),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(output, bool32(false));
+        // input must be a multiple of 192 bytes
+        assert_eq!(
+            call(PRECOMPILE_BN128_PAIR_ADDRESS, G1, 1_000_000).unwrap_err(),
+            PrecompileError::Bn128PairLength
+        );
+        assert_eq!(
+            call(PRECOMPILE_BN128_PAIR_ADDRESS, &[G1, G2].concat(), 78_999).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
+
+    #[test]
+    fn test_bn128_ethereum_vectors() {
+        // chfast1 from `bn256Add.json`
+        let (_, output) = call(
+            PRECOMPILE_BN128_ADD_ADDRESS,
+            "\
+            18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9\
+            063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266\
+            07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed\
+            06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7",
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(
+            output.as_ref(),
+            hex::decode(
+                "\
+                2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703\
+                301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915"
+            )
+            .unwrap()
+        );
+        // chfast1 from `bn256ScalarMul.json`
+        let (_, output) = call(
+            PRECOMPILE_BN128_MUL_ADDRESS,
+            "\
+            2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7\
+            21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204\
+            00000000000000000000000000000000000000000000000011138ce750fa15c2",
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(
+            output.as_ref(),
+            hex::decode(
+                "\
+                070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c\
+                031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc"
+            )
+            .unwrap()
+        );
+        // jeff1 from `bn256Pairing.json`
+        let (gas, output) = call(
+            PRECOMPILE_BN128_PAIR_ADDRESS,
+            "\
+            1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+            3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+            209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+            04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+            2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+            120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+            111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+            2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+            198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+            1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+            090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+            12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(gas, 113_000);
+        assert_eq!(output, bool32(true));
+    }
+
+    #[test]
+    fn test_bn128_main() {
+        let contract_input = ContractInput {
+            contract_address: PRECOMPILE_BN128_ADD_ADDRESS,
+            contract_gas_limit: 1_000_000,
+            contract_input: hex::decode([G1, G1].concat()).unwrap().into(),
+            ..Default::default()
+        };
+        LowLevelSDK::with_test_input(contract_input.encode_to_vec(0));
+        super::main();
+        assert_eq!(
+            LowLevelSDK::get_test_output(),
+            encode_precompile_output(150, &hex::decode(G1_DOUBLE).unwrap())
+        );
+    }
 }
@@ -1,11 +1,142 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Bytes, ExitCode};
+use kzg_rs::{Bytes32, Bytes48, KzgProof, KzgSettings};
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+/// Gas cost and version of the versioned hash according to EIP-4844
+const GAS_COST: u64 = 50_000;
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+lazy_static::lazy_static! {
+    /// Mainnet trusted setup embedded into `kzg-rs`, it's parsed once on the first verification
+    static ref KZG_SETTINGS: Option<KzgSettings> = KzgSettings::load_trusted_setup_file().ok();
+}
+
+/// `FIELD_ELEMENTS_PER_BLOB ++ BLS_MODULUS`, both encoded as 32-byte big-endian integers
+const RETURN_VALUE: [u8; 64] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00,
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
 pub fn deploy() {}
 
+/// Input is `versioned_hash ++ z ++ y ++ commitment ++ proof` (192 bytes), the proof is verified
+/// with the pure-Rust `kzg-rs` against the mainnet trusted setup it embeds.
+pub(crate) fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if GAS_COST > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    if input.len() != 192 {
+        return Err(PrecompileError::BlobInvalidInputLength);
+    }
+    // versioned hash is `VERSIONED_HASH_VERSION_KZG ++ sha256(commitment)[1..]`
+    let commitment = &input[96..144];
+    let mut versioned_hash = [0u8; 32];
+    LowLevelSDK::crypto_sha256(
+        commitment.as_ptr(),
+        commitment.len() as u32,
+        versioned_hash.as_mut_ptr(),
+    );
+    versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    if versioned_hash != input[..32] {
+        return Err(PrecompileError::BlobMismatchedVersion);
+    }
+    if !verify_kzg_proof(commitment, &input[32..64], &input[64..96], &input[144..192]) {
+        return Err(PrecompileError::BlobVerifyKzgProofFailed);
+    }
+    Ok((GAS_COST, Bytes::from_static(&RETURN_VALUE)))
+}
+
+fn verify_kzg_proof(commitment: &[u8], z: &[u8], y: &[u8], proof: &[u8]) -> bool {
+    let (Ok(commitment), Ok(z), Ok(y), Ok(proof)) = (
+        Bytes48::from_slice(commitment),
+        Bytes32::from_slice(z),
+        Bytes32::from_slice(y),
+        Bytes48::from_slice(proof),
+    ) else {
+        return false;
+    };
+    let Some(kzg_settings) = KZG_SETTINGS.as_ref() else {
+        return false;
+    };
+    KzgProof::verify_kzg_proof(&commitment, &z, &y, &proof, kzg_settings).unwrap_or(false)
+}
+
 pub fn main() {
-    panic!("not implemented yet");
-    // let input = ExecutionContext::contract_input();
-    // let gas_limit = ExecutionContext::contract_gas_limit();
-    //
-    // let pr = revm_precompile::kzg_point_evaluation::run(&input, gas_limit);
-    // let res = pr.unwrap();
-    // LowLevelSDK::sys_write(res.1.as_ref());
+    let input = ExecutionContext::contract_input();
+    let gas_limit = ExecutionContext::contract_gas_limit();
+    write_precompile_result(run(&input, gas_limit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::encode_precompile_output;
+    use fluentbase_codec::Encoder;
+    use fluentbase_sdk::evm::ContractInput;
+    use revm_primitives::hex;
+
+    // https://github.com/ethereum/c-kzg-4844/blob/main/tests/verify_kzg_proof/kzg-mainnet/verify_kzg_proof_case_correct_proof_31ebd010e6098750/data.yaml
+    const VERSIONED_HASH: &str = "01e798154708fe7789429634053cbf9f99b619f9f084048927333fce637f549b";
+    const Z: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000";
+    const Y: &str = "1522a4a7f34e1ea350ae07c29c96c7e79655aa926122e95fe69fcbd932ca49e9";
+    const COMMITMENT: &str = "8f59a8d2a1a625a17f3fea0fe5eb8c896db3764f3185481bc22f91b4aaffcca25f26936857bc3a7c2539ea8ec3a952b7";
+    const PROOF: &str = "a62ad71d14c5719385c0686f1871430475bf3a00f0aa3f7b8dd99a9abc2160744faf0070725e00b60ad9a026a15b1a8c";
+    /// `FIELD_ELEMENTS_PER_BLOB ++ BLS_MODULUS`
+    const EXPECTED_OUTPUT: &str = "\
+        0000000000000000000000000000000000000000000000000000000000001000\
+        73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+    fn input(versioned_hash: &str, y: &str) -> Bytes {
+        hex::decode([versioned_hash, Z, y, COMMITMENT, PROOF].concat())
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_point_evaluation() {
+        let (gas, output) = run(&input(VERSIONED_HASH, Y), 50_000).unwrap();
+        assert_eq!(gas, 50_000);
+        assert_eq!(output.as_ref(), hex::decode(EXPECTED_OUTPUT).unwrap());
+    }
+
+    #[test]
+    fn test_point_evaluation_errors() {
+        assert_eq!(
+            run(&input(VERSIONED_HASH, Y), 49_999).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+        assert_eq!(
+            run(&Bytes::from_static(&[0u8; 191]), 50_000).unwrap_err(),
+            PrecompileError::BlobInvalidInputLength
+        );
+        let wrong_version = ["02", &VERSIONED_HASH[2..]].concat();
+        assert_eq!(
+            run(&input(&wrong_version, Y), 50_000).unwrap_err(),
+            PrecompileError::BlobMismatchedVersion
+        );
+        let wrong_y = [&Y[..62], "00"].concat();
+        assert_eq!(
+            run(&input(VERSIONED_HASH, &wrong_y), 50_000).unwrap_err(),
+            PrecompileError::BlobVerifyKzgProofFailed
+        );
+    }
+
+    #[test]
+    fn test_point_evaluation_main() {
+        let contract_input = ContractInput {
+            contract_gas_limit: 50_000,
+            contract_input: input(VERSIONED_HASH, Y),
+            ..Default::default()
+        };
+        LowLevelSDK::with_test_input(contract_input.encode_to_vec(0));
+        super::main();
+        assert_eq!(
+            LowLevelSDK::get_test_output(),
+            encode_precompile_output(50_000, &hex::decode(EXPECTED_OUTPUT).unwrap())
+        );
+    }
 }
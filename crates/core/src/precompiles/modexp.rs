@@ -1,26 +1,136 @@
+use crate::precompiles::write_precompile_result;
+use alloc::vec;
+use core::cmp::{max, min};
 use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
 use fluentbase_types::{Bytes, ExitCode};
-use revm_interpreter::primitives::PrecompileError;
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult, U256};
+
+const MIN_GAS: u64 = 200;
+const HEADER_LENGTH: usize = 96;
 
 pub fn deploy() {}
 
+fn read_u256(input: &[u8], offset: usize) -> U256 {
+    let mut word = [0u8; 32];
+    let input = input.get(offset..).unwrap_or_default();
+    let len = min(input.len(), 32);
+    word[..len].copy_from_slice(&input[..len]);
+    U256::from_be_bytes(word)
+}
+
+fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> u64 {
+    let iteration_count = if exp_length <= 32 && *exp_highp == U256::ZERO {
+        0
+    } else if exp_length <= 32 {
+        exp_highp.bit_len() as u64 - 1
+    } else {
+        (8u64.saturating_mul(exp_length - 32))
+            .saturating_add(max(1, exp_highp.bit_len() as u64) - 1)
+    };
+    max(iteration_count, 1)
+}
+
+/// Gas cost according to EIP-2565
+fn calculate_gas_cost(base_length: u64, exp_length: u64, mod_length: u64, exp_highp: &U256) -> u64 {
+    let words = U256::from((max(base_length, mod_length) + 7) / 8);
+    let iteration_count = calculate_iteration_count(exp_length, exp_highp);
+    let gas = (words * words * U256::from(iteration_count)) / U256::from(3);
+    max(MIN_GAS, gas.saturating_to())
+}
+
+/// Input format (EIP-198):
+/// <length_of_BASE> <length_of_EXPONENT> <length_of_MODULUS> <BASE> <EXPONENT> <MODULUS>
+pub(crate) fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if MIN_GAS > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    let Ok(base_len) = usize::try_from(read_u256(input, 0)) else {
+        return Err(PrecompileError::ModexpBaseOverflow);
+    };
+    let Ok(exp_len) = usize::try_from(read_u256(input, 32)) else {
+        return Err(PrecompileError::ModexpExpOverflow);
+    };
+    let Ok(mod_len) = usize::try_from(read_u256(input, 64)) else {
+        return Err(PrecompileError::ModexpModOverflow);
+    };
+    if base_len == 0 && mod_len == 0 {
+        return Ok((MIN_GAS, Bytes::new()));
+    }
+    let input = input.get(HEADER_LENGTH..).unwrap_or_default();
+    // first 32 bytes of the exponent are used to calculate its adjusted length
+    let exp_highp = match min(exp_len, 32) {
+        0 => U256::ZERO,
+        exp_highp_len => read_u256(input, base_len) >> (8 * (32 - exp_highp_len)),
+    };
+    let gas_cost = calculate_gas_cost(base_len as u64, exp_len as u64, mod_len as u64, &exp_highp);
+    if gas_cost > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    // missing input is padded with zeroes
+    let mut data = vec![0u8; base_len + exp_len + mod_len];
+    let len = min(input.len(), data.len());
+    data[..len].copy_from_slice(&input[..len]);
+    let (base, data) = data.split_at(base_len);
+    let (exponent, modulus) = data.split_at(exp_len);
+    let mut output = vec![0u8; mod_len];
+    LowLevelSDK::crypto_modexp(base, exponent, modulus, output.as_mut_ptr());
+    Ok((gas_cost, output.into()))
+}
+
 pub fn main() {
     let input = ExecutionContext::contract_input();
     let gas_limit = ExecutionContext::contract_gas_limit();
+    write_precompile_result(run(&input, gas_limit));
+}
 
-    let result = revm_precompile::modexp::berlin_run(&input, gas_limit);
-    let result = match result {
-        Ok((_, result)) => result,
-        Err(err) => match err {
-            PrecompileError::OutOfGas => {
-                LowLevelSDK::sys_halt(ExitCode::OutOfFuel.into_i32());
-                Bytes::new()
-            }
-            _ => {
-                LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32());
-                Bytes::new()
-            }
-        },
-    };
-    LowLevelSDK::sys_write(result.as_ref());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    #[test]
+    fn test_modexp() {
+        // eth_tests_create2callPrecompiles_test0_berlin
+        let input = hex::decode(
+            "\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000020\
+            0000000000000000000000000000000000000000000000000000000000000020\
+            03\
+            fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+            ffffffffffffffffffffffffffffffffffffffffff2f",
+        )
+        .unwrap();
+        let (gas, output) = run(&input.into(), 100_000).unwrap();
+        assert_eq!(gas, 1_360);
+        assert_eq!(
+            output.as_ref(),
+            hex::decode("162ead82cadefaeaf6e9283248fdf2f2845f6396f6f17c4d5a39f820b6f6b5f9")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_modexp_padding() {
+        // modulus is cut off the input and right padded with zeroes: 3 ^ 5 % 0x6400 = 0xf3
+        let input = hex::decode(
+            "\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            030564",
+        )
+        .unwrap();
+        let (gas, output) = run(&input.into(), 100_000).unwrap();
+        assert_eq!(gas, 200);
+        assert_eq!(output.as_ref(), [0x00, 0xf3]);
+        // empty input returns empty output
+        let (gas, output) = run(&Bytes::new(), 100_000).unwrap();
+        assert_eq!(gas, 200);
+        assert!(output.is_empty());
+        assert_eq!(
+            run(&Bytes::new(), 199).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
 }
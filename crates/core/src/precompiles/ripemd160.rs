@@ -0,0 +1,51 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Bytes, ExitCode};
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+const BASE_GAS_COST: u64 = 600;
+const WORD_GAS_COST: u64 = 120;
+
+pub fn deploy() {}
+
+pub(crate) fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    let gas_used = (input.len() as u64 + 31) / 32 * WORD_GAS_COST + BASE_GAS_COST;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    // 20-byte digest is left padded to 32 bytes
+    let mut output = [0u8; 32];
+    LowLevelSDK::crypto_ripemd160(
+        input.as_ptr(),
+        input.len() as u32,
+        output[12..].as_mut_ptr(),
+    );
+    Ok((gas_used, Bytes::copy_from_slice(&output)))
+}
+
+pub fn main() {
+    let input = ExecutionContext::contract_input();
+    let gas_limit = ExecutionContext::contract_gas_limit();
+    write_precompile_result(run(&input, gas_limit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    #[test]
+    fn test_ripemd160() {
+        let (gas, output) = run(&Bytes::from_static(b"abc"), 1_000).unwrap();
+        assert_eq!(gas, 720);
+        assert_eq!(
+            output.as_ref(),
+            hex::decode("0000000000000000000000008eb208f7e05d987a9b044a8e98c6b087f15a0bfc")
+                .unwrap()
+        );
+        assert_eq!(
+            run(&Bytes::from_static(b"abc"), 719).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
+}
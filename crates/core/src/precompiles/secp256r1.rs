@@ -0,0 +1,93 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Bytes, ExitCode};
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+/// Gas cost of the P256VERIFY precompile (RIP-7212)
+const P256VERIFY_GAS_COST: u64 = 3_450;
+const INPUT_LENGTH: usize = 160;
+
+pub fn deploy() {}
+
+/// Input format (RIP-7212):
+/// [32 bytes for message hash][32 bytes for r][32 bytes for s][32 bytes for x][32 bytes for y]
+///
+/// Returns 32-byte encoded `1` if the signature is valid, and empty output otherwise, including
+/// malformed input.
+pub(crate) fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if P256VERIFY_GAS_COST > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    if input.len() != INPUT_LENGTH {
+        return Ok((P256VERIFY_GAS_COST, Bytes::new()));
+    }
+    let is_valid = LowLevelSDK::crypto_secp256r1_verify(
+        input[..32].as_ptr(),
+        input[32..96].as_ptr(),
+        input[96..160].as_ptr(),
+    );
+    if !is_valid {
+        return Ok((P256VERIFY_GAS_COST, Bytes::new()));
+    }
+    let mut output = [0u8; 32];
+    output[31] = 1;
+    Ok((P256VERIFY_GAS_COST, Bytes::copy_from_slice(&output)))
+}
+
+pub fn main() {
+    let input = ExecutionContext::contract_input();
+    let gas_limit = ExecutionContext::contract_gas_limit();
+    write_precompile_result(run(&input, gas_limit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::encode_precompile_output;
+    use fluentbase_codec::Encoder;
+    use fluentbase_sdk::evm::ContractInput;
+    use revm_primitives::hex;
+
+    const INPUT: &str = "\
+        4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4d\
+        a73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac\
+        36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d60\
+        4aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff3\
+        7618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e";
+
+    #[test]
+    fn test_secp256r1_verify() {
+        let input = Bytes::from(hex::decode(INPUT).unwrap());
+        let (gas, output) = run(&input, 10_000).unwrap();
+        assert_eq!(gas, 3_450);
+        assert_eq!(output.len(), 32);
+        assert_eq!(output[31], 1);
+        // invalid signature
+        let mut invalid = input.to_vec();
+        invalid[0] ^= 1;
+        let (gas, output) = run(&invalid.into(), 10_000).unwrap();
+        assert_eq!(gas, 3_450);
+        assert!(output.is_empty());
+        // invalid input length
+        let (_, output) = run(&input.slice(1..), 10_000).unwrap();
+        assert!(output.is_empty());
+        assert_eq!(run(&input, 3_449).unwrap_err(), PrecompileError::OutOfGas);
+    }
+
+    #[test]
+    fn test_secp256r1_main() {
+        let contract_input = ContractInput {
+            contract_gas_limit: 10_000,
+            contract_input: hex::decode(INPUT).unwrap().into(),
+            ..Default::default()
+        };
+        LowLevelSDK::with_test_input(contract_input.encode_to_vec(0));
+        super::main();
+        let mut expected = [0u8; 32];
+        expected[31] = 1;
+        assert_eq!(
+            LowLevelSDK::get_test_output(),
+            encode_precompile_output(3_450, &expected)
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Bytes, ExitCode};
+use revm_interpreter::primitives::{PrecompileError, PrecompileResult};
+
+const BASE_GAS_COST: u64 = 60;
+const WORD_GAS_COST: u64 = 12;
+
+pub fn deploy() {}
+
+pub(crate) fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    let gas_used = (input.len() as u64 + 31) / 32 * WORD_GAS_COST + BASE_GAS_COST;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+    let mut output = [0u8; 32];
+    LowLevelSDK::crypto_sha256(input.as_ptr(), input.len() as u32, output.as_mut_ptr());
+    Ok((gas_used, Bytes::copy_from_slice(&output)))
+}
+
+pub fn main() {
+    let input = ExecutionContext::contract_input();
+    let gas_limit = ExecutionContext::contract_gas_limit();
+    write_precompile_result(run(&input, gas_limit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    #[test]
+    fn test_sha256() {
+        let (gas, output) = run(&Bytes::from_static(b"abc"), 1_000).unwrap();
+        assert_eq!(gas, 72);
+        assert_eq!(
+            output.as_ref(),
+            hex::decode("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+                .unwrap()
+        );
+        assert_eq!(
+            run(&Bytes::from_static(b"abc"), 71).unwrap_err(),
+            PrecompileError::OutOfGas
+        );
+    }
+}
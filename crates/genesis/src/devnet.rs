@@ -1,5 +1,15 @@
 use crate::{ChainConfig, Genesis, GenesisAccount, EXAMPLE_GREETING_ADDRESS};
-use fluentbase_core::consts::{ECL_CONTRACT_ADDRESS, WCL_CONTRACT_ADDRESS};
+use fluentbase_core::consts::{
+    ECL_CONTRACT_ADDRESS, PRECOMPILE_BLAKE2_ADDRESS, PRECOMPILE_BLS12_381_G1_ADD_ADDRESS,
+    PRECOMPILE_BLS12_381_G1_MSM_ADDRESS, PRECOMPILE_BLS12_381_G1_MUL_ADDRESS,
+    PRECOMPILE_BLS12_381_G2_ADD_ADDRESS, PRECOMPILE_BLS12_381_G2_MSM_ADDRESS,
+    PRECOMPILE_BLS12_381_G2_MUL_ADDRESS, PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS,
+    PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS, PRECOMPILE_BLS12_381_PAIRING_ADDRESS,
+    PRECOMPILE_BN128_ADD_ADDRESS, PRECOMPILE_BN128_MUL_ADDRESS, PRECOMPILE_BN128_PAIR_ADDRESS,
+    PRECOMPILE_IDENTITY_ADDRESS, PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
+    PRECOMPILE_MODEXP_ADDRESS, PRECOMPILE_RIPEMD160_ADDRESS, PRECOMPILE_SECP256K1_ADDRESS,
+    PRECOMPILE_SECP256R1_ADDRESS, PRECOMPILE_SHA256_ADDRESS, WCL_CONTRACT_ADDRESS,
+};
 use fluentbase_poseidon::poseidon_hash;
 use fluentbase_types::{address, b256, Address, Bytes, B256, U256};
 use revm_primitives::keccak256;
@@ -113,30 +123,87 @@ pub fn devnet_genesis() -> Genesis {
         WCL_CONTRACT_ADDRESS,
         "../../contracts/assets/wcl_contract.rwasm"
     );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_BLAKE2_ADDRESS,
-    //     "../../contracts/assets/precompile_blake2.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_BN128_ADDRESS,
-    //     "../../contracts/assets/precompile_bn128.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_IDENTITY_ADDRESS,
-    //     "../../contracts/assets/precompile_identity.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
-    //     "../../contracts/assets/precompile_kzg_point_evaluation.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_MODEXP_ADDRESS,
-    //     "../../contracts/assets/precompile_modexp.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_SECP256K1_ADDRESS,
-    //     "../../contracts/assets/precompile_secp256k1.rwasm"
-    // );
+    // precompiles at their canonical addresses
+    enable_rwasm_contract!(
+        PRECOMPILE_SECP256K1_ADDRESS,
+        "../../contracts/assets/precompile_secp256k1.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_SHA256_ADDRESS,
+        "../../contracts/assets/precompile_sha256.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_RIPEMD160_ADDRESS,
+        "../../contracts/assets/precompile_ripemd160.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_IDENTITY_ADDRESS,
+        "../../contracts/assets/precompile_identity.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_MODEXP_ADDRESS,
+        "../../contracts/assets/precompile_modexp.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BN128_ADD_ADDRESS,
+        "../../contracts/assets/precompile_bn128.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BN128_MUL_ADDRESS,
+        "../../contracts/assets/precompile_bn128.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BN128_PAIR_ADDRESS,
+        "../../contracts/assets/precompile_bn128.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLAKE2_ADDRESS,
+        "../../contracts/assets/precompile_blake2.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
+        "../../contracts/assets/precompile_kzg_point_evaluation.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_G1_ADD_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_G1_MUL_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_G1_MSM_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_G2_ADD_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_G2_MUL_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_G2_MSM_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_PAIRING_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_MAP_FP_TO_G1_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLS12_381_MAP_FP2_TO_G2_ADDRESS,
+        "../../contracts/assets/precompile_bls12_381.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_SECP256R1_ADDRESS,
+        "../../contracts/assets/precompile_secp256r1.rwasm"
+    );
     enable_rwasm_contract!(
         EXAMPLE_GREETING_ADDRESS,
         "../../../examples/bin/greeting.rwasm"
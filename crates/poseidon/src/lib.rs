@@ -1,36 +1,151 @@
-use halo2curves::bn256::Fr;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use halo2curves::{
+    bn256::Fr,
+    group::ff::{Field, PrimeField},
+};
 pub use poseidon::Poseidon;
 
+mod constants;
+
+/// Amount of bytes packed into one field element
+const CHUNK_LEN: usize = 31;
+
+/// Width of the permutation, the first element is a capacity and the rest are absorbed inputs
+const WIDTH: usize = 3;
+const RATE: usize = WIDTH - 1;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 57;
+
+/// Multiplier of the byte length that gives a domain of the byte string hashing (Scroll's
+/// `HASHABLE_DOMAIN_SPEC`). It's much bigger than any domain of the field elements hashing, so
+/// these never intersect.
+pub const HASH_DOMAIN_SPEC: u128 = 1 << 64;
+
+lazy_static::lazy_static! {
+    static ref ROUND_CONSTANTS: Vec<Fr> = constants::ROUND_CONSTANTS
+        .iter()
+        .map(|limbs| Fr::from_raw(*limbs))
+        .collect();
+    static ref MDS: [[Fr; WIDTH]; WIDTH] = constants::MDS.map(|row| row.map(Fr::from_raw));
+}
+
+/// Version of the Poseidon hashing scheme, the same input has different hashes in different
+/// versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashVersion {
+    /// Legacy scheme: domain is ignored and the last chunk of bytes is padded with zeros, so
+    /// inputs that differ only in trailing zero bytes collide. It's kept for storage keys and to
+    /// verify and migrate code hashes that are already stored in the state.
+    V1,
+    /// Scroll's scheme: domain initialises the capacity element, bytes are packed big-endian
+    /// and hashed with a domain that depends on their length
+    V2,
+}
+
+impl HashVersion {
+    pub const LATEST: Self = Self::V2;
+
+    pub fn hash_with_domain(&self, arr: &[Fr], domain: &Fr) -> Fr {
+        match self {
+            Self::V1 => {
+                let mut hasher = Poseidon::<Fr, 3, 2>::new(8, 56);
+                hasher.update(arr);
+                hasher.squeeze()
+            }
+            Self::V2 => {
+                let mut state = [*domain, Fr::ZERO, Fr::ZERO];
+                // empty input is absorbed as a single chunk of zeros
+                if arr.is_empty() {
+                    permute(&mut state);
+                }
+                for chunk in arr.chunks(RATE) {
+                    for (value, input) in state[1..].iter_mut().zip(chunk) {
+                        *value += input;
+                    }
+                    permute(&mut state);
+                }
+                state[0]
+            }
+        }
+    }
+
+    pub fn hash_bytes(&self, data: &[u8]) -> [u8; 32] {
+        let elems = data
+            .chunks(CHUNK_LEN)
+            .map(|chunk| {
+                let mut buffer32: [u8; 32] = [0u8; 32];
+                match self {
+                    Self::V1 => buffer32[..chunk.len()].copy_from_slice(chunk),
+                    // chunk is a big-endian number that is padded with zeros to `CHUNK_LEN` bytes
+                    Self::V2 => chunk
+                        .iter()
+                        .enumerate()
+                        .for_each(|(i, byte)| buffer32[CHUNK_LEN - 1 - i] = *byte),
+                }
+                Fr::from_bytes(&buffer32).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let domain = Fr::from_u128(data.len() as u128 * HASH_DOMAIN_SPEC);
+        self.hash_with_domain(&elems, &domain).to_bytes()
+    }
+}
+
+/// Poseidon permutation over BN254 scalar field with `x^5` S-box (the same as in circomlib)
+fn permute(state: &mut [Fr; WIDTH]) {
+    let x5 = |x: Fr| x.square().square() * x;
+    for round in 0..FULL_ROUNDS + PARTIAL_ROUNDS {
+        for (i, value) in state.iter_mut().enumerate() {
+            *value += ROUND_CONSTANTS[round * WIDTH + i];
+        }
+        if round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + PARTIAL_ROUNDS {
+            state.iter_mut().for_each(|value| *value = x5(*value));
+        } else {
+            state[0] = x5(state[0]);
+        }
+        let mut result = [Fr::ZERO; WIDTH];
+        for (i, value) in result.iter_mut().enumerate() {
+            for (j, input) in state.iter().enumerate() {
+                *value += MDS[i][j] * input;
+            }
+        }
+        *state = result;
+    }
+}
+
+/// Hashes bytes with the latest scheme (`HashVersion::V2`), it's used for code hashes. Legacy
+/// code hashes can be converted with `migrate_hash`.
 pub fn poseidon_hash(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Poseidon::<Fr, 3, 2>::new(8, 56);
-    const CHUNK_LEN: usize = 31;
-    for chunk in data.chunks(CHUNK_LEN).into_iter() {
-        let mut buffer32: [u8; 32] = [0u8; 32];
-        buffer32[..chunk.len()].copy_from_slice(chunk);
-        let v = Fr::from_bytes(&buffer32).unwrap();
-        hasher.update(&[v]);
+    HashVersion::LATEST.hash_bytes(data)
+}
+
+/// Returns hash of the data computed with the latest scheme if the provided hash is a hash of the
+/// same data computed with any older scheme, otherwise `None` is returned
+pub fn migrate_hash(data: &[u8], hash: &[u8; 32]) -> Option<[u8; 32]> {
+    let latest = HashVersion::LATEST.hash_bytes(data);
+    if &latest == hash || &HashVersion::V1.hash_bytes(data) != hash {
+        return None;
     }
-    let h = hasher.squeeze();
-    h.to_bytes()
+    Some(latest)
 }
 
-pub fn hash_with_domain(arr: &[Fr], _domain: &Fr) -> Fr {
-    let mut hasher = Poseidon::<Fr, 3, 2>::new(8, 56);
-    hasher.update(arr);
-    hasher.squeeze()
+pub fn hash_with_domain(arr: &[Fr], domain: &Fr) -> Fr {
+    HashVersion::LATEST.hash_with_domain(arr, domain)
 }
 
 #[cfg(test)]
 mod poseidon_tests {
-    extern crate alloc;
-
-    use crate::{hash_with_domain, poseidon_hash};
+    use crate::{hash_with_domain, migrate_hash, poseidon_hash, HashVersion};
+    use alloc::{vec, vec::Vec};
     use halo2curves::{bn256::Fr, group::ff::PrimeField};
 
     #[test]
     fn empty() {
         assert_eq!(
-            poseidon_hash(&[0u8; 0]),
+            HashVersion::V1.hash_bytes(&[0u8; 0]),
             [
                 4, 44, 76, 53, 12, 109, 170, 99, 136, 141, 121, 133, 236, 148, 84, 202, 23, 196,
                 176, 71, 252, 181, 29, 144, 148, 84, 57, 217, 35, 221, 200, 12
@@ -45,7 +160,7 @@ mod poseidon_tests {
             13, 147, 215, 180, 93, 24, 214, 147, 24, 205, 39, 124, 162, 132, 216, 125, 204, 48,
             249, 43, 252, 181, 68, 137, 189, 87, 214, 31, 48, 215, 193, 14,
         ];
-        let hash = poseidon_hash(data.as_slice());
+        let hash = HashVersion::V1.hash_bytes(data.as_slice());
 
         assert_eq!(hash.as_slice(), expected.as_slice());
     }
@@ -57,7 +172,6 @@ mod poseidon_tests {
     }
 
     #[test]
-    #[ignore]
     fn with_domain() {
         let mut a = [0u8; 32];
         let mut b = [0u8; 32];
@@ -80,4 +194,69 @@ mod poseidon_tests {
         ];
         assert_eq!(expected_repr, repr_h2);
     }
+
+    #[test]
+    fn scroll_vectors() {
+        // Scroll's (and circomlib's) `Poseidon(1, 2)`, domain is the capacity element
+        let h = hash_with_domain(&[Fr::from(1u64), Fr::from(2u64)], &Fr::from(0u64));
+        assert_eq!(
+            h,
+            Fr::from_str_vartime(
+                "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+            )
+            .unwrap()
+        );
+        // Scroll's code hash of the empty code
+        let mut empty_code_hash = poseidon_hash(&[]);
+        empty_code_hash.reverse();
+        assert_eq!(
+            hex::encode(empty_code_hash),
+            "2098f5fb9e239eab3ceac3f27b81e481dc3124d55ffed523a839ee8446b64864"
+        );
+        // regression vector of the big-endian byte packing
+        let mut code_hash = poseidon_hash(b"hello world");
+        code_hash.reverse();
+        assert_eq!(
+            hex::encode(code_hash),
+            "15f39324a7b2ea6f6dc23e1d873e2fee3d69971efda77f81b17adc88c2c10214"
+        );
+    }
+
+    #[test]
+    fn domain_separation() {
+        let a = Fr::from(1u64);
+        let b = Fr::from(2u64);
+        assert_ne!(
+            hash_with_domain(&[a, b], &Fr::from(0u64)),
+            hash_with_domain(&[a, b], &Fr::from(1u64))
+        );
+        // legacy scheme ignores the domain
+        assert_eq!(
+            HashVersion::V1.hash_with_domain(&[a, b], &Fr::from(0u64)),
+            HashVersion::V1.hash_with_domain(&[a, b], &Fr::from(1u64))
+        );
+    }
+
+    #[test]
+    fn trailing_zeros() {
+        let latest = HashVersion::LATEST;
+        assert_ne!(latest.hash_bytes(&[1]), latest.hash_bytes(&[1, 0]));
+        assert_ne!(latest.hash_bytes(&[]), latest.hash_bytes(&[0]));
+        assert_ne!(latest.hash_bytes(&[0; 31]), latest.hash_bytes(&[0; 32]));
+        // legacy scheme can't distinguish them
+        let legacy = HashVersion::V1;
+        assert_eq!(legacy.hash_bytes(&[1]), legacy.hash_bytes(&[1, 0]));
+    }
+
+    #[test]
+    fn migration() {
+        let data = b"hello world";
+        let legacy_hash = HashVersion::V1.hash_bytes(data);
+        let latest_hash = poseidon_hash(data);
+        assert_ne!(legacy_hash, latest_hash);
+        assert_eq!(migrate_hash(data, &legacy_hash), Some(latest_hash));
+        // already migrated or unrelated hashes are not changed
+        assert_eq!(migrate_hash(data, &latest_hash), None);
+        assert_eq!(migrate_hash(b"hello", &legacy_hash), None);
+    }
 }
@@ -406,6 +406,13 @@ impl<'a, BuilderStage, EXT, DB: Database> EvmBuilder<'a, BuilderStage, EXT, DB>
         self
     }
 
+    /// Sets zktrie mirror of the committed state, it's used to calculate Fluent state root.
+    #[cfg(feature = "std")]
+    pub fn with_state_trie(mut self, state_trie: crate::StateTrie) -> Self {
+        self.context.evm.state_trie = state_trie;
+        self
+    }
+
     /// Allows modification of Evm's Config Environment.
     pub fn modify_cfg_env(mut self, f: impl FnOnce(&mut CfgEnv)) -> Self {
         f(&mut self.context.evm.env.cfg);
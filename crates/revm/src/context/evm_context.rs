@@ -292,6 +292,8 @@ pub(crate) mod test_utils {
                 journaled_state: JournaledState::new(SpecId::CANCUN, HashSet::new()),
                 db,
                 error: Ok(()),
+                #[cfg(feature = "std")]
+                state_trie: crate::StateTrie::default(),
                 #[cfg(feature = "optimism")]
                 l1_block_info: None,
             },
@@ -307,6 +309,8 @@ pub(crate) mod test_utils {
                 journaled_state: JournaledState::new(SpecId::CANCUN, HashSet::new()),
                 db,
                 error: Ok(()),
+                #[cfg(feature = "std")]
+                state_trie: crate::StateTrie::default(),
                 #[cfg(feature = "optimism")]
                 l1_block_info: None,
             },
@@ -1,4 +1,6 @@
 use crate::interpreter::{SStoreResult, SelfDestructResult};
+#[cfg(feature = "std")]
+use crate::StateTrie;
 use crate::{
     db::Database,
     interpreter::{Contract, CreateInputs, Gas, InstructionResult, Interpreter, InterpreterResult},
@@ -27,6 +29,9 @@ pub struct InnerEvmContext<DB: Database> {
     pub db: DB,
     /// Error that happened during execution.
     pub error: Result<(), EVMError<ExitCode>>,
+    /// Zktrie mirror of the committed state that is used to calculate Fluent state root.
+    #[cfg(feature = "std")]
+    pub state_trie: StateTrie,
     /// Used as temporary value holder to store L1 block info.
     #[cfg(feature = "optimism")]
     pub l1_block_info: Option<crate::optimism::L1BlockInfo>,
@@ -39,6 +44,8 @@ impl<DB: Database + Clone> Clone for InnerEvmContext<DB> {
             journaled_state: self.journaled_state.clone(),
             db: self.db.clone(),
             error: self.error.clone(),
+            #[cfg(feature = "std")]
+            state_trie: self.state_trie.clone(),
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info.clone(),
         }
@@ -52,6 +59,8 @@ impl<DB: Database> InnerEvmContext<DB> {
             journaled_state: JournaledState::new(SpecId::LATEST, HashSet::new()),
             db,
             error: Ok(()),
+            #[cfg(feature = "std")]
+            state_trie: StateTrie::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
         }
@@ -65,6 +74,8 @@ impl<DB: Database> InnerEvmContext<DB> {
             journaled_state: JournaledState::new(SpecId::LATEST, HashSet::new()),
             db,
             error: Ok(()),
+            #[cfg(feature = "std")]
+            state_trie: StateTrie::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
         }
@@ -80,6 +91,8 @@ impl<DB: Database> InnerEvmContext<DB> {
             journaled_state: self.journaled_state,
             db,
             error: Ok(()),
+            #[cfg(feature = "std")]
+            state_trie: self.state_trie,
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info,
         }
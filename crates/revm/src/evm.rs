@@ -7,7 +7,7 @@ use crate::{
     interpreter::{CallOutcome, CreateOutcome, InterpreterResult},
     primitives::{
         specification::SpecId, Address, BlockEnv, CfgEnv, EVMError, EVMResult, EnvWithHandlerCfg,
-        ExecutionResult, HandlerCfg, ResultAndState, TransactTo, TxEnv, B256, U256,
+        ExecutionResult, HandlerCfg, HashMap, ResultAndState, State, TransactTo, TxEnv, B256, U256,
     },
     Context, ContextWithHandlerCfg, EvmContext, FrameResult, JournalCheckpoint, JournalEntry,
 };
@@ -17,8 +17,9 @@ use fluentbase_core::evm::sload::_evm_sload;
 use fluentbase_core::evm::sstore::_evm_sstore;
 use fluentbase_core::fluent_host::FluentHost;
 use fluentbase_core::{
-    consts::{ECL_CONTRACT_ADDRESS, WCL_CONTRACT_ADDRESS},
+    consts::{is_precompile_address, ECL_CONTRACT_ADDRESS, WCL_CONTRACT_ADDRESS},
     evm::create::_evm_create,
+    precompiles::decode_precompile_output,
     wasm::create::_wasm_create,
     Account, JZKT_ACCOUNT_COMPRESSION_FLAGS, JZKT_ACCOUNT_FIELDS_COUNT,
     JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
@@ -31,7 +32,7 @@ use fluentbase_sdk::{
 };
 use fluentbase_types::{
     address, BytecodeType, Bytes, Bytes32, ExitCode, IJournaledTrie, JournalEvent, JournalLog,
-    NATIVE_TRANSFER_ADDRESS, NATIVE_TRANSFER_KECCAK, POSEIDON_EMPTY, STATE_MAIN,
+    StateDiff, NATIVE_TRANSFER_ADDRESS, NATIVE_TRANSFER_KECCAK, POSEIDON_EMPTY, STATE_MAIN,
 };
 use revm_primitives::{hex, Bytecode, CreateScheme, Env, Log, LogData};
 use std::vec::Vec;
@@ -66,9 +67,21 @@ impl<EXT, DB: Database + DatabaseCommit> Evm<'_, EXT, DB> {
     /// Commit the changes to the database.
     pub fn transact_commit(&mut self) -> Result<ExecutionResult, EVMError<ExitCode>> {
         let ResultAndState { result, state } = self.transact()?;
-        self.context.evm.db.commit(state);
+        self.commit_state(state)?;
         Ok(result)
     }
+
+    /// Commits the state returned by [`Evm::transact`] into the database and the state trie.
+    pub fn commit_state(&mut self, state: State) -> Result<(), EVMError<ExitCode>> {
+        #[cfg(feature = "std")]
+        self.context
+            .evm
+            .state_trie
+            .apply_state(&state, None)
+            .map_err(EVMError::Database)?;
+        self.context.evm.db.commit(state);
+        Ok(())
+    }
 }
 
 impl<'a> Evm<'a, (), EmptyDB> {
@@ -419,6 +432,10 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         self.context.evm.touch(&caller_account.address);
         self.context.evm.touch(&callee_account.address);
 
+        if is_precompile_address(&callee_account.address) {
+            return self.call_precompile(caller_account, callee_account, value, input, gas);
+        }
+
         let (callee_bytecode, _) = self
             .context
             .evm
@@ -484,6 +501,103 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         }
     }
 
+    /// Calls rWASM precompile deployed at genesis under one of the precompile addresses.
+    ///
+    /// Precompiles are executed directly w/o ECL or WCL, call data is passed as a contract input
+    /// and any failure consumes all gas provided to the call, like it's done for EVM precompiles.
+    fn call_precompile(
+        &mut self,
+        caller_account: &mut Account,
+        callee_account: &mut Account,
+        value: U256,
+        input: Bytes,
+        mut gas: Gas,
+    ) -> CallOutcome {
+        let return_result = |exit_code: ExitCode, output: Bytes, gas: Gas| CallOutcome {
+            result: InterpreterResult {
+                result: exit_code,
+                output,
+                gas,
+            },
+            memory_offset: Default::default(),
+        };
+
+        let checkpoint = self.context.evm.journaled_state.checkpoint();
+
+        // precompiles can receive value, so we must transfer it before the execution
+        let transfer_result = self.context.evm.inner.journaled_state.transfer(
+            &caller_account.address,
+            &callee_account.address,
+            value,
+            &mut self.context.evm.inner.db,
+        );
+        match transfer_result {
+            Ok(None) => {}
+            Ok(Some(exit_code)) => {
+                self.context
+                    .evm
+                    .journaled_state
+                    .checkpoint_revert(checkpoint);
+                return return_result(exit_code, Bytes::new(), gas);
+            }
+            Err(_) => {
+                self.context
+                    .evm
+                    .journaled_state
+                    .checkpoint_revert(checkpoint);
+                return return_result(ExitCode::FatalExternalError, Bytes::new(), gas);
+            }
+        }
+
+        // precompile contracts report the EIP gas they used in front of the output, the rWASM fuel
+        // spent on interpretation is bounded by the block gas limit but isn't charged to the caller
+        let mut fuel = Gas::new(
+            self.context
+                .evm
+                .env
+                .block
+                .gas_limit
+                .try_into()
+                .unwrap_or(u64::MAX),
+        );
+        let (output_buffer, exit_code) = self.exec_rwasm_binary_with_fuel(
+            &gas,
+            &mut fuel,
+            caller_account,
+            callee_account,
+            None,
+            input,
+            value,
+        );
+        let (exit_code, output_buffer) = match decode_precompile_output(&output_buffer) {
+            Some((gas_used, output)) if exit_code == ExitCode::Ok => {
+                if gas.record_cost(gas_used) {
+                    (exit_code, Bytes::copy_from_slice(output))
+                } else {
+                    (ExitCode::OutOfFuel, Bytes::new())
+                }
+            }
+            None if exit_code == ExitCode::Ok => (ExitCode::PrecompileError, Bytes::new()),
+            _ => (exit_code, Bytes::new()),
+        };
+
+        if exit_code == ExitCode::Ok {
+            self.context.evm.journaled_state.checkpoint_commit();
+            return_result(exit_code, output_buffer, gas)
+        } else {
+            self.context
+                .evm
+                .journaled_state
+                .checkpoint_revert(checkpoint);
+            gas.record_cost(gas.remaining());
+            let exit_code = match exit_code {
+                ExitCode::OutOfFuel => ExitCode::OutOfFuel,
+                _ => ExitCode::PrecompileError,
+            };
+            return_result(exit_code, Bytes::new(), gas)
+        }
+    }
+
     fn input_from_env(
         &self,
         gas: &Gas,
@@ -525,6 +639,31 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         callee_address: Option<Address>,
         input: Bytes,
         value: U256,
+    ) -> (Bytes, ExitCode) {
+        let contract_gas = *gas;
+        self.exec_rwasm_binary_with_fuel(
+            &contract_gas,
+            gas,
+            caller,
+            callee,
+            callee_address,
+            input,
+            value,
+        )
+    }
+
+    /// Executes rWASM binary with the gas limit passed to the contract taken from `gas` and
+    /// the consumed fuel recorded into `fuel`
+    #[cfg(feature = "std")]
+    fn exec_rwasm_binary_with_fuel(
+        &mut self,
+        gas: &Gas,
+        fuel: &mut Gas,
+        caller: &mut Account,
+        callee: &mut Account,
+        callee_address: Option<Address>,
+        input: Bytes,
+        value: U256,
     ) -> (Bytes, ExitCode) {
         use fluentbase_runtime::{Runtime, RuntimeContext};
         let input = self
@@ -545,7 +684,7 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         }
         let ctx = RuntimeContext::new(rwasm_bytecode)
             .with_input(input)
-            .with_fuel_limit(gas.remaining())
+            .with_fuel_limit(fuel.remaining())
             .with_jzkt(jzkt)
             .with_state(STATE_MAIN);
         let mut runtime = Runtime::new(ctx);
@@ -578,7 +717,7 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
             }
             println!(" - opcode used: {}", runtime.store().tracer().logs.len());
         }
-        gas.record_cost(result.fuel_consumed);
+        fuel.record_cost(result.fuel_consumed);
         (Bytes::from(result.output.clone()), result.exit_code.into())
     }
 
@@ -623,13 +762,40 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
     }
 }
 
+#[cfg(feature = "std")]
 struct JournalDbWrapper<'a, DB: Database> {
     ctx: RefCell<&'a mut EvmContext<DB>>,
 }
 
+#[cfg(feature = "std")]
+impl<'a, DB: Database> JournalDbWrapper<'a, DB> {
+    /// Returns current value of the trie leaf, in the same format as `get` does
+    fn leaf_value(state: &State, key: &[u8; 32]) -> (Vec<[u8; 32]>, u32) {
+        if key[..12] == [0u8; 12] {
+            let address = Address::from_slice(&key[12..]);
+            let account = Account::from(state.get(&address).unwrap().info.clone());
+            (
+                account.get_fields().to_vec(),
+                JZKT_ACCOUNT_COMPRESSION_FLAGS,
+            )
+        } else {
+            let value = state
+                .get(&EVM_STORAGE_ADDRESS)
+                .and_then(|account| account.storage.get(&U256::from_be_bytes(*key)))
+                .map(|slot| slot.present_value)
+                .unwrap_or_default();
+            (
+                vec![value.to_be_bytes::<32>()],
+                JZKT_STORAGE_COMPRESSION_FLAGS,
+            )
+        }
+    }
+}
+
 /// A special account for storing EVM storage trie `keccak256("evm_storage_trie")[12..32]`
 pub const EVM_STORAGE_ADDRESS: Address = address!("fabefeab43f96e51d7ace194b9abd33305bb6bfb");
 
+#[cfg(feature = "std")]
 impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
     fn checkpoint(&self) -> fluentbase_types::JournalCheckpoint {
         let mut ctx = self.ctx.borrow_mut();
@@ -687,13 +853,38 @@ impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
         }
     }
 
-    fn remove(&self, _key: &[u8; 32]) {
-        // TODO: "account removal is not supported"
+    fn remove(&self, key: &[u8; 32]) {
+        let mut ctx = self.ctx.borrow_mut();
+        if key[..12] == [0u8; 12] {
+            let address = Address::from_slice(&key[12..]);
+            let (account, _) = ctx.load_account_with_code(address).expect("database error");
+            let was_destroyed = account.is_selfdestructed();
+            let had_balance = account.info.balance;
+            account.mark_selfdestruct();
+            account.info.balance = U256::ZERO;
+            ctx.journaled_state
+                .journal
+                .last_mut()
+                .unwrap()
+                .push(JournalEntry::AccountDestroyed {
+                    address,
+                    target: address,
+                    was_destroyed,
+                    had_balance,
+                });
+        } else {
+            ctx.sstore(EVM_STORAGE_ADDRESS, U256::from_be_bytes(*key), U256::ZERO)
+                .expect("failed to remove storage slot");
+        }
     }
 
     fn compute_root(&self) -> [u8; 32] {
-        // TODO: "root is not supported"
-        [0u8; 32]
+        let mut ctx = self.ctx.borrow_mut();
+        let ctx = &mut ctx.inner;
+        // apply changes of the current transaction on top of the committed state trie
+        ctx.state_trie
+            .compute_root(&ctx.journaled_state.state, None)
+            .expect("failed to compute state root")
     }
 
     fn emit_log(&self, address: Address, topics: Vec<B256>, data: Bytes) {
@@ -716,12 +907,32 @@ impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
     }
 
     fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
+        // state is finalized by revm itself at the end of the transaction, and logs are
+        // stored inside revm's journal
+        Ok((self.compute_root(), vec![]))
+    }
+
+    fn commit_with_diff(&self) -> Result<([u8; 32], Vec<JournalLog>, StateDiff), ExitCode> {
+        let mut ctx = self.ctx.borrow_mut();
+        let ctx = &mut ctx.inner;
+        let mut diff = StateDiff::default();
+        let root = ctx
+            .state_trie
+            .compute_root(&ctx.journaled_state.state, Some(&mut diff))?;
+        Ok((root, vec![], diff))
+    }
+
+    fn checkpoint_commit(&self) {
         let mut ctx = self.ctx.borrow_mut();
         ctx.journaled_state.checkpoint_commit();
-        Ok(([0u8; 32], vec![]))
     }
 
-    fn rollback(&self, checkpoint: fluentbase_types::JournalCheckpoint) {
+    fn update_storage_slot(&self, key: &[u8; 32], address: &Address, slot: &U256) {
+        let mut ctx = self.ctx.borrow_mut();
+        ctx.state_trie.update_storage_slot(key, address, slot);
+    }
+
+    fn checkpoint_revert(&self, checkpoint: fluentbase_types::JournalCheckpoint) {
         let mut ctx = self.ctx.borrow_mut();
         ctx.journaled_state
             .checkpoint_revert((checkpoint.0, checkpoint.1).into());
@@ -764,8 +975,51 @@ impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
     }
 
     fn journal(&self) -> Vec<JournalEvent> {
-        // TODO: "journal is not supported here"
-        vec![]
+        let ctx = self.ctx.borrow();
+        let state = &ctx.journaled_state.state;
+        let mut result = Vec::new();
+        let mut last_state = HashMap::<[u8; 32], usize>::new();
+        // revm doesn't store intermediate values in its journal, so events contain the latest
+        // value of each affected leaf
+        for entry in ctx.journaled_state.journal.iter().flatten() {
+            let changes = match entry {
+                JournalEntry::BalanceTransfer { from, to, .. } => {
+                    vec![(from.into_word().0, false), (to.into_word().0, false)]
+                }
+                JournalEntry::NonceChange { address }
+                | JournalEntry::AccountCreated { address }
+                | JournalEntry::CodeChange { address } => vec![(address.into_word().0, false)],
+                JournalEntry::AccountDestroyed {
+                    address, target, ..
+                } if address != target => {
+                    vec![(address.into_word().0, true), (target.into_word().0, false)]
+                }
+                JournalEntry::AccountDestroyed { address, .. } => {
+                    vec![(address.into_word().0, true)]
+                }
+                JournalEntry::StorageChange {
+                    address,
+                    key,
+                    had_value: Some(_),
+                } if *address == EVM_STORAGE_ADDRESS => vec![(key.to_be_bytes::<32>(), false)],
+                _ => continue,
+            };
+            for (key, is_removed) in changes {
+                let prev_state = last_state.insert(key, result.len());
+                result.push(if is_removed {
+                    JournalEvent::ItemRemoved { key, prev_state }
+                } else {
+                    let (preimage, flags) = Self::leaf_value(state, &key);
+                    JournalEvent::ItemChanged {
+                        key,
+                        preimage,
+                        flags,
+                        prev_state,
+                    }
+                });
+            }
+        }
+        result
     }
 }
 
@@ -26,6 +26,8 @@ mod inspector;
 mod journaled_state;
 #[cfg(feature = "optimism")]
 pub mod optimism;
+#[cfg(feature = "std")]
+mod state_trie;
 #[cfg(test)]
 mod test;
 mod types;
@@ -47,6 +49,8 @@ pub use frame::{CallFrame, CreateFrame, Frame, FrameData, FrameOrResult, FrameRe
 pub use handler::Handler;
 pub use inspector::{inspector_handle_register, inspectors, GetInspector, Inspector};
 pub use journaled_state::{JournalCheckpoint, JournalEntry, JournaledState};
+#[cfg(feature = "std")]
+pub use state_trie::StateTrie;
 // export Optimism types, helpers, and constants
 #[cfg(feature = "optimism")]
 pub use optimism::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
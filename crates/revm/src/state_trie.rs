@@ -0,0 +1,192 @@
+use crate::{
+    db::{AccountState, CacheDB},
+    primitives::{Address, State, B256, U256},
+    EVM_STORAGE_ADDRESS,
+};
+use core::fmt;
+use fluentbase_core::{Account, JZKT_ACCOUNT_COMPRESSION_FLAGS, JZKT_STORAGE_COMPRESSION_FLAGS};
+use fluentbase_runtime::{
+    types::{InMemoryTrieDb, OverlayTrieDb, TrieDb},
+    zktrie::ZkTrieStateDb,
+    TrieStorage,
+};
+use fluentbase_types::{ExitCode, StateDiff};
+use std::collections::{BTreeMap, HashMap};
+
+/// Zktrie mirror of the state committed through revm.
+///
+/// Revm stores state in its own database, so to provide the same state root as the Fluent runtime
+/// does we replay every committed transaction on top of the zktrie. Contract storage is kept
+/// inside [`EVM_STORAGE_ADDRESS`] account where slots are already hashed trie keys.
+///
+/// The mirror must be seeded with the same state as revm's database (see
+/// [`StateTrie::from_cache_db`]) and it's updated only by [`Evm::transact_commit`] and
+/// [`Evm::commit_state`]. If state is committed into the database directly, then the same state
+/// must be passed into [`StateTrie::apply_state`], otherwise the mirror diverges.
+///
+/// [`Evm::transact_commit`]: crate::Evm::transact_commit
+/// [`Evm::commit_state`]: crate::Evm::commit_state
+#[derive(Clone)]
+pub struct StateTrie {
+    /// Nodes and preimages of the committed state only
+    db: InMemoryTrieDb,
+    root: [u8; 32],
+    /// Contract address and slot of the storage keys written since the last applied state
+    storage_slots: HashMap<[u8; 32], (Address, U256)>,
+}
+
+impl Default for StateTrie {
+    fn default() -> Self {
+        Self::new(InMemoryTrieDb::default(), [0u8; 32])
+    }
+}
+
+impl fmt::Debug for StateTrie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateTrie")
+            .field("root", &B256::from(self.root()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl StateTrie {
+    /// Opens the trie with `root` that is already stored inside `db`
+    pub fn new(db: InMemoryTrieDb, root: [u8; 32]) -> Self {
+        Self {
+            db,
+            root,
+            storage_slots: HashMap::new(),
+        }
+    }
+
+    /// Builds the trie from all accounts and storage slots of the cache database. Storage slots
+    /// with zero value are skipped, because cache database doesn't distinguish slots that were
+    /// only read from the ones that were written.
+    pub fn from_cache_db<ExtDB>(db: &CacheDB<ExtDB>) -> Result<Self, ExitCode> {
+        let mut state_trie = Self::default();
+        let mut changes = BTreeMap::new();
+        for (address, account) in db.accounts.iter() {
+            if *address == EVM_STORAGE_ADDRESS {
+                for (slot, value) in account.storage.iter().filter(|(_, v)| !v.is_zero()) {
+                    changes.insert(
+                        slot.to_be_bytes::<32>(),
+                        Some((
+                            JZKT_STORAGE_COMPRESSION_FLAGS,
+                            vec![value.to_be_bytes::<32>()],
+                        )),
+                    );
+                }
+            } else if account.account_state != AccountState::NotExisting {
+                let fields = Account::from(account.info.clone()).get_fields().to_vec();
+                changes.insert(
+                    address.into_word().0,
+                    Some((JZKT_ACCOUNT_COMPRESSION_FLAGS, fields)),
+                );
+            }
+        }
+        state_trie.write_changes(changes, None)?;
+        Ok(state_trie)
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    pub fn update_storage_slot(&mut self, key: &[u8; 32], address: &Address, slot: &U256) {
+        self.storage_slots.insert(*key, (*address, *slot));
+    }
+
+    /// Applies revm state changes to the trie and returns new state root, if `diff` is provided
+    /// then it's filled with all changes that were written into the trie
+    pub fn apply_state(
+        &mut self,
+        state: &State,
+        diff: Option<&mut StateDiff>,
+    ) -> Result<[u8; 32], ExitCode> {
+        let changes = Self::collect_changes(state);
+        self.write_changes(changes, diff)?;
+        self.storage_slots.clear();
+        Ok(self.root)
+    }
+
+    /// Computes state root as if revm state changes were applied, the committed state isn't
+    /// modified because all trie writes go into the temporary overlay
+    pub fn compute_root(
+        &mut self,
+        state: &State,
+        diff: Option<&mut StateDiff>,
+    ) -> Result<[u8; 32], ExitCode> {
+        let changes = Self::collect_changes(state);
+        let mut trie = ZkTrieStateDb::new_opened(OverlayTrieDb::new(&mut self.db), &self.root);
+        Self::write_trie(&mut trie, &self.storage_slots, changes, diff)
+    }
+
+    fn collect_changes(state: &State) -> BTreeMap<[u8; 32], Option<(u32, Vec<[u8; 32]>)>> {
+        let mut changes = BTreeMap::new();
+        for (address, account) in state.iter() {
+            if *address == EVM_STORAGE_ADDRESS {
+                for (slot, value) in account.storage.iter().filter(|(_, v)| v.is_changed()) {
+                    changes.insert(
+                        slot.to_be_bytes::<32>(),
+                        Some((
+                            JZKT_STORAGE_COMPRESSION_FLAGS,
+                            vec![value.present_value.to_be_bytes::<32>()],
+                        )),
+                    );
+                }
+            } else if account.is_selfdestructed() {
+                changes.insert(address.into_word().0, None);
+            } else if account.is_touched() {
+                let fields = Account::from(account.info.clone()).get_fields().to_vec();
+                changes.insert(
+                    address.into_word().0,
+                    Some((JZKT_ACCOUNT_COMPRESSION_FLAGS, fields)),
+                );
+            }
+        }
+        changes
+    }
+
+    fn write_changes(
+        &mut self,
+        changes: BTreeMap<[u8; 32], Option<(u32, Vec<[u8; 32]>)>>,
+        diff: Option<&mut StateDiff>,
+    ) -> Result<(), ExitCode> {
+        let mut trie = ZkTrieStateDb::new_opened(&mut self.db, &self.root);
+        self.root = Self::write_trie(&mut trie, &self.storage_slots, changes, diff)?;
+        Ok(())
+    }
+
+    fn write_trie<DB: TrieDb>(
+        trie: &mut ZkTrieStateDb<DB>,
+        storage_slots: &HashMap<[u8; 32], (Address, U256)>,
+        changes: BTreeMap<[u8; 32], Option<(u32, Vec<[u8; 32]>)>>,
+        mut diff: Option<&mut StateDiff>,
+    ) -> Result<[u8; 32], ExitCode> {
+        for (key, value) in changes.into_iter() {
+            if let Some(diff) = diff.as_mut() {
+                let before = trie.get(&key).map(|(values, _flags)| values);
+                let after = value.as_ref().map(|(_flags, values)| values);
+                if let Some((address, slot)) = storage_slots.get(&key) {
+                    diff.add_storage_change(
+                        *address,
+                        *slot,
+                        before.and_then(|v| v.first().copied()),
+                        after.and_then(|v| v.first().copied()),
+                    );
+                } else if key[..12] == [0u8; 12] {
+                    diff.add_account_change(
+                        Address::from_slice(&key[12..]),
+                        before.as_deref(),
+                        after.map(|v| v.as_slice()),
+                    )?;
+                }
+            }
+            match value {
+                Some((flags, values)) => trie.update(&key, flags, &values)?,
+                None => trie.remove(&key)?,
+            }
+        }
+        Ok(trie.compute_root())
+    }
+}
@@ -1,7 +1,7 @@
-use crate::{Evm, InMemoryDB};
+use crate::{Evm, InMemoryDB, StateTrie};
 use core::{mem::take, str::from_utf8};
 use fluentbase_codec::{BufferDecoder, Encoder};
-use fluentbase_core::{helpers::calc_create_address, Account};
+use fluentbase_core::{consts::PRECOMPILE_IDENTITY_ADDRESS, helpers::calc_create_address, Account};
 use fluentbase_genesis::{
     devnet::{devnet_genesis_from_file, KECCAK_HASH_KEY, POSEIDON_HASH_KEY},
     Genesis, EXAMPLE_GREETING_ADDRESS,
@@ -15,7 +15,7 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use revm_primitives::{
     db::DatabaseCommit, hex, keccak256, AccountInfo, Bytecode, CreateScheme, EVMError, Env,
-    ExecutionResult, HashMap, Output, TransactTo,
+    ExecutionResult, HashMap, Output, State, TransactTo,
 };
 use rwasm::engine::DropKeep;
 use rwasm::instruction_set;
@@ -25,6 +25,7 @@ use rwasm::rwasm::{BinaryFormat, InstructionSet, RwasmModule};
 struct TestingContext {
     genesis: Genesis,
     db: InMemoryDB,
+    state_trie: StateTrie,
 }
 
 impl Default for TestingContext {
@@ -75,7 +76,12 @@ impl TestingContext {
             info.rwasm_code = v.code.clone().map(Bytecode::new_raw);
             db.insert_account_info(*k, info);
         }
-        Self { genesis, db }
+        let state_trie = StateTrie::from_cache_db(&db).unwrap();
+        Self {
+            genesis,
+            db,
+            state_trie,
+        }
     }
 
     pub(crate) fn add_contract<I: Into<RwasmModule>>(
@@ -89,6 +95,14 @@ impl TestingContext {
             rwasm_module.write_binary_to_vec(&mut result).unwrap();
             result
         };
+        self.add_rwasm_binary(address, rwasm_binary)
+    }
+
+    pub(crate) fn add_rwasm_binary(
+        &mut self,
+        address: Address,
+        rwasm_binary: Vec<u8>,
+    ) -> AccountInfo {
         let account = Account {
             address,
             balance: U256::ZERO,
@@ -103,6 +117,9 @@ impl TestingContext {
         info.code = None;
         info.rwasm_code = Some(Bytecode::new_raw(rwasm_binary.into()));
         self.db.insert_account_info(address, info.clone());
+        let mut revm_account = revm_primitives::Account::from(info.clone());
+        revm_account.mark_touch();
+        self.commit(HashMap::from([(address, revm_account)]));
         info
     }
 
@@ -116,7 +133,12 @@ impl TestingContext {
         account.info.balance += value;
         let mut revm_account = revm_primitives::Account::from(account.info.clone());
         revm_account.mark_touch();
-        self.db.commit(HashMap::from([(address, revm_account)]));
+        self.commit(HashMap::from([(address, revm_account)]));
+    }
+
+    fn commit(&mut self, state: State) {
+        self.state_trie.apply_state(&state, None).unwrap();
+        self.db.commit(state);
     }
 }
 
@@ -178,8 +200,11 @@ impl<'a> TxBuilder<'a> {
         let mut evm = Evm::builder()
             .with_env(Box::new(take(&mut self.env)))
             .with_db(&mut self.ctx.db)
+            .with_state_trie(take(&mut self.ctx.state_trie))
             .build();
-        evm.transact_commit()
+        let result = evm.transact_commit();
+        self.ctx.state_trie = take(&mut evm.context.evm.state_trie);
+        result
     }
 }
 
@@ -246,6 +271,53 @@ fn test_genesis_greeting() {
     assert_eq!("Hello, World", from_utf8(bytes.as_ref()).unwrap());
 }
 
+#[test]
+fn test_precompile_identity() {
+    let mut ctx = TestingContext::default();
+    ctx.add_rwasm_binary(
+        PRECOMPILE_IDENTITY_ADDRESS,
+        include_bytes!("../../contracts/assets/precompile_identity.rwasm").to_vec(),
+    );
+    const CALLER_ADDRESS: Address = Address::ZERO;
+    let result = call_evm_tx(
+        &mut ctx,
+        CALLER_ADDRESS,
+        PRECOMPILE_IDENTITY_ADDRESS,
+        bytes!("0102030405"),
+        None,
+    )
+    .unwrap();
+    assert!(result.is_success());
+    assert_eq!(
+        result.output().unwrap_or_default().as_ref(),
+        &[0x01, 0x02, 0x03, 0x04, 0x05]
+    );
+    // intrinsic gas (21000 + 5 non-zero calldata bytes * 16) and identity gas (15 + 3 per word)
+    assert_eq!(result.gas_used(), 21_000 + 5 * 16 + 15 + 3);
+    let result = call_evm_tx(
+        &mut ctx,
+        CALLER_ADDRESS,
+        PRECOMPILE_IDENTITY_ADDRESS,
+        Bytes::from(vec![0u8; 64]),
+        None,
+    )
+    .unwrap();
+    assert!(result.is_success());
+    assert_eq!(result.gas_used(), 21_000 + 64 * 4 + 15 + 3 * 2);
+    // precompile failure burns all gas provided to the call
+    let gas_limit = 21_000 + 5 * 16 + 15 + 3 - 1;
+    let result = call_evm_tx(
+        &mut ctx,
+        CALLER_ADDRESS,
+        PRECOMPILE_IDENTITY_ADDRESS,
+        bytes!("0102030405"),
+        Some(gas_limit),
+    )
+    .unwrap();
+    assert!(!result.is_success());
+    assert_eq!(result.gas_used(), gas_limit);
+}
+
 #[test]
 fn test_deploy_greeting() {
     // deploy greeting WASM contract
@@ -458,6 +530,49 @@ fn test_simple_send() {
     assert_eq!(ctx.get_balance(RECIPIENT_ADDRESS), U256::from(1e18));
 }
 
+#[test]
+fn test_state_root_after_send() {
+    let mut ctx = TestingContext::default();
+    const SENDER_ADDRESS: Address = address!("1231238908230948230948209348203984029834");
+    const RECIPIENT_ADDRESS: Address = address!("1092381297182319023812093812312309123132");
+    ctx.add_balance(SENDER_ADDRESS, U256::from(2e18));
+    let empty_root = ctx.state_trie.root();
+    let result = TxBuilder::call(&mut ctx, SENDER_ADDRESS, RECIPIENT_ADDRESS)
+        .value(U256::from(1e18))
+        .exec()
+        .unwrap();
+    assert!(result.is_success());
+    let root = ctx.state_trie.root();
+    assert_ne!(root, empty_root);
+    // incrementally updated mirror must match the trie that is rebuilt from the whole state
+    let expected = StateTrie::from_cache_db(&ctx.db).unwrap();
+    assert_eq!(expected.root(), root);
+}
+
+#[test]
+fn test_state_root_matches_genesis() {
+    let ctx = TestingContext::default();
+    assert_ne!(ctx.state_trie.root(), [0u8; 32]);
+    // pending root is computed on top of the committed trie without modifying it
+    let mut state_trie = ctx.state_trie.clone();
+    const ADDRESS: Address = address!("1231238908230948230948209348203984029834");
+    let mut account = revm_primitives::Account::from(AccountInfo {
+        balance: U256::from(1),
+        ..Default::default()
+    });
+    account.mark_touch();
+    let state = HashMap::from([(ADDRESS, account)]);
+    let pending_root = state_trie.compute_root(&state, None).unwrap();
+    assert_ne!(pending_root, ctx.state_trie.root());
+    assert_eq!(state_trie.root(), ctx.state_trie.root());
+    assert_eq!(state_trie.apply_state(&state, None).unwrap(), pending_root);
+    // state trie can be moved between threads
+    let root = std::thread::spawn(move || state_trie.root())
+        .join()
+        .unwrap();
+    assert_eq!(root, pending_root);
+}
+
 #[test]
 fn test_create_send() {
     // deploy greeting EVM contract
@@ -480,6 +595,8 @@ fn test_create_send() {
     let tx_cost = gas_price * U256::from(result.gas_used());
     assert_eq!(ctx.get_balance(SENDER_ADDRESS), U256::from(1e18) - tx_cost);
     assert_eq!(ctx.get_balance(contract_address), U256::from(1e18));
+    let expected = StateTrie::from_cache_db(&ctx.db).unwrap();
+    assert_eq!(expected.root(), ctx.state_trie.root());
 }
 
 #[test]
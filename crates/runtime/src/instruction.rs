@@ -1,9 +1,18 @@
+pub mod crypto_blake2f;
+pub mod crypto_bls12381;
+pub mod crypto_bn128;
 pub mod crypto_ecrecover;
+pub mod crypto_ed25519_verify;
 pub mod crypto_keccak256;
+pub mod crypto_modexp;
 pub mod crypto_poseidon;
 pub mod crypto_poseidon2;
+pub mod crypto_ripemd160;
+pub mod crypto_secp256r1_verify;
+pub mod crypto_sha256;
 pub mod debug_log;
 pub mod jzkt_checkpoint;
+pub mod jzkt_checkpoint_commit;
 pub mod jzkt_commit;
 pub mod jzkt_compute_root;
 pub mod jzkt_emit_log;
@@ -15,6 +24,7 @@ pub mod jzkt_remove;
 pub mod jzkt_rollback;
 pub mod jzkt_update;
 pub mod jzkt_update_preimage;
+pub mod jzkt_update_storage_slot;
 pub mod sys_exec_hash;
 pub mod sys_forward_output;
 pub mod sys_fuel;
@@ -35,13 +45,24 @@ use crate::instruction::wasm_to_rwasm_size::WasmToRwasmSize;
 use crate::{
     impl_runtime_handler,
     instruction::{
-        crypto_ecrecover::CryptoEcrecover, crypto_keccak256::CryptoKeccak256,
-        crypto_poseidon::CryptoPoseidon, crypto_poseidon2::CryptoPoseidon2,
-        jzkt_checkpoint::JzktCheckpoint, jzkt_commit::JzktCommit,
+        crypto_blake2f::CryptoBlake2f,
+        crypto_bls12381::{
+            CryptoBls12381G1Add, CryptoBls12381G1Msm, CryptoBls12381G2Add, CryptoBls12381G2Msm,
+            CryptoBls12381MapFp2ToG2, CryptoBls12381MapFpToG1, CryptoBls12381Pairing,
+        },
+        crypto_bn128::{CryptoBn128Add, CryptoBn128Mul, CryptoBn128Pairing},
+        crypto_ecrecover::CryptoEcrecover, crypto_ed25519_verify::CryptoEd25519Verify,
+        crypto_keccak256::CryptoKeccak256,
+        crypto_modexp::CryptoModexp, crypto_poseidon::CryptoPoseidon,
+        crypto_poseidon2::CryptoPoseidon2, crypto_ripemd160::CryptoRipemd160,
+        crypto_secp256r1_verify::CryptoSecp256r1Verify, crypto_sha256::CryptoSha256,
+        jzkt_checkpoint::JzktCheckpoint, jzkt_checkpoint_commit::JzktCheckpointCommit,
+        jzkt_commit::JzktCommit,
         jzkt_compute_root::JzktComputeRoot, jzkt_emit_log::JzktEmitLog, jzkt_get::JzktGet,
         jzkt_open::JzktOpen, jzkt_preimage_copy::JzktPreimageCopy,
         jzkt_preimage_size::JzktPreimageSize, jzkt_remove::JzktRemove, jzkt_rollback::JzktRollback,
         jzkt_update::JzktUpdate, jzkt_update_preimage::JzktUpdatePreimage,
+        jzkt_update_storage_slot::JzktUpdateStorageSlot,
         sys_exec_hash::SysExecHash, sys_forward_output::SysForwardOutput, sys_halt::SysHalt,
         sys_input_size::SysInputSize, sys_output_size::SysOutputSize, sys_read::SysRead,
         sys_read_output::SysReadOutput, sys_state::SysState, sys_write::SysWrite,
@@ -66,6 +87,22 @@ impl_runtime_handler!(CryptoKeccak256, CRYPTO_KECCAK256, fn fluentbase_v1alpha::
 impl_runtime_handler!(CryptoPoseidon, CRYPTO_POSEIDON, fn fluentbase_v1alpha::_crypto_poseidon(f32s_offset: u32, f32s_len: u32, output_offset: u32) -> ());
 impl_runtime_handler!(CryptoPoseidon2, CRYPTO_POSEIDON2, fn fluentbase_v1alpha::_crypto_poseidon2(fa32_offset: u32, fb32_offset: u32, fd32_offset: u32, output_offset: u32) -> ());
 impl_runtime_handler!(CryptoEcrecover, CRYPTO_ECRECOVER, fn fluentbase_v1alpha::_crypto_ecrecover(digest32_offset: u32, sig64_offset: u32, output65_offset: u32, rec_id: u32) -> ());
+impl_runtime_handler!(CryptoSha256, CRYPTO_SHA256, fn fluentbase_v1alpha::_crypto_sha256(data_offset: u32, data_len: u32, output32_offset: u32) -> ());
+impl_runtime_handler!(CryptoRipemd160, CRYPTO_RIPEMD160, fn fluentbase_v1alpha::_crypto_ripemd160(data_offset: u32, data_len: u32, output20_offset: u32) -> ());
+impl_runtime_handler!(CryptoBlake2f, CRYPTO_BLAKE2F, fn fluentbase_v1alpha::_crypto_blake2f(rounds: u32, h64_offset: u32, m128_offset: u32, t16_offset: u32, f: u32) -> ());
+impl_runtime_handler!(CryptoBn128Add, CRYPTO_BN128_ADD, fn fluentbase_v1alpha::_crypto_bn128_add(p1_offset: u32, p2_offset: u32, output64_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBn128Mul, CRYPTO_BN128_MUL, fn fluentbase_v1alpha::_crypto_bn128_mul(p_offset: u32, scalar32_offset: u32, output64_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBn128Pairing, CRYPTO_BN128_PAIRING, fn fluentbase_v1alpha::_crypto_bn128_pairing(pairs_offset: u32, pairs_len: u32, output32_offset: u32) -> i32);
+impl_runtime_handler!(CryptoModexp, CRYPTO_MODEXP, fn fluentbase_v1alpha::_crypto_modexp(base_offset: u32, base_len: u32, exp_offset: u32, exp_len: u32, mod_offset: u32, mod_len: u32, output_offset: u32) -> ());
+impl_runtime_handler!(CryptoSecp256r1Verify, CRYPTO_SECP256R1_VERIFY, fn fluentbase_v1alpha::_crypto_secp256r1_verify(digest32_offset: u32, sig64_offset: u32, pk64_offset: u32) -> i32);
+impl_runtime_handler!(CryptoEd25519Verify, CRYPTO_ED25519_VERIFY, fn fluentbase_v1alpha::_crypto_ed25519_verify(msg_offset: u32, msg_len: u32, sig64_offset: u32, pk32_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381G1Add, CRYPTO_BLS12381_G1_ADD, fn fluentbase_v1alpha::_crypto_bls12381_g1_add(p1_offset: u32, p2_offset: u32, output128_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381G1Msm, CRYPTO_BLS12381_G1_MSM, fn fluentbase_v1alpha::_crypto_bls12381_g1_msm(pairs_offset: u32, pairs_len: u32, output128_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381G2Add, CRYPTO_BLS12381_G2_ADD, fn fluentbase_v1alpha::_crypto_bls12381_g2_add(p1_offset: u32, p2_offset: u32, output256_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381G2Msm, CRYPTO_BLS12381_G2_MSM, fn fluentbase_v1alpha::_crypto_bls12381_g2_msm(pairs_offset: u32, pairs_len: u32, output256_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381Pairing, CRYPTO_BLS12381_PAIRING, fn fluentbase_v1alpha::_crypto_bls12381_pairing(pairs_offset: u32, pairs_len: u32, output32_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381MapFpToG1, CRYPTO_BLS12381_MAP_FP_TO_G1, fn fluentbase_v1alpha::_crypto_bls12381_map_fp_to_g1(fp64_offset: u32, output128_offset: u32) -> i32);
+impl_runtime_handler!(CryptoBls12381MapFp2ToG2, CRYPTO_BLS12381_MAP_FP2_TO_G2, fn fluentbase_v1alpha::_crypto_bls12381_map_fp2_to_g2(fp2_128_offset: u32, output256_offset: u32) -> i32);
 
 impl_runtime_handler!(SysHalt, SYS_HALT, fn fluentbase_v1alpha::_sys_halt(exit_code: i32) -> ());
 impl_runtime_handler!(SysWrite, SYS_WRITE, fn fluentbase_v1alpha::_sys_write(offset: u32, length: u32) -> ());
@@ -87,9 +124,11 @@ impl_runtime_handler!(JzktComputeRoot, JZKT_COMPUTE_ROOT, fn fluentbase_v1alpha:
 impl_runtime_handler!(JzktEmitLog, JZKT_EMIT_LOG, fn fluentbase_v1alpha::_jzkt_emit_log(key32_ptr: u32, topics32s_ptr: u32, topics32s_len: u32, data_ptr: u32, data_len: u32) -> ());
 impl_runtime_handler!(JzktCommit, JZKT_COMMIT, fn fluentbase_v1alpha::_jzkt_commit(root32_offset: u32) -> ());
 impl_runtime_handler!(JzktRollback, JZKT_ROLLBACK, fn fluentbase_v1alpha::_jzkt_rollback(checkpoint: u64) -> ());
+impl_runtime_handler!(JzktCheckpointCommit, JZKT_CHECKPOINT_COMMIT, fn fluentbase_v1alpha::_jzkt_checkpoint_commit() -> ());
 impl_runtime_handler!(JzktPreimageSize, JZKT_PREIMAGE_SIZE, fn fluentbase_v1alpha::_jzkt_preimage_size(hash32_ptr: u32) -> u32);
 impl_runtime_handler!(JzktPreimageCopy, JZKT_PREIMAGE_COPY, fn fluentbase_v1alpha::_jzkt_preimage_copy(hash32_ptr: u32, preimage_ptr: u32) -> ());
 impl_runtime_handler!(JzktUpdatePreimage, JZKT_UPDATE_PREIMAGE, fn fluentbase_v1alpha::_jzkt_update_preimage(key32_ptr: u32, field: u32, preimage_ptr: u32, preimage_len: u32) -> i32);
+impl_runtime_handler!(JzktUpdateStorageSlot, JZKT_UPDATE_STORAGE_SLOT, fn fluentbase_v1alpha::_jzkt_update_storage_slot(key32_ptr: u32, address20_ptr: u32, slot32_ptr: u32) -> ());
 
 impl_runtime_handler!(WasmToRwasmSize, WASM_TO_RWASM_SIZE, fn fluentbase_v1alpha::_wasm_to_rwasm_size(input_offset: u32, input_len: u32) -> i32);
 impl_runtime_handler!(WasmToRwasm, WASM_TO_RWASM, fn fluentbase_v1alpha::_wasm_to_rwasm(input_offset: u32, input_len: u32, output_offset: u32, output_len: u32) -> i32);
@@ -104,6 +143,22 @@ fn runtime_register_handlers<DB: IJournaledTrie, const IS_SOVEREIGN: bool>(
     CryptoPoseidon::register_handler(linker, store);
     CryptoPoseidon2::register_handler(linker, store);
     CryptoEcrecover::register_handler(linker, store);
+    CryptoSha256::register_handler(linker, store);
+    CryptoRipemd160::register_handler(linker, store);
+    CryptoBlake2f::register_handler(linker, store);
+    CryptoBn128Add::register_handler(linker, store);
+    CryptoBn128Mul::register_handler(linker, store);
+    CryptoBn128Pairing::register_handler(linker, store);
+    CryptoModexp::register_handler(linker, store);
+    CryptoSecp256r1Verify::register_handler(linker, store);
+    CryptoEd25519Verify::register_handler(linker, store);
+    CryptoBls12381G1Add::register_handler(linker, store);
+    CryptoBls12381G1Msm::register_handler(linker, store);
+    CryptoBls12381G2Add::register_handler(linker, store);
+    CryptoBls12381G2Msm::register_handler(linker, store);
+    CryptoBls12381Pairing::register_handler(linker, store);
+    CryptoBls12381MapFpToG1::register_handler(linker, store);
+    CryptoBls12381MapFp2ToG2::register_handler(linker, store);
     SysHalt::register_handler(linker, store);
     SysWrite::register_handler(linker, store);
     SysForwardOutput::register_handler(linker, store);
@@ -126,10 +181,12 @@ fn runtime_register_handlers<DB: IJournaledTrie, const IS_SOVEREIGN: bool>(
     if IS_SOVEREIGN {
         JzktCommit::register_handler(linker, store);
         JzktRollback::register_handler(linker, store);
+        JzktCheckpointCommit::register_handler(linker, store);
     }
     if IS_SOVEREIGN {
         JzktPreimageSize::register_handler(linker, store);
         JzktUpdatePreimage::register_handler(linker, store);
+        JzktUpdateStorageSlot::register_handler(linker, store);
     }
     JzktPreimageCopy::register_handler(linker, store);
     WasmToRwasmSize::register_handler(linker, store);
@@ -0,0 +1,131 @@
+use crate::RuntimeContext;
+use byteorder::{ByteOrder, LittleEndian};
+use fluentbase_types::{ExitCode, IJournaledTrie};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+pub struct CryptoBlake2f;
+
+/// SIGMA from spec: <https://datatracker.ietf.org/doc/html/rfc7693#section-2.7>
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+impl CryptoBlake2f {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        rounds: u32,
+        h64_offset: u32,
+        m128_offset: u32,
+        t16_offset: u32,
+        f: u32,
+    ) -> Result<(), Trap> {
+        // the same as the BLAKE2F precompile, every round costs 1 gas (EIP-152)
+        let fuel_cost = rounds as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let h = caller.read_memory(h64_offset, 64)?;
+        let m = caller.read_memory(m128_offset, 128)?;
+        let t = caller.read_memory(t16_offset, 16)?;
+        let output = Self::fn_impl(rounds, h, m, t, f != 0);
+        caller.write_memory(h64_offset, &output)?;
+        Ok(())
+    }
+
+    /// Blake2 compression function F from EIP-152, all words are little-endian encoded, and the
+    /// result is the new state vector `h`.
+    pub fn fn_impl(rounds: u32, h: &[u8], m: &[u8], t: &[u8], f: bool) -> [u8; 64] {
+        let mut state = [0u64; 8];
+        LittleEndian::read_u64_into(&h[..64], &mut state);
+        let mut message = [0u64; 16];
+        LittleEndian::read_u64_into(&m[..128], &mut message);
+        let mut offset = [0u64; 2];
+        LittleEndian::read_u64_into(&t[..16], &mut offset);
+        Self::compress(rounds as usize, &mut state, &message, &offset, f);
+        let mut result = [0u8; 64];
+        LittleEndian::write_u64_into(&state, &mut result);
+        result
+    }
+
+    /// G function: <https://tools.ietf.org/html/rfc7693#section-3.1>
+    #[inline(always)]
+    fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    }
+
+    fn compress(rounds: usize, h: &mut [u64; 8], m: &[u64; 16], t: &[u64; 2], f: bool) {
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(h);
+        v[8..].copy_from_slice(&IV);
+        v[12] ^= t[0];
+        v[13] ^= t[1];
+        if f {
+            v[14] = !v[14];
+        }
+        for i in 0..rounds {
+            let s = &SIGMA[i % 10];
+            Self::g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            Self::g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            Self::g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            Self::g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            Self::g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            Self::g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            Self::g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            Self::g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+        for i in 0..8 {
+            h[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CryptoBlake2f;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_blake2f_eip152_vector() {
+        // test vector 5 from EIP-152, it's a Blake2b hash of "abc"
+        let h = hex!("48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b");
+        let mut m = [0u8; 128];
+        m[..3].copy_from_slice(b"abc");
+        let t = hex!("03000000000000000000000000000000");
+        let output = CryptoBlake2f::fn_impl(12, &h, &m, &t, true);
+        assert_eq!(output, hex!("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"));
+        // the same input without final block flag
+        let output = CryptoBlake2f::fn_impl(12, &h, &m, &t, false);
+        assert_eq!(output, hex!("75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735"));
+    }
+}
@@ -0,0 +1,592 @@
+use crate::RuntimeContext;
+use blst::{
+    blst_bendian_from_fp, blst_final_exp, blst_fp, blst_fp12, blst_fp12_is_one, blst_fp12_mul,
+    blst_fp2, blst_fp_from_bendian, blst_map_to_g1, blst_map_to_g2, blst_miller_loop, blst_p1,
+    blst_p1_add_or_double, blst_p1_affine, blst_p1_affine_in_g1, blst_p1_affine_on_curve,
+    blst_p1_from_affine, blst_p1_is_inf, blst_p1_mult, blst_p1_to_affine, blst_p2,
+    blst_p2_add_or_double, blst_p2_affine, blst_p2_affine_in_g2, blst_p2_affine_on_curve,
+    blst_p2_from_affine, blst_p2_is_inf, blst_p2_mult, blst_p2_to_affine, blst_scalar,
+    blst_scalar_from_bendian,
+};
+use fluentbase_types::{ExitCode, IJournaledTrie, SysFuncIdx, BLS12381_MSM_DISCOUNT_TABLE};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+/// Field element is encoded as 64 bytes, where top 16 bytes must be zero (EIP-2537)
+const PADDED_FP_LEN: usize = 64;
+const FP_PAD_LEN: usize = 16;
+const FP_LEN: usize = 48;
+/// Size of encoded G1 point (x, y)
+const G1_POINT_LEN: usize = 128;
+/// Size of encoded G2 point (x_c0, x_c1, y_c0, y_c1)
+const G2_POINT_LEN: usize = 256;
+const SCALAR_LEN: usize = 32;
+/// Size of encoded MSM element (point and scalar)
+const G1_MSM_ELEMENT_LEN: usize = G1_POINT_LEN + SCALAR_LEN;
+const G2_MSM_ELEMENT_LEN: usize = G2_POINT_LEN + SCALAR_LEN;
+/// Size of encoded pairing element (G1 point and G2 point)
+const PAIR_ELEMENT_LEN: usize = G1_POINT_LEN + G2_POINT_LEN;
+/// Scalars aren't reduced by the group order, so we need to process all 256 bits
+const SCALAR_BITS: usize = 256;
+/// Gas costs of the BLS12-381 precompiles that depend on the input size (EIP-2537)
+const G1_MUL_FUEL_COST: u64 = 12_000;
+const G2_MUL_FUEL_COST: u64 = 45_000;
+const PAIRING_BASE_FUEL_COST: u64 = 65_000;
+const PAIRING_PER_PAIR_FUEL_COST: u64 = 43_000;
+
+/// Multi-scalar multiplication is charged the same as the MSM precompiles, except a single
+/// element that is charged as the multiplication precompile, because `g1_mul` and `g2_mul` use
+/// the same functions
+fn msm_fuel_cost(pairs_len: u32, element_len: usize, multiplication_cost: u64) -> u64 {
+    let k = pairs_len as usize / element_len;
+    if k == 0 {
+        return 0;
+    } else if k == 1 {
+        return multiplication_cost;
+    }
+    let discount = BLS12381_MSM_DISCOUNT_TABLE[k.min(BLS12381_MSM_DISCOUNT_TABLE.len()) - 1];
+    (k as u64 * discount * multiplication_cost) / 1000
+}
+
+fn read_fp(input: &[u8]) -> Result<blst_fp, ExitCode> {
+    if input[..FP_PAD_LEN].iter().any(|v| *v != 0) {
+        return Err(ExitCode::Bls12381InvalidFieldElement);
+    }
+    let input = &input[FP_PAD_LEN..PADDED_FP_LEN];
+    let mut fp = blst_fp::default();
+    let mut encoded = [0u8; FP_LEN];
+    unsafe {
+        blst_fp_from_bendian(&mut fp, input.as_ptr());
+        blst_bendian_from_fp(encoded.as_mut_ptr(), &fp);
+    }
+    // non-canonical encoding (value is not less than modulus) doesn't survive the round trip
+    if encoded != input {
+        return Err(ExitCode::Bls12381InvalidFieldElement);
+    }
+    Ok(fp)
+}
+
+fn read_fp2(input: &[u8]) -> Result<blst_fp2, ExitCode> {
+    Ok(blst_fp2 {
+        fp: [read_fp(&input[..64])?, read_fp(&input[64..128])?],
+    })
+}
+
+fn encode_fp(fp: &blst_fp, output: &mut [u8]) {
+    unsafe { blst_bendian_from_fp(output[FP_PAD_LEN..PADDED_FP_LEN].as_mut_ptr(), fp) }
+}
+
+fn read_scalar(input: &[u8]) -> blst_scalar {
+    let mut scalar = blst_scalar::default();
+    unsafe { blst_scalar_from_bendian(&mut scalar, input[..SCALAR_LEN].as_ptr()) };
+    scalar
+}
+
+/// Reads G1 point, point at infinity is encoded as zeroes
+fn read_g1_point(input: &[u8], subgroup_check: bool) -> Result<blst_p1_affine, ExitCode> {
+    let point = blst_p1_affine {
+        x: read_fp(&input[..64])?,
+        y: read_fp(&input[64..128])?,
+    };
+    if input[..G1_POINT_LEN].iter().all(|v| *v == 0) {
+        return Ok(point);
+    }
+    if !unsafe { blst_p1_affine_on_curve(&point) } {
+        return Err(ExitCode::Bls12381InvalidPoint);
+    }
+    if subgroup_check && !unsafe { blst_p1_affine_in_g1(&point) } {
+        return Err(ExitCode::Bls12381InvalidPoint);
+    }
+    Ok(point)
+}
+
+fn read_g2_point(input: &[u8], subgroup_check: bool) -> Result<blst_p2_affine, ExitCode> {
+    let point = blst_p2_affine {
+        x: read_fp2(&input[..128])?,
+        y: read_fp2(&input[128..256])?,
+    };
+    if input[..G2_POINT_LEN].iter().all(|v| *v == 0) {
+        return Ok(point);
+    }
+    if !unsafe { blst_p2_affine_on_curve(&point) } {
+        return Err(ExitCode::Bls12381InvalidPoint);
+    }
+    if subgroup_check && !unsafe { blst_p2_affine_in_g2(&point) } {
+        return Err(ExitCode::Bls12381InvalidPoint);
+    }
+    Ok(point)
+}
+
+fn encode_g1_point(point: &blst_p1) -> [u8; G1_POINT_LEN] {
+    let mut output = [0u8; G1_POINT_LEN];
+    if unsafe { blst_p1_is_inf(point) } {
+        return output;
+    }
+    let mut affine = blst_p1_affine::default();
+    unsafe { blst_p1_to_affine(&mut affine, point) };
+    encode_fp(&affine.x, &mut output[..64]);
+    encode_fp(&affine.y, &mut output[64..]);
+    output
+}
+
+fn encode_g2_point(point: &blst_p2) -> [u8; G2_POINT_LEN] {
+    let mut output = [0u8; G2_POINT_LEN];
+    if unsafe { blst_p2_is_inf(point) } {
+        return output;
+    }
+    let mut affine = blst_p2_affine::default();
+    unsafe { blst_p2_to_affine(&mut affine, point) };
+    encode_fp(&affine.x.fp[0], &mut output[..64]);
+    encode_fp(&affine.x.fp[1], &mut output[64..128]);
+    encode_fp(&affine.y.fp[0], &mut output[128..192]);
+    encode_fp(&affine.y.fp[1], &mut output[192..]);
+    output
+}
+
+fn g1_from_affine(point: &blst_p1_affine) -> blst_p1 {
+    let mut result = blst_p1::default();
+    unsafe { blst_p1_from_affine(&mut result, point) };
+    result
+}
+
+fn g2_from_affine(point: &blst_p2_affine) -> blst_p2 {
+    let mut result = blst_p2::default();
+    unsafe { blst_p2_from_affine(&mut result, point) };
+    result
+}
+
+pub struct CryptoBls12381G1Add;
+
+impl CryptoBls12381G1Add {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        p1_offset: u32,
+        p2_offset: u32,
+        output128_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_BLS12381_G1_ADD.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let p1 = caller.read_memory(p1_offset, G1_POINT_LEN as u32)?;
+        let p2 = caller.read_memory(p2_offset, G1_POINT_LEN as u32)?;
+        match Self::fn_impl(p1, p2) {
+            Ok(output) => {
+                caller.write_memory(output128_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(p1: &[u8], p2: &[u8]) -> Result<[u8; G1_POINT_LEN], ExitCode> {
+        // EIP-2537 doesn't require subgroup check for addition
+        let p1 = g1_from_affine(&read_g1_point(p1, false)?);
+        let p2 = g1_from_affine(&read_g1_point(p2, false)?);
+        let mut result = blst_p1::default();
+        unsafe { blst_p1_add_or_double(&mut result, &p1, &p2) };
+        Ok(encode_g1_point(&result))
+    }
+}
+
+pub struct CryptoBls12381G1Msm;
+
+impl CryptoBls12381G1Msm {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        pairs_offset: u32,
+        pairs_len: u32,
+        output128_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = msm_fuel_cost(pairs_len, G1_MSM_ELEMENT_LEN, G1_MUL_FUEL_COST);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let pairs = caller.read_memory(pairs_offset, pairs_len)?;
+        match Self::fn_impl(pairs) {
+            Ok(output) => {
+                caller.write_memory(output128_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    /// Multi-scalar multiplication, single element input is a regular multiplication
+    pub fn fn_impl(pairs: &[u8]) -> Result<[u8; G1_POINT_LEN], ExitCode> {
+        if pairs.is_empty() || pairs.len() % G1_MSM_ELEMENT_LEN != 0 {
+            return Err(ExitCode::Bls12381InvalidInputLength);
+        }
+        let mut result = blst_p1::default();
+        for pair in pairs.chunks(G1_MSM_ELEMENT_LEN) {
+            let point = g1_from_affine(&read_g1_point(&pair[..G1_POINT_LEN], true)?);
+            let scalar = read_scalar(&pair[G1_POINT_LEN..]);
+            let mut product = blst_p1::default();
+            unsafe {
+                blst_p1_mult(&mut product, &point, scalar.b.as_ptr(), SCALAR_BITS);
+                let acc = result;
+                blst_p1_add_or_double(&mut result, &acc, &product);
+            }
+        }
+        Ok(encode_g1_point(&result))
+    }
+}
+
+pub struct CryptoBls12381G2Add;
+
+impl CryptoBls12381G2Add {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        p1_offset: u32,
+        p2_offset: u32,
+        output256_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_BLS12381_G2_ADD.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let p1 = caller.read_memory(p1_offset, G2_POINT_LEN as u32)?;
+        let p2 = caller.read_memory(p2_offset, G2_POINT_LEN as u32)?;
+        match Self::fn_impl(p1, p2) {
+            Ok(output) => {
+                caller.write_memory(output256_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(p1: &[u8], p2: &[u8]) -> Result<[u8; G2_POINT_LEN], ExitCode> {
+        let p1 = g2_from_affine(&read_g2_point(p1, false)?);
+        let p2 = g2_from_affine(&read_g2_point(p2, false)?);
+        let mut result = blst_p2::default();
+        unsafe { blst_p2_add_or_double(&mut result, &p1, &p2) };
+        Ok(encode_g2_point(&result))
+    }
+}
+
+pub struct CryptoBls12381G2Msm;
+
+impl CryptoBls12381G2Msm {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        pairs_offset: u32,
+        pairs_len: u32,
+        output256_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = msm_fuel_cost(pairs_len, G2_MSM_ELEMENT_LEN, G2_MUL_FUEL_COST);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let pairs = caller.read_memory(pairs_offset, pairs_len)?;
+        match Self::fn_impl(pairs) {
+            Ok(output) => {
+                caller.write_memory(output256_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(pairs: &[u8]) -> Result<[u8; G2_POINT_LEN], ExitCode> {
+        if pairs.is_empty() || pairs.len() % G2_MSM_ELEMENT_LEN != 0 {
+            return Err(ExitCode::Bls12381InvalidInputLength);
+        }
+        let mut result = blst_p2::default();
+        for pair in pairs.chunks(G2_MSM_ELEMENT_LEN) {
+            let point = g2_from_affine(&read_g2_point(&pair[..G2_POINT_LEN], true)?);
+            let scalar = read_scalar(&pair[G2_POINT_LEN..]);
+            let mut product = blst_p2::default();
+            unsafe {
+                blst_p2_mult(&mut product, &point, scalar.b.as_ptr(), SCALAR_BITS);
+                let acc = result;
+                blst_p2_add_or_double(&mut result, &acc, &product);
+            }
+        }
+        Ok(encode_g2_point(&result))
+    }
+}
+
+pub struct CryptoBls12381Pairing;
+
+impl CryptoBls12381Pairing {
+    pub fn fn_fuel_cost(pairs_len: u32) -> u64 {
+        PAIRING_BASE_FUEL_COST
+            + PAIRING_PER_PAIR_FUEL_COST * (pairs_len as usize / PAIR_ELEMENT_LEN) as u64
+    }
+
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        pairs_offset: u32,
+        pairs_len: u32,
+        output32_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = Self::fn_fuel_cost(pairs_len);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let pairs = caller.read_memory(pairs_offset, pairs_len)?;
+        match Self::fn_impl(pairs) {
+            Ok(success) => {
+                let mut output = [0u8; 32];
+                output[31] = success as u8;
+                caller.write_memory(output32_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(pairs: &[u8]) -> Result<bool, ExitCode> {
+        if pairs.is_empty() || pairs.len() % PAIR_ELEMENT_LEN != 0 {
+            return Err(ExitCode::Bls12381InvalidInputLength);
+        }
+        let mut acc: Option<blst_fp12> = None;
+        for pair in pairs.chunks(PAIR_ELEMENT_LEN) {
+            let p = read_g1_point(&pair[..G1_POINT_LEN], true)?;
+            let q = read_g2_point(&pair[G1_POINT_LEN..], true)?;
+            // pairs with point at infinity don't affect the result
+            if pair[..G1_POINT_LEN].iter().all(|v| *v == 0)
+                || pair[G1_POINT_LEN..].iter().all(|v| *v == 0)
+            {
+                continue;
+            }
+            let mut ml = blst_fp12::default();
+            unsafe { blst_miller_loop(&mut ml, &q, &p) };
+            acc = Some(match acc {
+                Some(acc) => {
+                    let mut result = blst_fp12::default();
+                    unsafe { blst_fp12_mul(&mut result, &acc, &ml) };
+                    result
+                }
+                None => ml,
+            });
+        }
+        let Some(acc) = acc else {
+            return Ok(true);
+        };
+        let mut result = blst_fp12::default();
+        unsafe { blst_final_exp(&mut result, &acc) };
+        Ok(unsafe { blst_fp12_is_one(&result) })
+    }
+}
+
+pub struct CryptoBls12381MapFpToG1;
+
+impl CryptoBls12381MapFpToG1 {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        fp64_offset: u32,
+        output128_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_BLS12381_MAP_FP_TO_G1.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let fp = caller.read_memory(fp64_offset, PADDED_FP_LEN as u32)?;
+        match Self::fn_impl(fp) {
+            Ok(output) => {
+                caller.write_memory(output128_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(fp: &[u8]) -> Result<[u8; G1_POINT_LEN], ExitCode> {
+        let fp = read_fp(fp)?;
+        let mut result = blst_p1::default();
+        unsafe { blst_map_to_g1(&mut result, &fp, core::ptr::null()) };
+        Ok(encode_g1_point(&result))
+    }
+}
+
+pub struct CryptoBls12381MapFp2ToG2;
+
+impl CryptoBls12381MapFp2ToG2 {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        fp2_128_offset: u32,
+        output256_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_BLS12381_MAP_FP2_TO_G2.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let fp2 = caller.read_memory(fp2_128_offset, 2 * PADDED_FP_LEN as u32)?;
+        match Self::fn_impl(fp2) {
+            Ok(output) => {
+                caller.write_memory(output256_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(fp2: &[u8]) -> Result<[u8; G2_POINT_LEN], ExitCode> {
+        let fp2 = read_fp2(fp2)?;
+        let mut result = blst_p2::default();
+        unsafe { blst_map_to_g2(&mut result, &fp2, core::ptr::null()) };
+        Ok(encode_g2_point(&result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction::crypto_bls12381::*;
+    use hex_literal::hex;
+
+    /// Generator of G1
+    const G1: [u8; 128] = hex!("0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1");
+    /// Generator of G2
+    const G2: [u8; 256] = hex!("00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be");
+
+    /// Order of the subgroup minus one
+    const R_MINUS_ONE: [u8; 32] =
+        hex!("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000");
+
+    fn scalar(value: u8) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        result[31] = value;
+        result
+    }
+
+    #[test]
+    fn test_g1_add_and_msm() {
+        let double = CryptoBls12381G1Add::fn_impl(&G1, &G1).unwrap();
+        let product = CryptoBls12381G1Msm::fn_impl(&[&G1[..], &scalar(2)].concat()).unwrap();
+        assert_eq!(double, product);
+        // G * 1 + G * 1 == G * 2
+        let msm =
+            CryptoBls12381G1Msm::fn_impl(&[&G1[..], &scalar(1), &G1[..], &scalar(1)].concat())
+                .unwrap();
+        assert_eq!(msm, double);
+        // adding point at infinity
+        assert_eq!(CryptoBls12381G1Add::fn_impl(&G1, &[0u8; 128]).unwrap(), G1);
+        // multiplication by zero
+        assert_eq!(
+            CryptoBls12381G1Msm::fn_impl(&[&G1[..], &scalar(0)].concat()).unwrap(),
+            [0u8; 128]
+        );
+        // point not on the curve
+        let mut invalid = G1;
+        invalid[127] ^= 1;
+        assert_eq!(
+            CryptoBls12381G1Add::fn_impl(&G1, &invalid).unwrap_err(),
+            ExitCode::Bls12381InvalidPoint
+        );
+        // non-zero padding
+        let mut invalid = G1;
+        invalid[0] = 1;
+        assert_eq!(
+            CryptoBls12381G1Add::fn_impl(&G1, &invalid).unwrap_err(),
+            ExitCode::Bls12381InvalidFieldElement
+        );
+        assert_eq!(
+            CryptoBls12381G1Msm::fn_impl(&G1).unwrap_err(),
+            ExitCode::Bls12381InvalidInputLength
+        );
+    }
+
+    #[test]
+    fn test_g2_add_and_msm() {
+        let double = CryptoBls12381G2Add::fn_impl(&G2, &G2).unwrap();
+        let product = CryptoBls12381G2Msm::fn_impl(&[&G2[..], &scalar(2)].concat()).unwrap();
+        assert_eq!(double, product);
+        assert_eq!(CryptoBls12381G2Add::fn_impl(&[0u8; 256], &G2).unwrap(), G2);
+    }
+
+    #[test]
+    fn test_fuel_cost() {
+        // the same as gas of the MUL, MSM and pairing precompiles
+        assert_eq!(msm_fuel_cost(0, G1_MSM_ELEMENT_LEN, G1_MUL_FUEL_COST), 0);
+        assert_eq!(
+            msm_fuel_cost(
+                G1_MSM_ELEMENT_LEN as u32,
+                G1_MSM_ELEMENT_LEN,
+                G1_MUL_FUEL_COST
+            ),
+            G1_MUL_FUEL_COST
+        );
+        assert_eq!(
+            msm_fuel_cost(
+                2 * G2_MSM_ELEMENT_LEN as u32,
+                G2_MSM_ELEMENT_LEN,
+                G2_MUL_FUEL_COST
+            ),
+            79_920
+        );
+        assert_eq!(
+            msm_fuel_cost(
+                200 * G1_MSM_ELEMENT_LEN as u32,
+                G1_MSM_ELEMENT_LEN,
+                G1_MUL_FUEL_COST
+            ),
+            200 * 174 * 12
+        );
+        assert_eq!(
+            CryptoBls12381Pairing::fn_fuel_cost(2 * PAIR_ELEMENT_LEN as u32),
+            151_000
+        );
+    }
+
+    #[test]
+    fn test_pairing() {
+        // multiplication by `r - 1` negates the point
+        let neg_g1 = CryptoBls12381G1Msm::fn_impl(&[&G1[..], &R_MINUS_ONE[..]].concat()).unwrap();
+        let g1_double = CryptoBls12381G1Add::fn_impl(&G1, &G1).unwrap();
+        let g2_double = CryptoBls12381G2Add::fn_impl(&G2, &G2).unwrap();
+        // e(2 * G1, G2) * e(-G1, 2 * G2) == 1
+        let input = [&g1_double[..], &G2[..], &neg_g1[..], &g2_double[..]].concat();
+        assert!(CryptoBls12381Pairing::fn_impl(&input).unwrap());
+        // e(G1, G2) * e(G1, 2 * G2) != 1
+        let input = [&G1[..], &G2[..], &G1[..], &g2_double[..]].concat();
+        assert!(!CryptoBls12381Pairing::fn_impl(&input).unwrap());
+        // pairs with point at infinity are skipped
+        assert!(CryptoBls12381Pairing::fn_impl(&[&[0u8; 128][..], &G2[..]].concat()).unwrap());
+        assert_eq!(
+            CryptoBls12381Pairing::fn_impl(&[]).unwrap_err(),
+            ExitCode::Bls12381InvalidInputLength
+        );
+    }
+
+    #[test]
+    fn test_map_to_curve() {
+        // mapped points are valid members of the subgroup, so they can be used in MSM
+        let g1 = CryptoBls12381MapFpToG1::fn_impl(&G1[..64]).unwrap();
+        assert!(CryptoBls12381G1Msm::fn_impl(&[&g1[..], &scalar(1)].concat()).is_ok());
+        let g2 = CryptoBls12381MapFp2ToG2::fn_impl(&G2[..128]).unwrap();
+        assert!(CryptoBls12381G2Msm::fn_impl(&[&g2[..], &scalar(1)].concat()).is_ok());
+        assert_eq!(
+            CryptoBls12381MapFpToG1::fn_impl(&[0xffu8; 64]).unwrap_err(),
+            ExitCode::Bls12381InvalidFieldElement
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::RuntimeContext;
+use bn::{AffineG1, AffineG2, Fq, Fq2, Fr, Group, Gt, G1, G2};
+use fluentbase_types::{ExitCode, IJournaledTrie, SysFuncIdx};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+/// Size of encoded G1 point (x, y)
+const G1_POINT_LEN: usize = 64;
+/// Size of encoded pairing element (G1 point and G2 point)
+const PAIR_ELEMENT_LEN: usize = 192;
+/// Gas costs of the pairing check according to EIP-1108
+const PAIR_BASE_FUEL_COST: u64 = 45_000;
+const PAIR_PER_POINT_FUEL_COST: u64 = 34_000;
+
+fn read_fq(input: &[u8]) -> Result<Fq, ExitCode> {
+    Fq::from_slice(&input[..32]).map_err(|_| ExitCode::Bn128FieldPointNotAMember)
+}
+
+fn new_g1_point(px: Fq, py: Fq) -> Result<G1, ExitCode> {
+    if px == Fq::zero() && py == Fq::zero() {
+        Ok(G1::zero())
+    } else {
+        AffineG1::new(px, py)
+            .map(Into::into)
+            .map_err(|_| ExitCode::Bn128AffineGFailedToCreate)
+    }
+}
+
+fn read_g1_point(input: &[u8]) -> Result<G1, ExitCode> {
+    let px = read_fq(&input[0..32])?;
+    let py = read_fq(&input[32..64])?;
+    new_g1_point(px, py)
+}
+
+fn encode_g1_point(point: G1) -> [u8; G1_POINT_LEN] {
+    let mut output = [0u8; G1_POINT_LEN];
+    // point at infinity is encoded as (0, 0)
+    if let Some(point) = AffineG1::from_jacobian(point) {
+        point.x().to_big_endian(&mut output[..32]).unwrap();
+        point.y().to_big_endian(&mut output[32..]).unwrap();
+    }
+    output
+}
+
+pub struct CryptoBn128Add;
+
+impl CryptoBn128Add {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        p1_offset: u32,
+        p2_offset: u32,
+        output64_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_BN128_ADD.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let p1 = caller.read_memory(p1_offset, 64)?;
+        let p2 = caller.read_memory(p2_offset, 64)?;
+        match Self::fn_impl(p1, p2) {
+            Ok(output) => {
+                caller.write_memory(output64_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(p1: &[u8], p2: &[u8]) -> Result<[u8; 64], ExitCode> {
+        let p1 = read_g1_point(p1)?;
+        let p2 = read_g1_point(p2)?;
+        Ok(encode_g1_point(p1 + p2))
+    }
+}
+
+pub struct CryptoBn128Mul;
+
+impl CryptoBn128Mul {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        p_offset: u32,
+        scalar32_offset: u32,
+        output64_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_BN128_MUL.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let p = caller.read_memory(p_offset, 64)?;
+        let scalar = caller.read_memory(scalar32_offset, 32)?;
+        match Self::fn_impl(p, scalar) {
+            Ok(output) => {
+                caller.write_memory(output64_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(p: &[u8], scalar: &[u8]) -> Result<[u8; 64], ExitCode> {
+        let p = read_g1_point(p)?;
+        // `Fr::from_slice` can only fail when the length is not 32
+        let fr = Fr::from_slice(&scalar[..32]).unwrap();
+        Ok(encode_g1_point(p * fr))
+    }
+}
+
+pub struct CryptoBn128Pairing;
+
+impl CryptoBn128Pairing {
+    pub fn fn_fuel_cost(pairs_len: u32) -> u64 {
+        PAIR_BASE_FUEL_COST
+            + PAIR_PER_POINT_FUEL_COST * (pairs_len as usize / PAIR_ELEMENT_LEN) as u64
+    }
+
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        pairs_offset: u32,
+        pairs_len: u32,
+        output32_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = Self::fn_fuel_cost(pairs_len);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let pairs = caller.read_memory(pairs_offset, pairs_len)?;
+        match Self::fn_impl(pairs) {
+            Ok(success) => {
+                let mut output = [0u8; 32];
+                output[31] = success as u8;
+                caller.write_memory(output32_offset, &output)?;
+                Ok(ExitCode::Ok.into_i32())
+            }
+            Err(err) => Ok(err.into_i32()),
+        }
+    }
+
+    pub fn fn_impl(pairs: &[u8]) -> Result<bool, ExitCode> {
+        if pairs.len() % PAIR_ELEMENT_LEN != 0 {
+            return Err(ExitCode::Bn128PairLength);
+        }
+        let mut mul = Gt::one();
+        for pair in pairs.chunks(PAIR_ELEMENT_LEN) {
+            let a = read_g1_point(&pair[0..64])?;
+            // G2 coordinates are encoded as (imaginary, real)
+            let bay = read_fq(&pair[64..96])?;
+            let bax = read_fq(&pair[96..128])?;
+            let bby = read_fq(&pair[128..160])?;
+            let bbx = read_fq(&pair[160..192])?;
+            let ba = Fq2::new(bax, bay);
+            let bb = Fq2::new(bbx, bby);
+            let b = if ba.is_zero() && bb.is_zero() {
+                G2::zero()
+            } else {
+                G2::from(AffineG2::new(ba, bb).map_err(|_| ExitCode::Bn128AffineGFailedToCreate)?)
+            };
+            mul = mul * bn::pairing(a, b);
+        }
+        Ok(mul == Gt::one())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::RuntimeContext;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use fluentbase_types::{ExitCode, IJournaledTrie, SysFuncIdx};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+pub struct CryptoEd25519Verify;
+
+impl CryptoEd25519Verify {
+    /// Message is hashed with SHA-512, so every word costs the same as in the SHA256 precompile
+    pub fn fn_fuel_cost(msg_len: u32) -> u64 {
+        SysFuncIdx::CRYPTO_ED25519_VERIFY.fuel_cost() as u64 + 12 * (msg_len as u64).div_ceil(32)
+    }
+
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        msg_offset: u32,
+        msg_len: u32,
+        sig64_offset: u32,
+        pk32_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = Self::fn_fuel_cost(msg_len);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let msg = caller.read_memory(msg_offset, msg_len)?;
+        let sig = caller.read_memory(sig64_offset, 64)?;
+        let pk = caller.read_memory(pk32_offset, 32)?;
+        Ok(Self::fn_impl(msg, sig, pk) as i32)
+    }
+
+    pub fn fn_impl(msg: &[u8], sig: &[u8], pk: &[u8]) -> bool {
+        let Ok(pk) = VerifyingKey::from_bytes(pk[..32].try_into().unwrap()) else {
+            return false;
+        };
+        let sig = Signature::from_bytes(sig[..64].try_into().unwrap());
+        pk.verify(msg, &sig).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction::crypto_ed25519_verify::CryptoEd25519Verify;
+    use hex_literal::hex;
+
+    // test 2 from RFC 8032
+    const PK: [u8; 32] = hex!("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c");
+    const MSG: [u8; 1] = hex!("72");
+    const SIG: [u8; 64] = hex!("92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00");
+
+    #[test]
+    fn test_ed25519_verify() {
+        assert!(CryptoEd25519Verify::fn_impl(&MSG, &SIG, &PK));
+        assert!(!CryptoEd25519Verify::fn_impl(&[0x73], &SIG, &PK));
+        let mut sig = SIG;
+        sig[0] ^= 1;
+        assert!(!CryptoEd25519Verify::fn_impl(&MSG, &sig, &PK));
+    }
+
+    #[test]
+    fn test_ed25519_fuel_cost() {
+        assert_eq!(CryptoEd25519Verify::fn_fuel_cost(0), 2_000);
+        assert_eq!(CryptoEd25519Verify::fn_fuel_cost(1), 2_012);
+        assert_eq!(
+            CryptoEd25519Verify::fn_fuel_cost(1024 * 1024),
+            2_000 + 12 * 32 * 1024
+        );
+    }
+}
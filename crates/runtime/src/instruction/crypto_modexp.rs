@@ -0,0 +1,88 @@
+use crate::RuntimeContext;
+use core::cmp::{max, min};
+use fluentbase_types::{ExitCode, IJournaledTrie};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+pub struct CryptoModexp;
+
+impl CryptoModexp {
+    /// Matches gas cost of the MODEXP precompile (EIP-2565), only the first 32 bytes of the
+    /// exponent are used to calculate the number of iterations
+    pub fn fn_fuel_cost(base_len: u32, exponent: &[u8], mod_len: u32) -> u64 {
+        let words = (max(base_len, mod_len) as u128).div_ceil(8);
+        let exp_head = &exponent[..min(exponent.len(), 32)];
+        let exp_head_bits = exp_head
+            .iter()
+            .position(|byte| *byte != 0)
+            .map(|i| (exp_head.len() - i) * 8 - exp_head[i].leading_zeros() as usize)
+            .unwrap_or_default();
+        let iteration_count = if exponent.len() <= 32 {
+            exp_head_bits.saturating_sub(1) as u128
+        } else {
+            8 * (exponent.len() as u128 - 32) + max(exp_head_bits, 1) as u128 - 1
+        };
+        let gas = words * words * max(iteration_count, 1) / 3;
+        max(200, min(gas, u64::MAX as u128) as u64)
+    }
+
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        base_offset: u32,
+        base_len: u32,
+        exp_offset: u32,
+        exp_len: u32,
+        mod_offset: u32,
+        mod_len: u32,
+        output_offset: u32,
+    ) -> Result<(), Trap> {
+        let exponent = caller.read_memory(exp_offset, exp_len)?;
+        let fuel_cost = Self::fn_fuel_cost(base_len, exponent, mod_len);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let base = caller.read_memory(base_offset, base_len)?;
+        let exponent = caller.read_memory(exp_offset, exp_len)?;
+        let modulus = caller.read_memory(mod_offset, mod_len)?;
+        let output = Self::fn_impl(base, exponent, modulus);
+        caller.write_memory(output_offset, &output)?;
+        Ok(())
+    }
+
+    /// Computes `base ^ exponent % modulus` over big-endian numbers, the result is left padded
+    /// to the modulus length.
+    pub fn fn_impl(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+        let result = aurora_engine_modexp::modexp(base, exponent, modulus);
+        if result.len() >= modulus.len() {
+            return result[..modulus.len()].to_vec();
+        }
+        let mut output = vec![0u8; modulus.len()];
+        output[modulus.len() - result.len()..].copy_from_slice(&result);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CryptoModexp;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_modexp_fuel_cost() {
+        // eth_tests_create2callPrecompiles_test0_berlin, the same as the precompile gas
+        let exponent = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e");
+        assert_eq!(CryptoModexp::fn_fuel_cost(1, &exponent, 32), 1_360);
+        // minimal cost
+        assert_eq!(CryptoModexp::fn_fuel_cost(1, &[0x05], 2), 200);
+        // long exponent, every byte after the first 32 adds 8 iterations
+        let mut exponent = [0u8; 64];
+        exponent[31] = 1;
+        assert_eq!(
+            CryptoModexp::fn_fuel_cost(512, &exponent, 512),
+            64 * 64 * 256 / 3
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::RuntimeContext;
-use fluentbase_poseidon::hash_with_domain;
+use fluentbase_poseidon::HashVersion;
 use fluentbase_types::{ExitCode, IJournaledTrie};
 use halo2curves::{bn256::Fr, group::ff::PrimeField};
 use rwasm::{core::Trap, Caller};
@@ -37,7 +37,8 @@ impl CryptoPoseidon2 {
         let fa = fr_from_bytes(fa)?;
         let fb = fr_from_bytes(fb)?;
         let fd = fr_from_bytes(fd)?;
-        let h2 = hash_with_domain(&[fa, fb], &fd);
+        // it's used to derive storage keys that are kept on the legacy scheme
+        let h2 = HashVersion::V1.hash_with_domain(&[fa, fb], &fd);
         Ok(h2.to_repr())
     }
 }
@@ -0,0 +1,70 @@
+use crate::RuntimeContext;
+use fluentbase_types::{ExitCode, IJournaledTrie, SysFuncIdx};
+use p256::{
+    ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+    EncodedPoint,
+};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+pub struct CryptoSecp256r1Verify;
+
+impl CryptoSecp256r1Verify {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        digest32_offset: u32,
+        sig64_offset: u32,
+        pk64_offset: u32,
+    ) -> Result<i32, Trap> {
+        let fuel_cost = SysFuncIdx::CRYPTO_SECP256R1_VERIFY.fuel_cost() as u64;
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let digest = caller.read_memory(digest32_offset, 32)?;
+        let sig = caller.read_memory(sig64_offset, 64)?;
+        let pk = caller.read_memory(pk64_offset, 64)?;
+        Ok(Self::fn_impl(digest, sig, pk) as i32)
+    }
+
+    /// Verifies ECDSA signature `(r, s)` of a prehashed message against an uncompressed public
+    /// key `(x, y)`, all values are 32-byte big-endian numbers (RIP-7212).
+    pub fn fn_impl(digest: &[u8], sig: &[u8], pk: &[u8]) -> bool {
+        let Ok(sig) = Signature::from_slice(sig) else {
+            return false;
+        };
+        let pk = EncodedPoint::from_affine_coordinates(pk[..32].into(), pk[32..64].into(), false);
+        let Ok(verifying_key) = VerifyingKey::from_encoded_point(&pk) else {
+            return false;
+        };
+        verifying_key.verify_prehash(digest, &sig).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction::crypto_secp256r1_verify::CryptoSecp256r1Verify;
+    use hex_literal::hex;
+
+    const DIGEST: [u8; 32] =
+        hex!("4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4d");
+    const SIG: [u8; 64] = hex!("a73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d60");
+    const PK: [u8; 64] = hex!("4aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff37618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e");
+
+    #[test]
+    fn test_secp256r1_verify() {
+        assert!(CryptoSecp256r1Verify::fn_impl(&DIGEST, &SIG, &PK));
+        // modified digest
+        let mut digest = DIGEST;
+        digest[0] ^= 1;
+        assert!(!CryptoSecp256r1Verify::fn_impl(&digest, &SIG, &PK));
+        // public key is not on the curve
+        let mut pk = PK;
+        pk[63] ^= 1;
+        assert!(!CryptoSecp256r1Verify::fn_impl(&DIGEST, &SIG, &pk));
+        // zero signature is invalid
+        assert!(!CryptoSecp256r1Verify::fn_impl(&DIGEST, &[0u8; 64], &PK));
+    }
+}
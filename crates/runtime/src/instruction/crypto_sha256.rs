@@ -0,0 +1,36 @@
+use crate::RuntimeContext;
+use fluentbase_types::{ExitCode, IJournaledTrie};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+use sha2::{Digest, Sha256};
+
+pub struct CryptoSha256;
+
+impl CryptoSha256 {
+    /// Matches gas cost of the SHA256 precompile: 60 + 12 per word
+    pub fn fn_fuel_cost(data_len: u32) -> u64 {
+        60 + 12 * (data_len as u64).div_ceil(32)
+    }
+
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        data_offset: u32,
+        data_len: u32,
+        output32_offset: u32,
+    ) -> Result<(), Trap> {
+        let fuel_cost = Self::fn_fuel_cost(data_len);
+        match caller.consume_fuel(fuel_cost) {
+            Ok(_) => {}
+            Err(err) => match err {
+                FuelError::OutOfFuel => return Err(ExitCode::OutOfFuel.into_trap()),
+                _ => {}
+            },
+        }
+        let data = caller.read_memory(data_offset, data_len)?;
+        caller.write_memory(output32_offset, &Self::fn_impl(data))?;
+        Ok(())
+    }
+
+    pub fn fn_impl(data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+}
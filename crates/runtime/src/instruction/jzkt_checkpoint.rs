@@ -16,6 +16,12 @@ impl JzktCheckpoint {
         context: &mut RuntimeContext<DB>,
     ) -> Result<JournalCheckpoint, ExitCode> {
         let checkpoint = context.jzkt().checkpoint();
+        // checkpoint must fit into u64 to be passed back into `_jzkt_rollback`
+        if checkpoint.depth() > JournalCheckpoint::MAX_DEPTH as usize {
+            return Err(ExitCode::CallDepthOverflow);
+        } else if checkpoint.logs() > JournalCheckpoint::MAX_LOGS as usize {
+            return Err(ExitCode::OutputOverflow);
+        }
         Ok(checkpoint)
     }
 }
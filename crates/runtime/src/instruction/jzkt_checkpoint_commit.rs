@@ -0,0 +1,18 @@
+use crate::RuntimeContext;
+use fluentbase_types::IJournaledTrie;
+use rwasm::{core::Trap, Caller};
+
+pub struct JzktCheckpointCommit;
+
+impl JzktCheckpointCommit {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+    ) -> Result<(), Trap> {
+        Self::fn_impl(caller.data_mut());
+        Ok(())
+    }
+
+    pub fn fn_impl<DB: IJournaledTrie>(ctx: &mut RuntimeContext<DB>) {
+        ctx.jzkt().checkpoint_commit();
+    }
+}
@@ -17,6 +17,6 @@ impl JzktRollback {
         ctx: &mut RuntimeContext<DB>,
         checkpoint: JournalCheckpoint,
     ) {
-        ctx.jzkt().rollback(checkpoint);
+        ctx.jzkt().checkpoint_revert(checkpoint);
     }
 }
@@ -0,0 +1,34 @@
+use crate::RuntimeContext;
+use fluentbase_types::{Address, IJournaledTrie, U256};
+use rwasm::{core::Trap, Caller};
+
+pub struct JzktUpdateStorageSlot;
+
+impl JzktUpdateStorageSlot {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        key32_ptr: u32,
+        address20_ptr: u32,
+        slot32_ptr: u32,
+    ) -> Result<(), Trap> {
+        let key = caller.read_memory(key32_ptr, 32)?.to_vec();
+        let address = caller.read_memory(address20_ptr, 20)?.to_vec();
+        let slot = caller.read_memory(slot32_ptr, 32)?.to_vec();
+        Self::fn_impl(caller.data_mut(), &key, &address, &slot);
+        Ok(())
+    }
+
+    /// Slot is encoded in little-endian, the same way as for the storage key derivation
+    pub fn fn_impl<DB: IJournaledTrie>(
+        ctx: &mut RuntimeContext<DB>,
+        key: &[u8],
+        address: &[u8],
+        slot: &[u8],
+    ) {
+        ctx.jzkt().update_storage_slot(
+            key.try_into().unwrap(),
+            &Address::from_slice(address),
+            &U256::from_le_slice(slot),
+        );
+    }
+}
@@ -1,14 +1,20 @@
-use crate::types::InMemoryTrieDb;
-use crate::zktrie::ZkTrieStateDb;
+use crate::snapshot::{SnapshotError, StateSnapshot};
+use crate::types::{InMemoryTrieDb, TrieDb};
+use crate::zktrie::{PruningStats, ZkTrieStateDb};
 use crate::TrieStorage;
 use core::mem::take;
-use fluentbase_poseidon::{hash_with_domain, Poseidon};
+use fluentbase_poseidon::{HashVersion, Poseidon};
 use fluentbase_types::{
-    Address, Bytes, ExitCode, IJournaledTrie, JournalCheckpoint, JournalEvent, JournalLog, B256,
+    Address, Bytes, ExitCode, IJournaledTrie, JournalCheckpoint, JournalEvent, JournalLog,
+    StateDiff, B256, U256,
 };
+use fluentbase_zktrie::Error as ZkTrieError;
 use halo2curves::bn256::Fr;
 use hashbrown::HashMap;
-use std::sync::{Arc, RwLock};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
 
 macro_rules! bytes32 {
     ($val:literal) => {
@@ -33,11 +39,26 @@ struct JournalTrieInner<DB: TrieStorage> {
     journal: Vec<JournalEvent>,
     root: [u8; 32],
     committed: usize,
+    checkpoints: Vec<JournalCheckpoint>,
+    storage_slots: HashMap<[u8; 32], (Address, U256)>,
 }
 
 impl<DB: TrieStorage> JournalTrieInner<DB> {
-    fn checkpoint(&self) -> JournalCheckpoint {
-        JournalCheckpoint(self.journal.len() as u32, self.logs.len() as u32)
+    fn checkpoint(&mut self) -> JournalCheckpoint {
+        let checkpoint = JournalCheckpoint(
+            self.journal.len() as u32,
+            self.logs.len() as u32,
+            self.checkpoints.len() as u32,
+        );
+        self.checkpoints.push(checkpoint);
+        checkpoint
+    }
+
+    fn checkpoint_commit(&mut self) {
+        // changes stay in the journal and become a part of the parent frame
+        self.checkpoints
+            .pop()
+            .expect("there is no checkpoint to commit");
     }
 
     fn get(&self, key: &[u8; 32]) -> Option<(Vec<[u8; 32]>, u32, bool)> {
@@ -91,24 +112,40 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
         return &self.journal;
     }
 
-    fn commit(&mut self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
+    fn commit(
+        &mut self,
+        mut diff: Option<&mut StateDiff>,
+    ) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
+        let mut entries = Vec::new();
         for (key, value) in self
             .journal
             .iter()
             .skip(self.committed)
             .map(|v| (*v.key(), v.preimage()))
-            .collect::<HashMap<_, _>>()
+            .collect::<BTreeMap<_, _>>()
             .into_iter()
         {
-            match value {
-                Some((value, flags)) => {
-                    self.storage.update(&key[..], flags, &value)?;
-                }
-                None => {
-                    self.storage.remove(&key[..])?;
+            if let Some(diff) = diff.as_mut() {
+                let before = self.storage.get(&key).map(|(values, _flags)| values);
+                let after = value.as_ref().map(|(values, _flags)| values);
+                if let Some((address, slot)) = self.storage_slots.get(&key) {
+                    diff.add_storage_change(
+                        *address,
+                        *slot,
+                        before.and_then(|v| v.first().copied()),
+                        after.and_then(|v| v.first().copied()),
+                    );
+                } else if key[..12] == [0u8; 12] {
+                    diff.add_account_change(
+                        Address::from_slice(&key[12..]),
+                        before.as_deref(),
+                        after.map(|v| v.as_slice()),
+                    )?;
                 }
             }
+            entries.push((key, value.map(|(value, flags)| (flags, value))));
         }
+        self.storage.update_batch(&entries)?;
         for (hash, preimage) in self.preimages.iter() {
             self.storage
                 .update_preimage(hash, Bytes::from(preimage.clone()));
@@ -118,11 +155,14 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
         self.state.clear();
         let logs = take(&mut self.logs);
         self.committed = 0;
+        self.checkpoints.clear();
+        self.storage_slots.clear();
+        self.storage.commit_root();
         self.root = self.storage.compute_root();
         Ok((self.root, logs))
     }
 
-    fn rollback(&mut self, checkpoint: JournalCheckpoint) {
+    fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
         if checkpoint.state() < self.committed {
             panic!("reverting already committed changes is not allowed")
         } else if checkpoint.state() > self.journal.len() {
@@ -131,6 +171,12 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
                 checkpoint.state(),
                 self.journal.len()
             )
+        } else if checkpoint.depth() > self.checkpoints.len() {
+            panic!(
+                "checkpoint depth overflow during rollback ({} > {})",
+                checkpoint.depth(),
+                self.checkpoints.len()
+            )
         }
         self.journal
             .iter()
@@ -146,6 +192,9 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
             });
         self.journal.truncate(checkpoint.state());
         self.logs.truncate(checkpoint.logs());
+        // drop reverted checkpoint and all checkpoints nested into it, parent checkpoints can be
+        // taken at the same journal position so only the depth identifies the reverted one
+        self.checkpoints.truncate(checkpoint.depth());
     }
 
     fn update_preimage(&mut self, key: &[u8; 32], field: u32, preimage: &[u8]) -> bool {
@@ -218,6 +267,8 @@ impl<DB: TrieStorage> JournaledTrie<DB> {
                 journal: Vec::new(),
                 root,
                 committed: 0,
+                checkpoints: Vec::new(),
+                storage_slots: HashMap::new(),
             })),
         }
     }
@@ -240,21 +291,44 @@ impl<DB: TrieStorage> JournaledTrie<DB> {
         let val1 = Fr::from_bytes(&bytes32).unwrap();
         bytes32[0..16].copy_from_slice(&val[16..]);
         let val2 = Fr::from_bytes(&bytes32).unwrap();
-        hash_with_domain(&[val1, val2], &Self::DOMAIN)
+        // storage keys are kept on the legacy scheme, so existing slots keep their keys
+        HashVersion::V1.hash_with_domain(&[val1, val2], &Self::DOMAIN)
     }
 
     pub fn storage_key(address: &Address, slot: &[u8; 32]) -> [u8; 32] {
         // storage key is `p(address, p(slot_0, slot_1, d), d)`
         let address = Fr::from_bytes(&address.into_word()).unwrap();
         let slot = Self::compress_value(slot);
-        let key = hash_with_domain(&[address, slot], &Self::DOMAIN);
+        let key = HashVersion::V1.hash_with_domain(&[address, slot], &Self::DOMAIN);
         key.to_bytes()
     }
 }
 
+impl<DB: TrieDb> JournaledTrie<ZkTrieStateDb<DB>> {
+    /// Exports committed state, changes that are still in the journal are not included
+    pub fn export_snapshot(&self) -> Result<StateSnapshot, SnapshotError> {
+        self.inner.read().unwrap().storage.export_snapshot()
+    }
+
+    pub fn import_snapshot(storage: DB, snapshot: &StateSnapshot) -> Result<Self, SnapshotError> {
+        let storage = ZkTrieStateDb::import_snapshot(storage, snapshot)?;
+        Ok(Self::new(storage))
+    }
+
+    /// Removes storage nodes that are unreachable from the committed roots kept by the storage,
+    /// pruning must be enabled with `ZkTrieStateDb::enable_pruning`
+    pub fn prune(&self) -> Result<PruningStats, ZkTrieError> {
+        self.inner.write().unwrap().storage.prune()
+    }
+}
+
 impl<DB: TrieStorage> IJournaledTrie for JournaledTrie<DB> {
     fn checkpoint(&self) -> JournalCheckpoint {
-        self.inner.read().unwrap().checkpoint()
+        self.inner.write().unwrap().checkpoint()
+    }
+
+    fn checkpoint_commit(&self) {
+        self.inner.write().unwrap().checkpoint_commit()
     }
 
     fn get(&self, key: &[u8; 32]) -> Option<(Vec<[u8; 32]>, u32, bool)> {
@@ -278,11 +352,25 @@ impl<DB: TrieStorage> IJournaledTrie for JournaledTrie<DB> {
     }
 
     fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
-        self.inner.write().unwrap().commit()
+        self.inner.write().unwrap().commit(None)
     }
 
-    fn rollback(&self, checkpoint: JournalCheckpoint) {
-        self.inner.write().unwrap().rollback(checkpoint)
+    fn commit_with_diff(&self) -> Result<([u8; 32], Vec<JournalLog>, StateDiff), ExitCode> {
+        let mut diff = StateDiff::default();
+        let (root, logs) = self.inner.write().unwrap().commit(Some(&mut diff))?;
+        Ok((root, logs, diff))
+    }
+
+    fn checkpoint_revert(&self, checkpoint: JournalCheckpoint) {
+        self.inner.write().unwrap().checkpoint_revert(checkpoint)
+    }
+
+    fn update_storage_slot(&self, key: &[u8; 32], address: &Address, slot: &U256) {
+        self.inner
+            .write()
+            .unwrap()
+            .storage_slots
+            .insert(*key, (*address, *slot));
     }
 
     fn update_preimage(&self, key: &[u8; 32], field: u32, preimage: &[u8]) -> bool {
@@ -314,7 +402,7 @@ mod tests {
         TrieStorage,
     };
     use fluentbase_poseidon::poseidon_hash;
-    use fluentbase_types::JournalCheckpoint;
+    use fluentbase_types::{address, ExitCode, JournalCheckpoint, U256};
 
     fn calc_trie_root(values: Vec<([u8; 32], Vec<[u8; 32]>, u32)>) -> [u8; 32] {
         let db = InMemoryTrieDb::default();
@@ -327,18 +415,20 @@ mod tests {
 
     #[test]
     fn test_journal_u64() {
-        let test_checkpoint = |a: u32, b: u32| {
-            let jc = JournalCheckpoint(a, b);
+        let test_checkpoint = |a: u32, b: u32, c: u32| {
+            let jc = JournalCheckpoint(a, b, c);
             assert_eq!(JournalCheckpoint::from_u64(jc.to_u64()), jc);
         };
-        test_checkpoint(100, 0);
-        test_checkpoint(0, 100);
-        test_checkpoint(0xffffffff, 0x7fffffff);
-        test_checkpoint(0x7fffffff, 0xffffffff);
-        test_checkpoint(0xffffffff, 0xffffffff);
-        test_checkpoint(0xffffffff, 0);
-        test_checkpoint(0, 0xffffffff);
-        test_checkpoint(12312312, 74492);
+        test_checkpoint(100, 0, 0);
+        test_checkpoint(0, 100, 0);
+        test_checkpoint(0, 0, 100);
+        test_checkpoint(0xffffffff, 0x7ffff, 0x7ff);
+        test_checkpoint(0x7fffffff, 0xfffff, 0xfff);
+        test_checkpoint(0xffffffff, 0xfffff, 0xfff);
+        test_checkpoint(0xffffffff, 0, 0);
+        test_checkpoint(0, JournalCheckpoint::MAX_LOGS, 0);
+        test_checkpoint(0, 0, JournalCheckpoint::MAX_DEPTH);
+        test_checkpoint(12312312, 74492, 1024);
     }
 
     #[test]
@@ -413,7 +503,7 @@ mod tests {
         // add third key to the existing trie and rollback
         let checkpoint = journal.checkpoint();
         journal.update(&bytes32!("key3"), &vec![bytes32!("val3")], 0);
-        journal.rollback(checkpoint);
+        journal.checkpoint_revert(checkpoint);
         assert_eq!(journal.inner.read().unwrap().state.len(), 0);
         assert_eq!(
             journal.compute_root(),
@@ -425,7 +515,7 @@ mod tests {
         // modify the same key and rollback
         let checkpoint = journal.checkpoint();
         journal.update(&bytes32!("key2"), &vec![bytes32!("Hello, World")], 0);
-        journal.rollback(checkpoint);
+        journal.checkpoint_revert(checkpoint);
         assert_eq!(journal.inner.read().unwrap().state.len(), 0);
         assert_eq!(
             journal.compute_root(),
@@ -436,6 +526,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nested_checkpoints() {
+        let db = InMemoryTrieDb::default();
+        let zktrie = ZkTrieStateDb::new_empty(db);
+        let journal = JournaledTrie::new(zktrie);
+        journal.update(&bytes32!("key1"), &vec![bytes32!("val1")], 0);
+        let outer = journal.checkpoint();
+        journal.update(&bytes32!("key2"), &vec![bytes32!("val2")], 0);
+        // inner changes are merged into the outer frame after commit
+        journal.checkpoint();
+        journal.update(&bytes32!("key3"), &vec![bytes32!("val3")], 0);
+        journal.checkpoint_commit();
+        assert_eq!(
+            journal.get(&bytes32!("key3")),
+            Some((vec![bytes32!("val3")], 0, false))
+        );
+        // reverted inner frame doesn't affect the outer one
+        let inner = journal.checkpoint();
+        journal.update(&bytes32!("key2"), &vec![bytes32!("val4")], 0);
+        journal.checkpoint_revert(inner);
+        assert_eq!(
+            journal.get(&bytes32!("key2")),
+            Some((vec![bytes32!("val2")], 0, false))
+        );
+        assert_eq!(journal.inner.read().unwrap().checkpoints.len(), 1);
+        // outer revert discards changes of the committed inner frame too
+        journal.checkpoint_revert(outer);
+        assert_eq!(journal.get(&bytes32!("key2")), None);
+        assert_eq!(journal.get(&bytes32!("key3")), None);
+        assert!(journal.inner.read().unwrap().checkpoints.is_empty());
+        journal.commit().unwrap();
+        assert_eq!(
+            journal.compute_root(),
+            calc_trie_root(vec![(bytes32!("key1"), vec![bytes32!("val1")], 0)])
+        );
+    }
+
+    #[test]
+    fn test_revert_inner_checkpoint_at_same_position() {
+        let db = InMemoryTrieDb::default();
+        let zktrie = ZkTrieStateDb::new_empty(db);
+        let journal = JournaledTrie::new(zktrie);
+        // both checkpoints are taken at the same journal position
+        let outer = journal.checkpoint();
+        let inner = journal.checkpoint();
+        assert_eq!(outer.state(), inner.state());
+        journal.update(&bytes32!("key1"), &vec![bytes32!("val1")], 0);
+        // reverting inner checkpoint keeps the outer one
+        journal.checkpoint_revert(inner);
+        assert_eq!(journal.inner.read().unwrap().checkpoints, vec![outer]);
+        journal.update(&bytes32!("key2"), &vec![bytes32!("val2")], 0);
+        journal.checkpoint_commit();
+        assert!(journal.inner.read().unwrap().checkpoints.is_empty());
+        journal.commit().unwrap();
+        assert_eq!(
+            journal.compute_root(),
+            calc_trie_root(vec![(bytes32!("key2"), vec![bytes32!("val2")], 0)])
+        );
+    }
+
+    #[test]
+    fn test_commit_with_diff() {
+        let db = InMemoryTrieDb::default();
+        let zktrie = ZkTrieStateDb::new_empty(db);
+        let journal = JournaledTrie::new(zktrie);
+        let address1 = address!("0000000000000000000000000000000000000001");
+        let address2 = address!("0000000000000000000000000000000000000002");
+        let account_fields = |balance: u64, nonce: u64| {
+            let mut fields = vec![[0u8; 32]; 6];
+            fields[0] = U256::from(balance).to_le_bytes();
+            fields[1][..8].copy_from_slice(&nonce.to_le_bytes());
+            fields
+        };
+        let slot = U256::from(1);
+        let storage_key = JournaledTrie::<ZkTrieStateDb<InMemoryTrieDb>>::storage_key(
+            &address1,
+            &slot.to_le_bytes(),
+        );
+        journal.update(&address1.into_word(), &account_fields(100, 0), 1);
+        journal.update(&address2.into_word(), &account_fields(0, 1), 1);
+        journal.update_storage_slot(&storage_key, &address1, &slot);
+        journal.update(&storage_key, &vec![bytes32!("val1")], 0);
+        let (_, _, diff) = journal.commit_with_diff().unwrap();
+        assert_eq!(diff.accounts.len(), 2);
+        assert_eq!(diff.created_accounts().count(), 2);
+        assert_eq!(diff.storage.len(), 1);
+        assert_eq!(diff.storage[0].address, address1);
+        assert_eq!(diff.storage[0].slot, slot);
+        assert_eq!(diff.storage[0].before, None);
+        assert_eq!(diff.storage[0].after, Some(bytes32!("val1")));
+        // transfer balance, remove second account and rewrite storage with the same value
+        journal.update(&address1.into_word(), &account_fields(50, 1), 1);
+        journal.update(&address1.into_word(), &account_fields(40, 1), 1);
+        journal.remove(&address2.into_word());
+        journal.update_storage_slot(&storage_key, &address1, &slot);
+        journal.update(&storage_key, &vec![bytes32!("val1")], 0);
+        let (_, _, diff) = journal.commit_with_diff().unwrap();
+        assert!(diff.storage.is_empty());
+        assert_eq!(diff.accounts.len(), 2);
+        let account1 = &diff.accounts[0];
+        assert_eq!(account1.address, address1);
+        assert_eq!(account1.before.as_ref().unwrap().balance, U256::from(100));
+        assert_eq!(account1.after.as_ref().unwrap().balance, U256::from(40));
+        assert_eq!(account1.after.as_ref().unwrap().nonce, 1);
+        let removed = diff.removed_accounts().collect::<Vec<_>>();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].address, address2);
+        assert_eq!(removed[0].before.as_ref().unwrap().nonce, 1);
+        // leaf stored under the account key must have account fields
+        journal.update(&address1.into_word(), &vec![bytes32!("val1")], 1);
+        assert!(matches!(
+            journal.commit_with_diff(),
+            Err(ExitCode::PersistentStorageError)
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let db = InMemoryTrieDb::default();
+        let zktrie = ZkTrieStateDb::new_empty(db);
+        let journal = JournaledTrie::new(zktrie);
+        journal.update(&bytes32!("key1"), &vec![bytes32!("val1")], 0);
+        journal.update(&bytes32!("key2"), &vec![bytes32!("val2")], 1);
+        let (root, _) = journal.commit().unwrap();
+        // uncommitted changes are not exported
+        journal.update(&bytes32!("key3"), &vec![bytes32!("val3")], 0);
+        let snapshot = journal.export_snapshot().unwrap();
+        assert_eq!(snapshot.root, root);
+        assert_eq!(snapshot.leaves.len(), 2);
+        let journal2 =
+            JournaledTrie::import_snapshot(InMemoryTrieDb::default(), &snapshot).unwrap();
+        assert_eq!(journal2.compute_root(), root);
+        assert_eq!(
+            journal2.get(&bytes32!("key2")),
+            Some((vec![bytes32!("val2")], 1, true))
+        );
+    }
+
     #[test]
     fn test_rollback_to_empty() {
         let db = InMemoryTrieDb::default();
@@ -444,13 +672,13 @@ mod tests {
         let checkpoint = journal.checkpoint();
         journal.update(&bytes32!("key1"), &vec![bytes32!("val1")], 0);
         journal.update(&bytes32!("key2"), &vec![bytes32!("val2")], 1);
-        journal.rollback(checkpoint);
+        journal.checkpoint_revert(checkpoint);
         assert_eq!(journal.compute_root(), calc_trie_root(vec![]));
         assert_eq!(journal.inner.read().unwrap().state.len(), 0);
         let checkpoint = journal.checkpoint();
         journal.update(&bytes32!("key3"), &vec![bytes32!("val3")], 0);
         journal.update(&bytes32!("key4"), &vec![bytes32!("val4")], 1);
-        journal.rollback(checkpoint);
+        journal.checkpoint_revert(checkpoint);
         assert_eq!(journal.compute_root(), calc_trie_root(vec![]));
         assert_eq!(journal.inner.read().unwrap().state.len(), 0);
     }
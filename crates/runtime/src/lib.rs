@@ -16,6 +16,7 @@ mod journal;
 pub use journal::*;
 
 pub mod mptrie;
+pub mod snapshot;
 #[cfg(test)]
 mod tests;
 pub mod types;
@@ -348,7 +348,30 @@ impl<DB: IJournaledTrie> Runtime<DB> {
         Self { store, linker }
     }
 
+    /// Executes the bytecode, the top-level call is a transaction boundary: its state changes are
+    /// committed if it succeeds and reverted otherwise, nested calls (`_sys_exec_hash`) are merged
+    /// into their parent checkpoint by the core contracts
     pub fn call(&mut self) -> Result<ExecutionResult, RuntimeError> {
+        let checkpoint = match self.store.data().jzkt.as_ref() {
+            Some(jzkt) if self.store.data().depth == 0 => Some(jzkt.checkpoint()),
+            _ => None,
+        };
+        let mut result = self.execute();
+        if let (Some(checkpoint), Some(jzkt)) = (checkpoint, self.store.data().jzkt.as_ref()) {
+            match &mut result {
+                Ok(execution_result) if execution_result.exit_code == ExitCode::Ok.into_i32() => {
+                    jzkt.checkpoint_commit();
+                    if let Err(exit_code) = jzkt.commit() {
+                        execution_result.exit_code = exit_code.into_i32();
+                    }
+                }
+                _ => jzkt.checkpoint_revert(checkpoint),
+            }
+        }
+        result
+    }
+
+    fn execute(&mut self) -> Result<ExecutionResult, RuntimeError> {
         let instance = CACHING_RUNTIME.with_borrow_mut(|caching_runtime| {
             let bytecode_repr = take(&mut self.store.data_mut().bytecode);
 
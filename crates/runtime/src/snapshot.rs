@@ -0,0 +1,264 @@
+use fluentbase_poseidon::migrate_hash;
+use fluentbase_types::{
+    Bytes, ExitCode, JZKT_ACCOUNT_FIELDS_COUNT, JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
+    LEGACY_POSEIDON_EMPTY, POSEIDON_EMPTY,
+};
+use fluentbase_zktrie::Error as ZkTrieError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Magic prefix of the binary snapshot format
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"FBSS";
+/// Latest supported version of the snapshot format (both binary and JSON)
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    MalformedData,
+    MissingKeyPreimage([u8; 32]),
+    RootMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+    TrieNotOpened,
+    Trie(ZkTrieError),
+    Storage(ExitCode),
+    Json(String),
+}
+
+impl From<ZkTrieError> for SnapshotError {
+    fn from(value: ZkTrieError) -> Self {
+        Self::Trie(value)
+    }
+}
+
+impl From<ExitCode> for SnapshotError {
+    fn from(value: ExitCode) -> Self {
+        Self::Storage(value)
+    }
+}
+
+/// Trie leaf (an account or a storage slot) with its original (not hashed) key
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotLeaf {
+    pub key: [u8; 32],
+    pub flags: u32,
+    pub values: Vec<[u8; 32]>,
+}
+
+/// Full state dump that contains all trie leaves, preimages of leaf values (like bytecode) and
+/// the state root these leaves must produce.
+///
+/// Binary format (all integers are big-endian):
+/// - magic (4 bytes) and version (4 bytes)
+/// - root (32 bytes)
+/// - leaves count (4 bytes), each leaf is key (32 bytes), flags (4 bytes), values count (4 bytes)
+///   and values (32 bytes each)
+/// - preimages count (4 bytes), each preimage is hash (32 bytes), length (4 bytes) and bytes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateSnapshot {
+    pub root: [u8; 32],
+    pub leaves: Vec<SnapshotLeaf>,
+    pub preimages: BTreeMap<[u8; 32], Bytes>,
+}
+
+struct SnapshotReader<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> SnapshotReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.buffer.len() < len {
+            return Err(SnapshotError::MalformedData);
+        }
+        let (result, rest) = self.buffer.split_at(len);
+        self.buffer = rest;
+        Ok(result)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, SnapshotError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_bytes32(&mut self) -> Result<[u8; 32], SnapshotError> {
+        let bytes = self.read_bytes(32)?;
+        Ok(bytes.try_into().unwrap())
+    }
+}
+
+impl StateSnapshot {
+    pub fn new(root: [u8; 32]) -> Self {
+        Self {
+            root,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces rwasm code hashes of accounts that are computed with the legacy Poseidon scheme
+    /// by the latest ones and moves bytecode preimages to the new hashes. Empty code has no
+    /// preimage, so `LEGACY_POSEIDON_EMPTY` is replaced by `POSEIDON_EMPTY` explicitly, other
+    /// accounts without bytecode preimage are skipped. Returns the amount of migrated accounts.
+    pub fn migrate_code_hashes(&mut self) -> usize {
+        let mut migrated = 0;
+        for leaf in self.leaves.iter_mut() {
+            if leaf.values.len() != JZKT_ACCOUNT_FIELDS_COUNT as usize {
+                continue;
+            }
+            let code_hash = &mut leaf.values[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize];
+            if *code_hash == LEGACY_POSEIDON_EMPTY.0 {
+                *code_hash = POSEIDON_EMPTY.0;
+                migrated += 1;
+                continue;
+            }
+            let Some(bytecode) = self.preimages.get(&*code_hash) else {
+                continue;
+            };
+            let Some(new_code_hash) = migrate_hash(bytecode, code_hash) else {
+                continue;
+            };
+            // the same bytecode can be shared by several accounts
+            let bytecode = bytecode.clone();
+            self.preimages.insert(new_code_hash, bytecode);
+            *code_hash = new_code_hash;
+            migrated += 1;
+        }
+        // remove preimages of the legacy hashes that aren't referenced anymore
+        let referenced = self
+            .leaves
+            .iter()
+            .flat_map(|leaf| leaf.values.iter())
+            .collect::<BTreeSet<_>>();
+        self.preimages.retain(|hash, preimage| {
+            referenced.contains(hash) || migrate_hash(preimage, hash).is_none()
+        });
+        migrated
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&SNAPSHOT_MAGIC);
+        result.extend_from_slice(&SNAPSHOT_VERSION.to_be_bytes());
+        result.extend_from_slice(&self.root);
+        result.extend_from_slice(&(self.leaves.len() as u32).to_be_bytes());
+        for leaf in self.leaves.iter() {
+            result.extend_from_slice(&leaf.key);
+            result.extend_from_slice(&leaf.flags.to_be_bytes());
+            result.extend_from_slice(&(leaf.values.len() as u32).to_be_bytes());
+            leaf.values
+                .iter()
+                .for_each(|value| result.extend_from_slice(value));
+        }
+        result.extend_from_slice(&(self.preimages.len() as u32).to_be_bytes());
+        for (hash, preimage) in self.preimages.iter() {
+            result.extend_from_slice(hash);
+            result.extend_from_slice(&(preimage.len() as u32).to_be_bytes());
+            result.extend_from_slice(preimage);
+        }
+        result
+    }
+
+    pub fn from_bytes(buffer: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = SnapshotReader { buffer };
+        if reader.read_bytes(4)? != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let version = reader.read_u32()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let mut snapshot = Self::new(reader.read_bytes32()?);
+        let leaves_count = reader.read_u32()?;
+        for _ in 0..leaves_count {
+            let key = reader.read_bytes32()?;
+            let flags = reader.read_u32()?;
+            let values_count = reader.read_u32()?;
+            let values = (0..values_count)
+                .map(|_| reader.read_bytes32())
+                .collect::<Result<Vec<_>, _>>()?;
+            snapshot.leaves.push(SnapshotLeaf { key, flags, values });
+        }
+        let preimages_count = reader.read_u32()?;
+        for _ in 0..preimages_count {
+            let hash = reader.read_bytes32()?;
+            let len = reader.read_u32()? as usize;
+            let preimage = Bytes::copy_from_slice(reader.read_bytes(len)?);
+            snapshot.preimages.insert(hash, preimage);
+        }
+        if !reader.buffer.is_empty() {
+            return Err(SnapshotError::MalformedData);
+        }
+        Ok(snapshot)
+    }
+
+    pub fn to_json(&self) -> String {
+        let json = JsonSnapshot {
+            version: SNAPSHOT_VERSION,
+            root: hex::encode(self.root),
+            leaves: self
+                .leaves
+                .iter()
+                .map(|leaf| JsonSnapshotLeaf {
+                    key: hex::encode(leaf.key),
+                    flags: leaf.flags,
+                    values: leaf.values.iter().map(hex::encode).collect(),
+                })
+                .collect(),
+            preimages: self
+                .preimages
+                .iter()
+                .map(|(hash, preimage)| (hex::encode(hash), hex::encode(preimage)))
+                .collect(),
+        };
+        serde_json::to_string_pretty(&json).expect("failed to serialize snapshot")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let json = serde_json::from_str::<JsonSnapshot>(json)
+            .map_err(|err| SnapshotError::Json(err.to_string()))?;
+        if json.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(json.version));
+        }
+        let decode_bytes32 = |value: &str| -> Result<[u8; 32], SnapshotError> {
+            let mut result = [0u8; 32];
+            hex::decode_to_slice(value, &mut result).map_err(|_| SnapshotError::MalformedData)?;
+            Ok(result)
+        };
+        let mut snapshot = Self::new(decode_bytes32(&json.root)?);
+        for leaf in json.leaves.iter() {
+            snapshot.leaves.push(SnapshotLeaf {
+                key: decode_bytes32(&leaf.key)?,
+                flags: leaf.flags,
+                values: leaf
+                    .values
+                    .iter()
+                    .map(|value| decode_bytes32(value))
+                    .collect::<Result<Vec<_>, _>>()?,
+            });
+        }
+        for (hash, preimage) in json.preimages.iter() {
+            let preimage = hex::decode(preimage).map_err(|_| SnapshotError::MalformedData)?;
+            snapshot
+                .preimages
+                .insert(decode_bytes32(hash)?, preimage.into());
+        }
+        Ok(snapshot)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonSnapshotLeaf {
+    key: String,
+    flags: u32,
+    values: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonSnapshot {
+    version: u32,
+    root: String,
+    leaves: Vec<JsonSnapshotLeaf>,
+    preimages: BTreeMap<String, String>,
+}
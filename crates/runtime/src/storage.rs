@@ -16,6 +16,25 @@ pub trait TrieStorage {
 
     fn remove(&mut self, key: &[u8]) -> Result<(), ExitCode>;
 
+    /// Applies updates (`Some`) and removals (`None`) at once, result must be the same as for
+    /// sequential calls of `update` and `remove`
+    fn update_batch(
+        &mut self,
+        entries: &[([u8; 32], Option<(u32, Vec<[u8; 32]>)>)],
+    ) -> Result<(), ExitCode> {
+        for (key, value) in entries.iter() {
+            match value {
+                Some((flags, value)) => self.update(key, *flags, value)?,
+                None => self.remove(key)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Remembers current root as a committed one, storages with pruning keep nodes of the
+    /// latest committed roots
+    fn commit_root(&mut self) {}
+
     fn proof(&self, key: &[u8; 32]) -> Option<Vec<Vec<u8>>>;
 
     fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes>;
@@ -1,6 +1,6 @@
 use crate::{runtime::Runtime, DefaultEmptyRuntimeDatabase, RuntimeContext};
 use fluentbase_types::SysFuncIdx::SYS_STATE;
-use fluentbase_types::{create_sovereign_import_linker, STATE_DEPLOY, STATE_MAIN};
+use fluentbase_types::{create_sovereign_import_linker, ExitCode, STATE_DEPLOY, STATE_MAIN};
 use hex_literal::hex;
 use rwasm::engine::bytecode::Instruction;
 use rwasm::engine::{RwasmConfig, StateRouterConfig};
@@ -122,3 +122,123 @@ fn test_keccak256() {
         execution_result.output.as_slice()
     );
 }
+
+fn run_with_fuel_limit(wat: &str, fuel_limit: u64) -> i32 {
+    let rwasm_binary = wat2rwasm(wat);
+    let ctx = RuntimeContext::new(rwasm_binary).with_fuel_limit(fuel_limit);
+    Runtime::<DefaultEmptyRuntimeDatabase>::run_with_context(ctx)
+        .unwrap()
+        .exit_code
+}
+
+#[test]
+fn test_crypto_host_functions_charge_fuel() {
+    // blake2f with `rounds = u32::MAX`
+    let exit_code = run_with_fuel_limit(
+        r#"
+(module
+  (import "fluentbase_v1alpha" "_crypto_blake2f" (func $blake2f (param i32 i32 i32 i32 i32)))
+  (func $main
+    i32.const -1
+    i32.const 0
+    i32.const 64
+    i32.const 192
+    i32.const 0
+    call $blake2f)
+  (memory (;0;) 1)
+  (export "main" (func $main)))
+    "#,
+        1_000_000,
+    );
+    assert_eq!(exit_code, ExitCode::OutOfFuel.into_i32());
+    // modexp with 4 KiB operands and 64-byte exponent
+    let exit_code = run_with_fuel_limit(
+        r#"
+(module
+  (import "fluentbase_v1alpha" "_crypto_modexp" (func $modexp (param i32 i32 i32 i32 i32 i32 i32)))
+  (func $main
+    i32.const 0
+    i32.const 4096
+    i32.const 0
+    i32.const 64
+    i32.const 0
+    i32.const 4096
+    i32.const 8192
+    call $modexp)
+  (memory (;0;) 1)
+  (export "main" (func $main)))
+    "#,
+        1_000_000,
+    );
+    assert_eq!(exit_code, ExitCode::OutOfFuel.into_i32());
+    // sha256 of 6 MB
+    let exit_code = run_with_fuel_limit(
+        r#"
+(module
+  (import "fluentbase_v1alpha" "_crypto_sha256" (func $sha256 (param i32 i32 i32)))
+  (func $main
+    i32.const 0
+    i32.const 6000000
+    i32.const 0
+    call $sha256)
+  (memory (;0;) 100)
+  (export "main" (func $main)))
+    "#,
+        1_000_000,
+    );
+    assert_eq!(exit_code, ExitCode::OutOfFuel.into_i32());
+    // the same call fits into the limit with a small input
+    let exit_code = run_with_fuel_limit(
+        r#"
+(module
+  (import "fluentbase_v1alpha" "_crypto_sha256" (func $sha256 (param i32 i32 i32)))
+  (func $main
+    i32.const 0
+    i32.const 64
+    i32.const 0
+    call $sha256)
+  (memory (;0;) 1)
+  (export "main" (func $main)))
+    "#,
+        1_000_000,
+    );
+    assert_eq!(exit_code, ExitCode::Ok.into_i32());
+}
+
+#[test]
+fn test_bls12381_host_functions_charge_fuel() {
+    // pairing check of 100 pairs costs 4.365M
+    let exit_code = run_with_fuel_limit(
+        r#"
+(module
+  (import "fluentbase_v1alpha" "_crypto_bls12381_pairing" (func $pairing (param i32 i32 i32) (result i32)))
+  (func $main
+    i32.const 0
+    i32.const 38400
+    i32.const 0
+    call $pairing
+    drop)
+  (memory (;0;) 1)
+  (export "main" (func $main)))
+    "#,
+        1_000_000,
+    );
+    assert_eq!(exit_code, ExitCode::OutOfFuel.into_i32());
+    // G1 MSM of 100 pairs costs 234K
+    let exit_code = run_with_fuel_limit(
+        r#"
+(module
+  (import "fluentbase_v1alpha" "_crypto_bls12381_g1_msm" (func $msm (param i32 i32 i32) (result i32)))
+  (func $main
+    i32.const 0
+    i32.const 16000
+    i32.const 0
+    call $msm
+    drop)
+  (memory (;0;) 1)
+  (export "main" (func $main)))
+    "#,
+        100_000,
+    );
+    assert_eq!(exit_code, ExitCode::OutOfFuel.into_i32());
+}
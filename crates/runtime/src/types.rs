@@ -12,6 +12,12 @@ pub trait TrieDb {
     fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes>;
 
     fn update_preimage(&mut self, key: &[u8], value: Bytes);
+
+    /// Removes the node, it's used by the pruning only, so databases that are never pruned can
+    /// keep the default implementation that does nothing
+    fn remove_node(&mut self, _key: &[u8]) {}
+
+    fn remove_preimage(&mut self, _key: &[u8]) {}
 }
 
 #[derive(Default, Clone)]
@@ -36,6 +42,91 @@ impl TrieDb for InMemoryTrieDb {
     fn update_preimage(&mut self, key: &[u8], value: Bytes) {
         self.preimages.insert(Bytes::copy_from_slice(key), value);
     }
+
+    fn remove_node(&mut self, key: &[u8]) {
+        self.nodes.remove(&Bytes::copy_from_slice(key));
+    }
+
+    fn remove_preimage(&mut self, key: &[u8]) {
+        self.preimages.remove(&Bytes::copy_from_slice(key));
+    }
+}
+
+impl<T: TrieDb + ?Sized> TrieDb for &mut T {
+    fn get_node(&mut self, key: &[u8]) -> Option<Bytes> {
+        (**self).get_node(key)
+    }
+
+    fn update_node(&mut self, key: &[u8], value: Bytes) {
+        (**self).update_node(key, value)
+    }
+
+    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes> {
+        (**self).get_preimage(key)
+    }
+
+    fn update_preimage(&mut self, key: &[u8], value: Bytes) {
+        (**self).update_preimage(key, value)
+    }
+
+    fn remove_node(&mut self, key: &[u8]) {
+        (**self).remove_node(key)
+    }
+
+    fn remove_preimage(&mut self, key: &[u8]) {
+        (**self).remove_preimage(key)
+    }
+}
+
+/// Trie database that buffers all writes in memory and reads through to the base database, so
+/// the trie can be modified (f.e. to compute a pending state root) without touching the base.
+pub struct OverlayTrieDb<DB> {
+    base: DB,
+    nodes: HashMap<Bytes, Option<Bytes>>,
+    preimages: HashMap<Bytes, Option<Bytes>>,
+}
+
+impl<DB: TrieDb> OverlayTrieDb<DB> {
+    pub fn new(base: DB) -> Self {
+        Self {
+            base,
+            nodes: HashMap::new(),
+            preimages: HashMap::new(),
+        }
+    }
+}
+
+impl<DB: TrieDb> TrieDb for OverlayTrieDb<DB> {
+    fn get_node(&mut self, key: &[u8]) -> Option<Bytes> {
+        match self.nodes.get(key) {
+            Some(value) => value.clone(),
+            None => self.base.get_node(key),
+        }
+    }
+
+    fn update_node(&mut self, key: &[u8], value: Bytes) {
+        self.nodes.insert(Bytes::copy_from_slice(key), Some(value));
+    }
+
+    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes> {
+        match self.preimages.get(key) {
+            Some(value) => value.clone(),
+            None => self.base.get_preimage(key),
+        }
+    }
+
+    fn update_preimage(&mut self, key: &[u8], value: Bytes) {
+        self.preimages
+            .insert(Bytes::copy_from_slice(key), Some(value));
+    }
+
+    fn remove_node(&mut self, key: &[u8]) {
+        self.nodes.insert(Bytes::copy_from_slice(key), None);
+    }
+
+    fn remove_preimage(&mut self, key: &[u8]) {
+        self.preimages.insert(Bytes::copy_from_slice(key), None);
+    }
 }
 
 impl TrieDb for eth_trie::MemoryDB {
@@ -54,6 +145,14 @@ impl TrieDb for eth_trie::MemoryDB {
     fn update_preimage(&mut self, key: &[u8], value: Bytes) {
         self.insert(key, value.into()).unwrap()
     }
+
+    fn remove_node(&mut self, key: &[u8]) {
+        self.remove(key).unwrap()
+    }
+
+    fn remove_preimage(&mut self, key: &[u8]) {
+        self.remove(key).unwrap()
+    }
 }
 
 #[derive(Debug)]
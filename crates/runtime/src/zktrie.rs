@@ -1,14 +1,30 @@
-use crate::{storage::TrieStorage, types::TrieDb};
+use crate::{
+    snapshot::{SnapshotError, SnapshotLeaf, StateSnapshot},
+    storage::TrieStorage,
+    types::TrieDb,
+};
 use fluentbase_types::{Bytes, ExitCode, POSEIDON_EMPTY};
 use fluentbase_zktrie::{
-    Byte32, Database, Error, Hash, Node, PoseidonHash, PreimageDatabase, TrieData, ZkTrie,
+    Byte32, Database, Error, Hash, Node, NodeValue, PoseidonHash, PreimageDatabase, TrieData,
+    ZkTrie,
 };
 use halo2curves::bn256::Fr;
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
 
 #[derive(Clone)]
 struct NodeDb<DB>(Rc<RefCell<DB>>);
 
+impl<DB: TrieDb> NodeDb<DB> {
+    fn new(storage: DB) -> Self {
+        Self(Rc::new(RefCell::new(storage)))
+    }
+}
+
 const STORAGE_PREFIX_NODE: u8 = 0x01;
 const STORAGE_PREFIX_PREIMAGE: u8 = 0x02;
 const STORAGE_PREFIX_CODE: u8 = 0x02;
@@ -58,10 +74,27 @@ impl<'a, DB: TrieDb> PreimageDatabase for NodeDb<DB> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruningError {
+    /// At least one committed root must be retained
+    NoRetainedRoots,
+}
+
+/// Amount of nodes and preimages removed by the pruning
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PruningStats {
+    pub nodes: usize,
+    pub preimages: usize,
+}
+
 #[derive(Clone)]
 pub struct ZkTrieStateDb<DB> {
     storage: NodeDb<DB>,
     trie: Option<ZkTrie<PoseidonHash>>,
+    keep_roots: usize,
+    roots: VecDeque<[u8; 32]>,
+    /// Committed roots that went out of the retained window since the last pruning
+    evicted_roots: Vec<[u8; 32]>,
 }
 
 const MAX_LEVEL: usize = 31 * 8;
@@ -69,8 +102,11 @@ const MAX_LEVEL: usize = 31 * 8;
 impl<DB: TrieDb> ZkTrieStateDb<DB> {
     pub fn new(storage: DB) -> Self {
         Self {
-            storage: NodeDb(Rc::new(RefCell::new(storage))),
+            storage: NodeDb::new(storage),
             trie: None,
+            keep_roots: 0,
+            roots: VecDeque::new(),
+            evicted_roots: Vec::new(),
         }
     }
 
@@ -83,6 +119,181 @@ impl<DB: TrieDb> ZkTrieStateDb<DB> {
         storage.open(root32);
         storage
     }
+
+    /// Enables pruning that keeps everything reachable from the last `keep_roots` committed
+    /// roots and from the current root. Only nodes of the committed roots that go out of this
+    /// window are removed, so nodes of intermediate states that were never committed are kept.
+    pub fn enable_pruning(&mut self, keep_roots: usize) -> Result<(), PruningError> {
+        if keep_roots == 0 {
+            return Err(PruningError::NoRetainedRoots);
+        }
+        self.keep_roots = keep_roots;
+        self.evict_roots();
+        Ok(())
+    }
+
+    /// Committed roots that are protected from the pruning, the oldest goes first
+    pub fn retained_roots(&self) -> impl Iterator<Item = &[u8; 32]> {
+        self.roots.iter()
+    }
+
+    fn evict_roots(&mut self) {
+        while self.roots.len() > self.keep_roots {
+            let root = self.roots.pop_front().unwrap();
+            self.evicted_roots.push(root);
+        }
+    }
+
+    /// Removes nodes and preimages of the evicted roots that are unreachable from the retained
+    /// roots and from the current root
+    pub fn prune(&mut self) -> Result<PruningStats, Error> {
+        let mut stats = PruningStats::default();
+        if self.evicted_roots.is_empty() {
+            return Ok(stats);
+        }
+        // mark, subtrees shared between roots are visited only once
+        let mut nodes = BTreeSet::new();
+        let mut preimages = BTreeSet::new();
+        let retained = self
+            .roots
+            .iter()
+            .chain(Some(&self.compute_root()))
+            .map(|root| Hash::from_bytes(root))
+            .collect::<Vec<_>>();
+        self.walk_nodes(retained, &mut nodes, |node| {
+            if let NodeValue::Leaf(leaf) = node.value() {
+                preimages.insert(leaf.key.raw_bytes().to_vec());
+                for value in leaf.value_preimage.iter() {
+                    preimages.insert(value.bytes().to_vec());
+                }
+            }
+        })?;
+        // sweep, subtrees of the evicted roots that are marked are still reachable
+        let evicted = self
+            .evicted_roots
+            .drain(..)
+            .map(|root| Hash::from_bytes(&root))
+            .collect::<Vec<_>>();
+        let mut removed_nodes = Vec::new();
+        let mut removed_preimages = Vec::new();
+        self.walk_nodes(evicted, &mut nodes, |node| {
+            removed_nodes.push(*node.hash());
+            if let NodeValue::Leaf(leaf) = node.value() {
+                removed_preimages.push(leaf.key.raw_bytes().to_vec());
+                for value in leaf.value_preimage.iter() {
+                    removed_preimages.push(value.bytes().to_vec());
+                }
+            }
+        })?;
+        let mut storage = self.storage.0.borrow_mut();
+        for hash in removed_nodes {
+            storage.remove_node(hash.raw_bytes());
+            stats.nodes += 1;
+        }
+        for key in removed_preimages {
+            if preimages.insert(key.clone()) && storage.get_preimage(&key).is_some() {
+                storage.remove_preimage(&key);
+                stats.preimages += 1;
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Visits all nodes reachable from the roots except the ones that are already visited, nodes
+    /// that are missing in the storage are skipped because they're already pruned
+    fn walk_nodes(
+        &self,
+        roots: Vec<Hash>,
+        visited: &mut BTreeSet<Vec<u8>>,
+        mut f: impl FnMut(&Node<PoseidonHash>),
+    ) -> Result<(), Error> {
+        let mut stack = roots;
+        while let Some(hash) = stack.pop() {
+            if hash.is_zero() || !visited.insert(hash.raw_bytes().to_vec()) {
+                continue;
+            }
+            let Some(node) = self.storage.get_node(&hash)? else {
+                continue;
+            };
+            if let NodeValue::Branch(branch) = node.value() {
+                stack.push(*branch.left.hash());
+                stack.push(*branch.right.hash());
+            }
+            f(&node);
+        }
+        Ok(())
+    }
+
+    /// Dumps all trie leaves with their original keys and preimages of leaf values
+    pub fn export_snapshot(&self) -> Result<StateSnapshot, SnapshotError> {
+        let trie = self.trie.as_ref().ok_or(SnapshotError::TrieNotOpened)?;
+        let mut nodes = Vec::new();
+        trie.walk_leaves(&self.storage, |_, node| {
+            nodes.push(node);
+            Ok(())
+        })?;
+        let mut snapshot = StateSnapshot::new(self.compute_root());
+        let mut storage = self.storage.0.borrow_mut();
+        for node in nodes {
+            let leaf = node.leaf().ok_or(Error::ExpectedLeafNode)?;
+            // trie stores only secure key, but original key is saved as its preimage
+            let key = storage
+                .get_preimage(leaf.key.raw_bytes())
+                .filter(|key| key.len() == 32)
+                .ok_or(SnapshotError::MissingKeyPreimage(leaf.key.bytes()))?;
+            let values = leaf
+                .value_preimage
+                .iter()
+                .map(|value| {
+                    let mut bytes = [0u8; 32];
+                    bytes.copy_from_slice(value.bytes());
+                    bytes
+                })
+                .collect::<Vec<_>>();
+            // any leaf value can be a hash of some preimage (f.e. bytecode)
+            for value in values.iter() {
+                if let Some(preimage) = storage.get_preimage(value) {
+                    snapshot.preimages.insert(*value, preimage);
+                }
+            }
+            snapshot.leaves.push(SnapshotLeaf {
+                key: key[..].try_into().unwrap(),
+                flags: leaf.compressed_flags,
+                values,
+            });
+        }
+        Ok(snapshot)
+    }
+
+    /// Rebuilds trie from the snapshot and verifies that computed root matches snapshot's root
+    pub fn import_snapshot(storage: DB, snapshot: &StateSnapshot) -> Result<Self, SnapshotError> {
+        let state_db = Self::rebuild(storage, snapshot)?;
+        let root = state_db.compute_root();
+        if root != snapshot.root {
+            return Err(SnapshotError::RootMismatch {
+                expected: snapshot.root,
+                actual: root,
+            });
+        }
+        Ok(state_db)
+    }
+
+    /// Rebuilds trie from the snapshot without root verification. It's used to migrate the state
+    /// to the latest hash scheme: leaves are walked by the node hashes stored in the database, so
+    /// the snapshot can be exported from the trie that is built with the legacy scheme.
+    pub fn rebuild(storage: DB, snapshot: &StateSnapshot) -> Result<Self, SnapshotError> {
+        let mut state_db = Self::new_empty(storage);
+        let entries = snapshot
+            .leaves
+            .iter()
+            .map(|leaf| (leaf.key, Some((leaf.flags, leaf.values.clone()))))
+            .collect::<Vec<_>>();
+        state_db.update_batch(&entries)?;
+        for (hash, preimage) in snapshot.preimages.iter() {
+            state_db.update_preimage(hash, preimage.clone());
+        }
+        Ok(state_db)
+    }
 }
 
 impl<DB: TrieDb> TrieStorage for ZkTrieStateDb<DB> {
@@ -145,6 +356,34 @@ impl<DB: TrieDb> TrieStorage for ZkTrieStateDb<DB> {
         self.update(key, 0, &vec![POSEIDON_EMPTY.0])
     }
 
+    fn update_batch(
+        &mut self,
+        entries: &[([u8; 32], Option<(u32, Vec<[u8; 32]>)>)],
+    ) -> Result<(), ExitCode> {
+        let trie = self.trie.as_mut().unwrap();
+        // removed keys are replaced with empty value, the same way as `remove` does
+        let entries = entries.iter().map(|(key, value)| {
+            let (flags, value) = match value {
+                Some((flags, value)) => (*flags, value.iter().map(|v| Byte32::from(*v)).collect()),
+                None => (0, vec![Byte32::from(POSEIDON_EMPTY.0)]),
+            };
+            (&key[..], Some((flags, value)))
+        });
+        trie.update_batch(&mut self.storage, entries)
+            .map_err(|_| ExitCode::PersistentStorageError)
+    }
+
+    fn commit_root(&mut self) {
+        if self.keep_roots == 0 {
+            return;
+        }
+        let root = self.compute_root();
+        if self.roots.back() != Some(&root) {
+            self.roots.push_back(root);
+        }
+        self.evict_roots();
+    }
+
     fn proof(&self, key: &[u8; 32]) -> Option<Vec<Vec<u8>>> {
         let trie = self.trie.as_ref().unwrap();
         match trie.proof(&self.storage, &key[..]) {
@@ -164,7 +403,16 @@ impl<DB: TrieDb> TrieStorage for ZkTrieStateDb<DB> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{storage::TrieStorage, types::InMemoryTrieDb, zktrie::ZkTrieStateDb};
+    use crate::{
+        snapshot::{SnapshotError, StateSnapshot},
+        storage::TrieStorage,
+        types::{InMemoryTrieDb, OverlayTrieDb},
+        zktrie::{PruningError, PruningStats, ZkTrieStateDb},
+    };
+    use fluentbase_poseidon::{poseidon_hash, HashVersion};
+    use fluentbase_types::{
+        Bytes, JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, LEGACY_POSEIDON_EMPTY, POSEIDON_EMPTY,
+    };
 
     macro_rules! bytes32 {
         ($val:expr) => {{
@@ -197,4 +445,180 @@ mod tests {
         assert_eq!(data[0], *bytes32!("value1"));
         assert_eq!(data[1], *bytes32!("value2"));
     }
+
+    #[test]
+    fn test_overlay() {
+        let mut db = InMemoryTrieDb::default();
+        let mut zkt = ZkTrieStateDb::new_empty(&mut db);
+        zkt.update(bytes32!("key1"), 0, &vec![*bytes32!("value1")])
+            .unwrap();
+        let root = zkt.compute_root();
+        drop(zkt);
+        // writes into the overlay are not visible through the base database
+        let mut zkt = ZkTrieStateDb::new_opened(OverlayTrieDb::new(&mut db), &root);
+        zkt.update(bytes32!("key2"), 0, &vec![*bytes32!("value2")])
+            .unwrap();
+        let new_root = zkt.compute_root();
+        assert_ne!(new_root, root);
+        drop(zkt);
+        let zkt = ZkTrieStateDb::new_opened(&mut db, &new_root);
+        assert_eq!(zkt.get(bytes32!("key1")), None);
+        drop(zkt);
+        let zkt = ZkTrieStateDb::new_opened(&mut db, &root);
+        assert_eq!(
+            zkt.get(bytes32!("key1")).unwrap().0,
+            vec![*bytes32!("value1")]
+        );
+        assert_eq!(zkt.get(bytes32!("key2")), None);
+    }
+
+    #[test]
+    fn test_snapshot_export_import() {
+        let db = InMemoryTrieDb::default();
+        let mut zkt = ZkTrieStateDb::new_empty(db);
+        zkt.update(bytes32!("key1"), 1, &vec![*bytes32!("value1")])
+            .unwrap();
+        zkt.update(
+            bytes32!("key2"),
+            2,
+            &vec![*bytes32!("value2"), *bytes32!("code_hash")],
+        )
+        .unwrap();
+        zkt.update_preimage(bytes32!("code_hash"), Bytes::from_static(&[1, 2, 3]));
+        let root = zkt.compute_root();
+        let snapshot = zkt.export_snapshot().unwrap();
+        assert_eq!(snapshot.root, root);
+        assert_eq!(snapshot.leaves.len(), 2);
+        assert_eq!(
+            snapshot
+                .preimages
+                .get(&*bytes32!("code_hash"))
+                .unwrap()
+                .as_ref(),
+            &[1, 2, 3]
+        );
+        // both binary and json formats must be lossless
+        let snapshot2 = StateSnapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+        assert_eq!(snapshot, snapshot2);
+        let snapshot2 = StateSnapshot::from_json(&snapshot.to_json()).unwrap();
+        assert_eq!(snapshot, snapshot2);
+        // import rebuilds the same trie
+        let mut zkt2 =
+            ZkTrieStateDb::import_snapshot(InMemoryTrieDb::default(), &snapshot2).unwrap();
+        assert_eq!(zkt2.compute_root(), root);
+        let (data, flags) = zkt2.get(bytes32!("key2")).unwrap();
+        assert_eq!(data, vec![*bytes32!("value2"), *bytes32!("code_hash")]);
+        assert_eq!(flags, 2);
+        assert_eq!(
+            zkt2.get_preimage(bytes32!("code_hash")).unwrap().as_ref(),
+            &[1, 2, 3]
+        );
+        // root of the tampered snapshot doesn't match
+        let mut tampered = snapshot.clone();
+        tampered.leaves[0].values[0] = *bytes32!("value3");
+        assert!(matches!(
+            ZkTrieStateDb::import_snapshot(InMemoryTrieDb::default(), &tampered),
+            Err(SnapshotError::RootMismatch { .. })
+        ));
+        // unknown version is rejected
+        let mut bytes = snapshot.to_bytes();
+        bytes[7] = 2;
+        assert!(matches!(
+            StateSnapshot::from_bytes(&bytes),
+            Err(SnapshotError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_pruning() {
+        let db = InMemoryTrieDb::default();
+        let mut zkt = ZkTrieStateDb::new_empty(db);
+        assert_eq!(zkt.enable_pruning(0), Err(PruningError::NoRetainedRoots));
+        zkt.enable_pruning(2).unwrap();
+        zkt.update(bytes32!("key2"), 1, &vec![*bytes32!("value")])
+            .unwrap();
+        let mut roots = Vec::new();
+        for i in 0..4 {
+            let code_hash = *bytes32!(format!("code_hash{}", i));
+            zkt.update(bytes32!("key1"), 1, &vec![code_hash]).unwrap();
+            zkt.update_preimage(&code_hash, Bytes::from(vec![i as u8]));
+            zkt.commit_root();
+            roots.push(zkt.compute_root());
+        }
+        assert_eq!(
+            zkt.retained_roots().collect::<Vec<_>>(),
+            vec![&roots[2], &roots[3]]
+        );
+        let stats = zkt.prune().unwrap();
+        assert!(stats.nodes > 0);
+        assert_eq!(stats.preimages, 2);
+        // nothing else to prune
+        assert_eq!(zkt.prune().unwrap(), PruningStats::default());
+        let db = zkt.storage.0.borrow().clone();
+        for (i, root) in roots.iter().enumerate() {
+            let code_hash = *bytes32!(format!("code_hash{}", i));
+            let mut zkt2 = ZkTrieStateDb::new_opened(db.clone(), root);
+            if i < 2 {
+                // pruned roots can't be opened anymore
+                assert_eq!(zkt2.get(bytes32!("key1")), None);
+                assert_eq!(zkt2.get_preimage(&code_hash), None);
+            } else {
+                assert_eq!(zkt2.get(bytes32!("key1")).unwrap().0, vec![code_hash]);
+                assert_eq!(
+                    zkt2.get(bytes32!("key2")).unwrap().0,
+                    vec![*bytes32!("value")]
+                );
+                assert_eq!(zkt2.get_preimage(&code_hash).unwrap().as_ref(), &[i as u8]);
+            }
+        }
+        // key preimages are still there, so the state can be exported
+        assert_eq!(zkt.export_snapshot().unwrap().leaves.len(), 2);
+    }
+
+    #[test]
+    fn test_code_hash_migration() {
+        let code = b"some rwasm bytecode";
+        let legacy_hash = HashVersion::V1.hash_bytes(code);
+        let mut account = vec![[0u8; 32]; 6];
+        account[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize] = legacy_hash;
+        let mut zkt = ZkTrieStateDb::new_empty(InMemoryTrieDb::default());
+        zkt.update(bytes32!("account"), 0, &account).unwrap();
+        zkt.update(bytes32!("slot"), 0, &vec![legacy_hash]).unwrap();
+        zkt.update_preimage(&legacy_hash, Bytes::from_static(code));
+        let mut snapshot = zkt.export_snapshot().unwrap();
+        // storage slots are not touched even if they look like code hashes
+        assert_eq!(snapshot.migrate_code_hashes(), 1);
+        assert_eq!(snapshot.migrate_code_hashes(), 0);
+        let mut zkt2 = ZkTrieStateDb::rebuild(InMemoryTrieDb::default(), &snapshot).unwrap();
+        assert_ne!(zkt2.compute_root(), snapshot.root);
+        let new_hash = poseidon_hash(code);
+        let (values, _) = zkt2.get(bytes32!("account")).unwrap();
+        assert_eq!(
+            values[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize],
+            new_hash
+        );
+        assert_eq!(zkt2.get(bytes32!("slot")).unwrap().0, vec![legacy_hash]);
+        assert_eq!(zkt2.get_preimage(&new_hash).unwrap().as_ref(), code);
+        // legacy preimage is still referenced by the slot
+        assert!(zkt2.get_preimage(&legacy_hash).is_some());
+    }
+
+    #[test]
+    fn test_empty_code_hash_migration() {
+        let mut account = vec![[0u8; 32]; 6];
+        account[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize] = LEGACY_POSEIDON_EMPTY.0;
+        let mut zkt = ZkTrieStateDb::new_empty(InMemoryTrieDb::default());
+        zkt.update(bytes32!("account"), 0, &account).unwrap();
+        let mut snapshot = zkt.export_snapshot().unwrap();
+        // empty code has no preimage, but it's migrated anyway
+        assert_eq!(snapshot.migrate_code_hashes(), 1);
+        assert_eq!(snapshot.migrate_code_hashes(), 0);
+        assert!(snapshot.preimages.is_empty());
+        let mut zkt2 = ZkTrieStateDb::rebuild(InMemoryTrieDb::default(), &snapshot).unwrap();
+        let (values, _) = zkt2.get(bytes32!("account")).unwrap();
+        assert_eq!(
+            values[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize],
+            POSEIDON_EMPTY.0
+        );
+    }
 }
@@ -0,0 +1,515 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    self,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    FnArg, GenericArgument, Ident, ImplItem, ImplItemFn, ItemImpl, LitInt, LitStr, PathArguments,
+    ReturnType, Token, Type,
+};
+
+/// Options from `#[contract(...)]`
+#[derive(Default)]
+struct ContractAttrs {
+    /// `#[contract(solidity)]` - params and results are encoded with Solidity ABI
+    solidity: bool,
+}
+
+impl Parse for ContractAttrs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for ident in Punctuated::<Ident, Token![,]>::parse_terminated(input)? {
+            if ident == "solidity" {
+                attrs.solidity = true;
+            } else {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "unsupported contract attribute",
+                ));
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+/// How the function is exposed by the contract
+enum EntryKind {
+    /// `#[constructor]` - called from `deploy`
+    Constructor,
+    /// `#[method]` - called from `main` by the method id
+    Method(MethodAttrs),
+    /// `#[fallback]` - called from `main` if input has no method id or the id is unknown
+    Fallback,
+}
+
+/// Options from `#[method(...)]`
+#[derive(Default)]
+struct MethodAttrs {
+    /// `#[method(id = N)]` - explicit method id
+    id: Option<LitInt>,
+    /// `#[method(name = "balanceOf")]` - function name used in the Solidity signature
+    name: Option<LitStr>,
+}
+
+/// Function marked with `#[constructor]` or `#[method]`
+struct Entry {
+    kind: EntryKind,
+    ident: Ident,
+    has_receiver: bool,
+    inputs: Vec<Type>,
+    output: Option<Type>,
+}
+
+impl Entry {
+    /// Removes entry attributes from the function, returns `None` for regular functions
+    fn parse(func: &mut ImplItemFn) -> syn::Result<Option<Self>> {
+        let mut kind = None;
+        let mut error = None;
+        func.attrs.retain(|attr| {
+            let result = if attr.path().is_ident("constructor") {
+                attr.meta
+                    .require_path_only()
+                    .map(|_| Some(EntryKind::Constructor))
+            } else if attr.path().is_ident("method") {
+                Self::parse_method_attrs(attr).map(|attrs| Some(EntryKind::Method(attrs)))
+            } else if attr.path().is_ident("fallback") {
+                attr.meta
+                    .require_path_only()
+                    .map(|_| Some(EntryKind::Fallback))
+            } else {
+                return true;
+            };
+            match result {
+                Ok(value) if kind.is_none() => kind = value,
+                Ok(_) => {
+                    error = Some(syn::Error::new_spanned(
+                        attr,
+                        "function can have only one entry attribute",
+                    ))
+                }
+                Err(err) => error = Some(err),
+            }
+            false
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+        let Some(kind) = kind else {
+            return Ok(None);
+        };
+        let sig = &func.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.generics,
+                "contract functions can't be generic",
+            ));
+        }
+        let mut has_receiver = false;
+        let mut inputs = Vec::new();
+        for input in &sig.inputs {
+            match input {
+                FnArg::Receiver(receiver) if receiver.mutability.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        receiver,
+                        "contract is created with `Default` for every call, so its fields can't be \
+                         changed, keep the state in storage (`StorageValue`, `StorageMap`, ...) and \
+                         use `&self`",
+                    ))
+                }
+                FnArg::Receiver(receiver) if receiver.reference.is_some() => has_receiver = true,
+                FnArg::Receiver(receiver) => {
+                    return Err(syn::Error::new_spanned(
+                        receiver,
+                        "contract is passed by reference, use `&self`",
+                    ))
+                }
+                FnArg::Typed(pat_type) => match pat_type.ty.as_ref() {
+                    Type::Reference(_) => {
+                        return Err(syn::Error::new_spanned(
+                            &pat_type.ty,
+                            "params are decoded from the input, use owned types",
+                        ))
+                    }
+                    ty => inputs.push(ty.clone()),
+                },
+            }
+        }
+        let output = match &sig.output {
+            ReturnType::Type(_, ty) if !is_unit(ty) => Some(ty.as_ref().clone()),
+            _ => None,
+        };
+        Ok(Some(Self {
+            kind,
+            ident: sig.ident.clone(),
+            has_receiver,
+            inputs,
+            output,
+        }))
+    }
+
+    fn parse_method_attrs(attr: &syn::Attribute) -> syn::Result<MethodAttrs> {
+        let mut attrs = MethodAttrs::default();
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return Ok(attrs);
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                attrs.id = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                attrs.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported method attribute"))
+            }
+        })?;
+        Ok(attrs)
+    }
+
+    /// Name of the method id constant, f.e. `TRANSFER_METHOD_ID` for `fn transfer`
+    fn const_ident(&self) -> Ident {
+        format_ident!(
+            "{}_METHOD_ID",
+            self.ident.unraw().to_string().to_uppercase()
+        )
+    }
+
+    /// Method id is a Solidity selector derived from the Rust signature unless it's set
+    /// explicitly with `#[method(id = N)]`
+    fn method_id(&self, attrs: &MethodAttrs) -> syn::Result<proc_macro2::TokenStream> {
+        if let Some(id) = &attrs.id {
+            return Ok(quote! { #id });
+        }
+        let ident = match &attrs.name {
+            Some(name) => name.parse::<Ident>()?,
+            None => self.ident.unraw(),
+        };
+        let names = (0..self.inputs.len()).map(|i| format_ident!("arg{}", i));
+        let types = &self.inputs;
+        Ok(quote! {
+            ::fluentbase_sdk::derive_keccak256_id!(fn #ident(#(#names: #types),*))
+        })
+    }
+
+    /// Checks if the function returns a value, `Result<(), E>` is treated as no value
+    fn returns_value(&self) -> bool {
+        match &self.output {
+            Some(ty) => result_ok_type(ty).map_or(true, |ok| !is_unit(ok)),
+            None => false,
+        }
+    }
+
+    /// Decodes params and calls the function, the result is stored into `output`
+    fn call(&self, solidity: bool) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let args = (0..self.inputs.len())
+            .map(|i| format_ident!("arg{}", i))
+            .collect::<Vec<_>>();
+        let types = &self.inputs;
+        let decode = match (args.is_empty(), solidity) {
+            (true, _) => quote! {},
+            (false, true) => quote! {
+                let (#(#args,)*) =
+                    ::fluentbase_sdk::contract::decode_sol_input::<(#(#types,)*)>(input)?;
+            },
+            (false, false) => quote! {
+                let (#(#args,)*) =
+                    ::fluentbase_sdk::contract::decode_input::<(#(#types,)*)>(input)?;
+            },
+        };
+        let mut call = if self.has_receiver {
+            quote! { self.#ident(#(#args),*) }
+        } else {
+            quote! { Self::#ident(#(#args),*) }
+        };
+        // error is converted into the exit code
+        if self.output.as_ref().and_then(result_ok_type).is_some() {
+            call = quote! {
+                #call.map_err(::core::convert::Into::<::fluentbase_sdk::contract::ExitCode>::into)?
+            };
+        }
+        if self.returns_value() {
+            quote! {
+                #decode
+                let output = #call;
+            }
+        } else {
+            quote! {
+                #decode
+                #call;
+            }
+        }
+    }
+
+    /// Encodes `output` of the method
+    fn encode(&self, solidity: bool) -> proc_macro2::TokenStream {
+        match (self.returns_value(), solidity) {
+            (false, _) => quote! { ::fluentbase_sdk::contract::Vec::new() },
+            (true, true) => quote! { ::fluentbase_sdk::contract::encode_sol_output(output) },
+            (true, false) => quote! { ::fluentbase_sdk::contract::encode_output(&output) },
+        }
+    }
+}
+
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
+}
+
+/// Returns `T` for `Result<T, E>`
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn impl_contract(
+    attrs: ContractAttrs,
+    mut item: ItemImpl,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if !item.generics.params.is_empty() || item.trait_.is_some() {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "contract must be an inherent impl of a non-generic type",
+        ));
+    }
+    let mut constructor = None;
+    let mut fallback = None;
+    let mut methods = Vec::new();
+    for impl_item in item.items.iter_mut() {
+        let ImplItem::Fn(func) = impl_item else {
+            continue;
+        };
+        let entry = Entry::parse(func)?;
+        // inherent `fallback` shadows `Contract::fallback` when it's called on the contract
+        let is_fallback =
+            matches!(&entry, Some(entry) if matches!(entry.kind, EntryKind::Fallback));
+        if func.sig.ident.unraw() == "fallback" && !is_fallback {
+            return Err(syn::Error::new_spanned(
+                &func.sig.ident,
+                "`fallback` clashes with `Contract::fallback`, mark the function with \
+                 `#[fallback]` to call it for unknown method ids, or rename it",
+            ));
+        }
+        let Some(entry) = entry else {
+            continue;
+        };
+        match entry.kind {
+            EntryKind::Constructor if constructor.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    &func.sig,
+                    "contract can have only one constructor",
+                ))
+            }
+            EntryKind::Constructor if entry.returns_value() => {
+                return Err(syn::Error::new_spanned(
+                    &func.sig.output,
+                    "constructor can't return a value",
+                ))
+            }
+            EntryKind::Constructor => constructor = Some(entry),
+            EntryKind::Fallback if fallback.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    &func.sig,
+                    "contract can have only one fallback",
+                ))
+            }
+            EntryKind::Fallback if !entry.inputs.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    &func.sig.inputs,
+                    "fallback has no params, input can be read from `ExecutionContext`",
+                ))
+            }
+            EntryKind::Fallback => fallback = Some(entry),
+            EntryKind::Method(_) => methods.push(entry),
+        }
+    }
+
+    let self_ty = &item.self_ty;
+    let mut consts = Vec::new();
+    for method in &methods {
+        let const_ident = method.const_ident();
+        let EntryKind::Method(attrs) = &method.kind else {
+            unreachable!()
+        };
+        let method_id = method.method_id(attrs)?;
+        consts.push(quote! {
+            pub const #const_ident: u32 = #method_id;
+        });
+    }
+    // derived selectors are known only after macro expansion, so all method ids are compared in
+    // a constant, the same id would make one of the methods unreachable
+    let mut id_checks = Vec::new();
+    for (i, a) in methods.iter().enumerate() {
+        for b in &methods[i + 1..] {
+            let (a_const, b_const) = (a.const_ident(), b.const_ident());
+            let message = format!(
+                "methods `{}` and `{}` have the same method id",
+                a.ident.unraw(),
+                b.ident.unraw()
+            );
+            id_checks.push(quote! {
+                if <#self_ty>::#a_const == <#self_ty>::#b_const {
+                    panic!(#message);
+                }
+            });
+        }
+    }
+    let routes = methods.iter().map(|method| {
+        let const_ident = method.const_ident();
+        let call = method.call(attrs.solidity);
+        let encode = method.encode(attrs.solidity);
+        quote! {
+            Self::#const_ident => {
+                #call
+                Ok(#encode)
+            }
+        }
+    });
+    let construct = constructor
+        .as_ref()
+        .map(|constructor| constructor.call(attrs.solidity));
+    let fallback = fallback.as_ref().map(|fallback| {
+        let call = fallback.call(attrs.solidity);
+        let encode = fallback.encode(attrs.solidity);
+        quote! {
+            fn fallback(
+                &self,
+            ) -> ::core::result::Result<
+                ::fluentbase_sdk::contract::Vec<u8>,
+                ::fluentbase_sdk::contract::ExitCode,
+            > {
+                #call
+                Ok(#encode)
+            }
+        }
+    });
+
+    Ok(quote! {
+        #item
+
+        impl #self_ty {
+            #(#consts)*
+        }
+
+        const _: () = {
+            #(#id_checks)*
+        };
+
+        impl ::fluentbase_sdk::contract::Contract for #self_ty {
+            #[allow(unused_variables)]
+            fn construct(
+                &self,
+                input: &[u8],
+            ) -> ::core::result::Result<(), ::fluentbase_sdk::contract::ExitCode> {
+                #construct
+                Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn dispatch(
+                &self,
+                method_id: u32,
+                input: &[u8],
+            ) -> ::core::result::Result<
+                ::fluentbase_sdk::contract::Vec<u8>,
+                ::fluentbase_sdk::contract::ExitCode,
+            > {
+                match method_id {
+                    #(#routes)*
+                    _ => ::fluentbase_sdk::contract::Contract::fallback(self),
+                }
+            }
+
+            #fallback
+        }
+
+        pub fn deploy() {
+            ::fluentbase_sdk::contract::deploy::<#self_ty>()
+        }
+
+        pub fn main() {
+            ::fluentbase_sdk::contract::main::<#self_ty>()
+        }
+    })
+}
+
+/// Generates contract entrypoints (`deploy` and `main`) for the impl block.
+///
+/// Functions marked with `#[method]` are routed by the method id (first 4 bytes of the input,
+/// big-endian), the rest of the input is decoded as a tuple of params and the result is encoded
+/// with `Codec` (or Solidity ABI with `#[contract(solidity)]`). Function marked with
+/// `#[constructor]` is called from `deploy` with params decoded from the whole input.
+/// Method id can be set explicitly with `#[method(id = N)]`, or derived from a different name
+/// with `#[method(name = "balanceOf")]` to match Solidity selectors.
+/// Function marked with `#[fallback]` (without params) is called if input is shorter than a
+/// method id (f.e. plain value transfer) or the method id is unknown, otherwise such calls halt
+/// with `ExitCode::NotSupportedCall`. Methods with the same id and functions named `fallback`
+/// without `#[fallback]` are compile errors.
+///
+/// Contract is created with `Default` for every call of `deploy` and `main`, so nothing written to
+/// its fields is preserved between calls: `&mut self` is rejected, and the state must be kept in
+/// storage (`StorageValue`, `StorageMap`, ...), which fields of the contract can refer to.
+/// Malformed input halts execution with an error exit code, as well as `Err` returned from a
+/// function (`E: Into<ExitCode>`).
+#[proc_macro_attribute]
+pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attrs = syn::parse_macro_input!(attr as ContractAttrs);
+    let item = syn::parse_macro_input!(item as ItemImpl);
+    impl_contract(attrs, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Marks contract method, it's handled by `#[contract]`
+#[proc_macro_attribute]
+pub fn method(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut output = proc_macro2::TokenStream::from(item);
+    output.extend(
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[method]` can be used only inside `#[contract]` impl",
+        )
+        .to_compile_error(),
+    );
+    output.into()
+}
+
+/// Marks contract fallback, it's handled by `#[contract]`
+#[proc_macro_attribute]
+pub fn fallback(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut output = proc_macro2::TokenStream::from(item);
+    output.extend(
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[fallback]` can be used only inside `#[contract]` impl",
+        )
+        .to_compile_error(),
+    );
+    output.into()
+}
+
+/// Marks contract constructor, it's handled by `#[contract]`
+#[proc_macro_attribute]
+pub fn constructor(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut output = proc_macro2::TokenStream::from(item);
+    output.extend(
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[constructor]` can be used only inside `#[contract]` impl",
+        )
+        .to_compile_error(),
+    );
+    output.into()
+}
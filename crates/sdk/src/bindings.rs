@@ -4,6 +4,10 @@ extern "C" {
     /// - Keccak256
     /// - Poseidon (two modes, message hash and two elements hash)
     /// - Ecrecover
+    /// - Ethereum precompile primitives (sha256, ripemd160, blake2f, bn128, modexp)
+    /// - Secp256r1 signature verification (RIP-7212)
+    /// - Ed25519 signature verification
+    /// - BLS12-381 curve operations (EIP-2537)
     pub fn _crypto_keccak256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8);
     pub fn _crypto_poseidon(data_offset: *const u8, data_len: u32, output32_offset: *mut u8);
     pub fn _crypto_poseidon2(
@@ -18,6 +22,80 @@ extern "C" {
         output65_offset: *mut u8,
         rec_id: u32,
     );
+    pub fn _crypto_sha256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8);
+    pub fn _crypto_ripemd160(data_offset: *const u8, data_len: u32, output20_offset: *mut u8);
+    pub fn _crypto_blake2f(
+        rounds: u32,
+        h64_offset: *mut u8,
+        m128_offset: *const u8,
+        t16_offset: *const u8,
+        f: u32,
+    );
+    pub fn _crypto_bn128_add(
+        p1_offset: *const u8,
+        p2_offset: *const u8,
+        output64_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bn128_mul(
+        p_offset: *const u8,
+        scalar32_offset: *const u8,
+        output64_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bn128_pairing(
+        pairs_offset: *const u8,
+        pairs_len: u32,
+        output32_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_modexp(
+        base_offset: *const u8,
+        base_len: u32,
+        exp_offset: *const u8,
+        exp_len: u32,
+        mod_offset: *const u8,
+        mod_len: u32,
+        output_offset: *mut u8,
+    );
+    pub fn _crypto_secp256r1_verify(
+        digest32_offset: *const u8,
+        sig64_offset: *const u8,
+        pk64_offset: *const u8,
+    ) -> i32;
+    pub fn _crypto_ed25519_verify(
+        msg_offset: *const u8,
+        msg_len: u32,
+        sig64_offset: *const u8,
+        pk32_offset: *const u8,
+    ) -> i32;
+    pub fn _crypto_bls12381_g1_add(
+        p1_offset: *const u8,
+        p2_offset: *const u8,
+        output128_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bls12381_g1_msm(
+        pairs_offset: *const u8,
+        pairs_len: u32,
+        output128_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bls12381_g2_add(
+        p1_offset: *const u8,
+        p2_offset: *const u8,
+        output256_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bls12381_g2_msm(
+        pairs_offset: *const u8,
+        pairs_len: u32,
+        output256_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bls12381_pairing(
+        pairs_offset: *const u8,
+        pairs_len: u32,
+        output32_offset: *mut u8,
+    ) -> i32;
+    pub fn _crypto_bls12381_map_fp_to_g1(fp64_offset: *const u8, output128_offset: *mut u8) -> i32;
+    pub fn _crypto_bls12381_map_fp2_to_g2(
+        fp2_128_offset: *const u8,
+        output256_offset: *mut u8,
+    ) -> i32;
 
     /// Basic system methods that are available for every app (shared and sovereign)
     pub fn _sys_halt(code: i32) -> !;
@@ -55,6 +133,11 @@ extern "C" {
         preimage_ptr: *const u8,
         preimage_len: u32,
     ) -> bool;
+    pub fn _jzkt_update_storage_slot(
+        key32_ptr: *const u8,
+        address20_ptr: *const u8,
+        slot32_ptr: *const u8,
+    );
     pub fn _jzkt_remove(key32_ptr: *const u8);
     pub fn _jzkt_compute_root(output32_ptr: *mut u8);
     pub fn _jzkt_emit_log(
@@ -66,6 +149,7 @@ extern "C" {
     );
     pub fn _jzkt_commit(root32_ptr: *mut u8);
     pub fn _jzkt_rollback(checkpoint: u64);
+    pub fn _jzkt_checkpoint_commit();
     pub fn _jzkt_preimage_size(hash32_ptr: *const u8) -> u32;
     pub fn _jzkt_preimage_copy(hash32_ptr: *const u8, preimage_ptr: *mut u8);
 
@@ -0,0 +1,248 @@
+//! Entrypoints of contracts generated by `#[contract]`.
+//!
+//! Input of `main` is a 4-byte method id (big-endian, the same as Solidity selector) followed by
+//! method params, input of `deploy` contains only constructor params. Contract is created with
+//! `Default` for every call, so its state is kept in storage (see `storage`), not in its fields.
+//!
+//! Method id is always derived from a Solidity signature, but only `#[contract(solidity)]`
+//! encodes params and results with Solidity ABI. By default they're encoded with `Codec`, so a
+//! Solidity caller can match the selector, but not the params that follow it.
+use crate::{evm::ExecutionContext, LowLevelAPI, LowLevelSDK};
+pub use alloc::vec::Vec;
+use fluentbase_codec::{solidity::SolidityCodec, Encoder};
+pub use fluentbase_types::ExitCode;
+
+pub trait Contract: Default {
+    /// Decodes constructor params and calls the constructor
+    fn construct(&self, input: &[u8]) -> Result<(), ExitCode>;
+
+    /// Decodes params of the method with `method_id`, calls it and returns encoded result, calls
+    /// `fallback` if the method id is unknown
+    fn dispatch(&self, method_id: u32, input: &[u8]) -> Result<Vec<u8>, ExitCode>;
+
+    /// Called if input has no method id or the method id is unknown
+    fn fallback(&self) -> Result<Vec<u8>, ExitCode> {
+        Err(ExitCode::NotSupportedCall)
+    }
+
+    /// Splits input into a method id and params, and calls the method
+    fn call(&self, input: &[u8]) -> Result<Vec<u8>, ExitCode> {
+        if input.len() < 4 {
+            return self.fallback();
+        }
+        let (method_id, input) = input.split_at(4);
+        let method_id = u32::from_be_bytes(method_id.try_into().unwrap());
+        self.dispatch(method_id, input)
+    }
+}
+
+pub fn deploy<C: Contract>() {
+    let input = ExecutionContext::contract_input();
+    finalize(C::default().construct(&input).map(|_| Vec::new()));
+}
+
+pub fn main<C: Contract>() {
+    let input = ExecutionContext::contract_input();
+    finalize(C::default().call(&input));
+}
+
+fn finalize(result: Result<Vec<u8>, ExitCode>) {
+    match result {
+        Ok(output) => {
+            if !output.is_empty() {
+                LowLevelSDK::sys_write(&output);
+            }
+        }
+        Err(exit_code) => LowLevelSDK::sys_halt(exit_code.into_i32()),
+    }
+}
+
+pub fn decode_input<T: Encoder<T> + Default>(input: &[u8]) -> Result<T, ExitCode> {
+    // input is controlled by the caller, so malformed input must not trap
+    T::try_decode(input).map_err(|_| ExitCode::InputDecodeFailure)
+}
+
+pub fn encode_output<T: Encoder<T>>(output: &T) -> Vec<u8> {
+    output.encode_to_vec(0)
+}
+
+pub fn decode_sol_input<T: SolidityCodec>(input: &[u8]) -> Result<T, ExitCode> {
+    T::sol_decode(input, 0).map_err(|_| ExitCode::InputDecodeFailure)
+}
+
+/// Result is encoded as a tuple of return values, the same way as Solidity does
+pub fn encode_sol_output<T: SolidityCodec>(output: T) -> Vec<u8> {
+    let mut result = Vec::new();
+    (output,).sol_encode(&mut result);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        contract,
+        contract::{Contract, ExitCode},
+        evm::ContractInput,
+        storage::{MemoryStorage, StorageValue},
+        LowLevelSDK,
+    };
+    use alloc::{format, string::String, vec::Vec};
+    use fluentbase_codec::Encoder;
+    use fluentbase_types::{Address, Bytes, U256};
+
+    struct Counter {
+        value: StorageValue<u64, MemoryStorage>,
+    }
+
+    impl Default for Counter {
+        fn default() -> Self {
+            Self {
+                value: StorageValue::new(U256::ZERO),
+            }
+        }
+    }
+
+    #[contract]
+    impl Counter {
+        #[constructor]
+        fn init(&self, value: u64) {
+            self.value.set(&value);
+        }
+
+        #[method]
+        fn add(&self, delta: u64) -> Result<u64, ExitCode> {
+            let value = self
+                .value
+                .get()
+                .checked_add(delta)
+                .ok_or(ExitCode::IntegerOverflow)?;
+            self.value.set(&value);
+            Ok(value)
+        }
+
+        #[method]
+        fn greet(name: String) -> String {
+            format!("Hello, {}", name)
+        }
+
+        #[method(id = 1)]
+        fn reset(&self) {
+            self.value.clear();
+        }
+    }
+
+    fn method_input<T: Encoder<T>>(method_id: u32, params: T) -> Vec<u8> {
+        let mut input = method_id.to_be_bytes().to_vec();
+        input.extend(params.encode_to_vec(0));
+        input
+    }
+
+    #[test]
+    fn test_contract_dispatch() {
+        let counter = Counter::default();
+        counter.construct(&(10u64,).encode_to_vec(0)).unwrap();
+        assert_eq!(counter.value.get(), 10);
+        // method id is derived from the Rust signature (`add(uint64)`)
+        assert_eq!(
+            Counter::ADD_METHOD_ID,
+            crate::derive_keccak256_id!(fn add(delta: u64))
+        );
+        assert_eq!(Counter::RESET_METHOD_ID, 1);
+        assert_eq!(
+            counter.call(&method_input(Counter::ADD_METHOD_ID, (5u64,))),
+            Ok(15u64.encode_to_vec(0))
+        );
+        // state is kept in storage, so it's shared by all instances
+        assert_eq!(
+            Counter::default().call(&method_input(Counter::ADD_METHOD_ID, (5u64,))),
+            Ok(20u64.encode_to_vec(0))
+        );
+        assert_eq!(
+            counter.call(&method_input(Counter::ADD_METHOD_ID, (u64::MAX,))),
+            Err(ExitCode::IntegerOverflow)
+        );
+        assert_eq!(
+            counter.call(&Counter::RESET_METHOD_ID.to_be_bytes()),
+            Ok(Vec::new())
+        );
+        assert_eq!(counter.value.get(), 0);
+        // unknown method, missing method id and malformed params
+        assert_eq!(
+            counter.call(&method_input(0xdeadbeef, (5u64,))),
+            Err(ExitCode::NotSupportedCall)
+        );
+        assert_eq!(counter.call(&[0, 1]), Err(ExitCode::NotSupportedCall));
+        assert_eq!(
+            counter.call(&Counter::ADD_METHOD_ID.to_be_bytes()),
+            Err(ExitCode::InputDecodeFailure)
+        );
+    }
+
+    #[test]
+    fn test_contract_entrypoints() {
+        let contract_input = |input: Vec<u8>| {
+            ContractInput {
+                contract_input: Bytes::from(input),
+                ..Default::default()
+            }
+            .encode_to_vec(0)
+        };
+        LowLevelSDK::with_test_input(contract_input((10u64,).encode_to_vec(0)));
+        deploy();
+        assert_eq!(LowLevelSDK::get_test_output(), Vec::<u8>::new());
+        let input = method_input(Counter::GREET_METHOD_ID, (String::from("Fluent"),));
+        LowLevelSDK::with_test_input(contract_input(input));
+        main();
+        assert_eq!(
+            LowLevelSDK::get_test_output(),
+            String::from("Hello, Fluent").encode_to_vec(0)
+        );
+    }
+
+    // `deploy` and `main` are generated per module
+    #[allow(dead_code)]
+    mod token {
+        use super::*;
+
+        #[derive(Default)]
+        pub(super) struct Token;
+
+        #[contract(solidity)]
+        impl Token {
+            #[method(name = "balanceOf")]
+            fn balance_of(&self, _owner: Address) -> U256 {
+                U256::from(100)
+            }
+
+            #[fallback]
+            fn receive(&self) -> bool {
+                true
+            }
+        }
+    }
+
+    #[test]
+    fn test_solidity_contract() {
+        use token::Token;
+        // selector of `balanceOf(address)`
+        assert_eq!(Token::BALANCE_OF_METHOD_ID, 0x70a08231);
+        let mut input = Token::BALANCE_OF_METHOD_ID.to_be_bytes().to_vec();
+        input.extend([0u8; 12]);
+        input.extend_from_slice(Address::repeat_byte(0x11).as_slice());
+        let mut output = [0u8; 32];
+        output[31] = 100;
+        assert_eq!(Token::default().call(&input), Ok(output.to_vec()));
+        assert_eq!(
+            Token::default().call(&input[..20]),
+            Err(ExitCode::InputDecodeFailure)
+        );
+        // fallback handles input without method id and unknown method ids
+        let mut output = [0u8; 32];
+        output[31] = 1;
+        assert_eq!(Token::default().call(&[]), Ok(output.to_vec()));
+        assert_eq!(
+            Token::default().call(&0xdeadbeefu32.to_be_bytes()),
+            Ok(output.to_vec())
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use crate::{LowLevelAPI, LowLevelSDK};
 use alloc::{vec, vec::Vec};
-use fluentbase_codec::BufferDecoder;
-use fluentbase_codec::Encoder;
+use fluentbase_codec::{BufferDecoder, ByteSource, Encoder};
 use fluentbase_codec_derive::Codec;
-use fluentbase_types::{Address, Bytes, B256, U256};
+use fluentbase_types::{Address, Bytes, ExitCode, B256, U256};
 
+/// Input passed to every contract. It keeps the unversioned layout, because the deployed ECL/WCL
+/// assets decode it (as well as `*MethodInput` structs), switching to `#[codec(version = 1)]`
+/// changes the layout and must be done together with rebuilding these assets
 #[derive(Clone, Debug, Default, Codec)]
+#[codec(reader)]
 pub struct ContractInput {
     // journal
     pub journal_checkpoint: u64,
@@ -33,67 +36,35 @@ pub struct ContractInput {
     pub contract_input: Bytes,
 }
 
-macro_rules! impl_reader_helper {
-    (@header $input_type:ty, $return_typ:ty) => {
-        let mut buffer: [u8; <$input_type>::FIELD_SIZE] = [0; <$input_type>::FIELD_SIZE];
-        LowLevelSDK::sys_read(&mut buffer, <$input_type>::FIELD_OFFSET as u32);
-        let mut result: $return_typ = Default::default();
-        _ = <$input_type>::decode_field_header_at(&buffer, 0, &mut result);
-        result
-    };
-    (@dynamic $input_type:ty, $return_typ:ty) => {
-        let mut buffer: [u8; <$input_type>::FIELD_SIZE] = [0; <$input_type>::FIELD_SIZE];
-        LowLevelSDK::sys_read(&mut buffer, <$input_type>::FIELD_OFFSET as u32);
-        let mut result: $return_typ = Default::default();
-        let (offset, length) = <$input_type>::decode_field_header_at(&buffer, 0, &mut result);
-        if length > 0 {
-            let mut buffer2 = vec![0; offset + length];
-            buffer2[0..<$input_type>::FIELD_SIZE].copy_from_slice(&buffer);
-            LowLevelSDK::sys_read(
-                &mut buffer2.as_mut_slice()[offset..(offset + length)],
-                offset as u32,
-            );
-            <$input_type>::decode_field_body_at(&buffer2, 0, &mut result);
-        }
-        result
-    };
-    (@size $input_type:ty, $return_typ:ty) => {
-        let mut buffer: [u8; <$input_type>::FIELD_SIZE] = [0; <$input_type>::FIELD_SIZE];
-        LowLevelSDK::sys_read(&mut buffer, <$input_type>::FIELD_OFFSET as u32);
-        let mut result: $return_typ = Default::default();
-        let (_, length) = <$input_type>::decode_field_header_at(&buffer, 0, &mut result);
-        length as u32
-    };
+/// Contract input available through `sys_read`
+#[derive(Default, Clone, Copy)]
+pub struct InputSource;
+
+impl ByteSource for InputSource {
+    #[inline(always)]
+    fn read(&self, target: &mut [u8], offset: usize) {
+        LowLevelSDK::sys_read(target, offset as u32);
+    }
 }
+
+/// Output of the last call available through `sys_read_output`
+#[derive(Default, Clone, Copy)]
+pub struct OutputSource;
+
+impl ByteSource for OutputSource {
+    #[inline(always)]
+    fn read(&self, target: &mut [u8], offset: usize) {
+        LowLevelSDK::sys_read_output(target.as_mut_ptr(), offset as u32, target.len() as u32);
+    }
+}
+
 macro_rules! impl_reader_func {
-    (fn $fn_name:ident() -> $return_typ:ty, $input_type:ty) => {
-        paste::paste! {
-            #[inline(always)]
-            pub fn $fn_name() -> $return_typ {
-                impl_reader_helper!{@header <ContractInput as IContractInput>::$input_type, $return_typ}
-            }
-        }
-    };
-    (fn $fn_name:ident(result: &mut $return_typ:ty), $input_type:ty) => {
-        paste::paste! {
-            #[inline(always)]
-            pub fn $fn_name(result: &mut $return_typ) {
-                let mut buffer: [u8; <<ContractInput as IContractInput>::$input_type>::FIELD_SIZE] = [0; <<ContractInput as IContractInput>::$input_type>::FIELD_SIZE];
-                LowLevelSDK::sys_read(&mut buffer, <<ContractInput as IContractInput>::$input_type>::FIELD_OFFSET as u32);
-                _ = <<ContractInput as IContractInput>::$input_type>::decode_field_header_at(&buffer, 0, result);
-            }
-        }
-    };
-    (@dynamic fn $fn_name:ident() -> $return_typ:ty, $input_type:ty) => {
-        paste::paste! {
-            #[inline(always)]
-            pub fn $fn_name() -> $return_typ {
-                impl_reader_helper!{@dynamic <ContractInput as IContractInput>::$input_type, $return_typ}
-            }
-            #[inline(always)]
-            pub fn [<$fn_name _size>]() -> u32 {
-                impl_reader_helper!{@size <ContractInput as IContractInput>::$input_type, $return_typ}
-            }
+    (fn $fn_name:ident() -> $return_typ:ty) => {
+        #[inline(always)]
+        pub fn $fn_name() -> $return_typ {
+            Self::input()
+                .and_then(|input| input.$fn_name().map_err(|_| ExitCode::InputDecodeFailure))
+                .unwrap_or_else(Self::halt_on_error)
         }
     };
 }
@@ -102,30 +73,62 @@ macro_rules! impl_reader_func {
 pub struct ExecutionContext;
 
 impl ExecutionContext {
+    /// Reader of contract input fields, only requested fields are read from the input, the
+    /// input header is validated once here
+    #[inline(always)]
+    pub fn input() -> Result<ContractInputReader<InputSource>, ExitCode> {
+        ContractInputReader::new(InputSource).map_err(|_| ExitCode::InputDecodeFailure)
+    }
+
+    /// Halts the contract when the input can't be read, accessors return a default value if the
+    /// execution continues (only possible in tests)
+    fn halt_on_error<T: Default>(exit_code: ExitCode) -> T {
+        LowLevelSDK::sys_halt(exit_code.into_i32());
+        T::default()
+    }
+
     // journal
-    impl_reader_func!(fn journal_checkpoint() -> u64, JournalCheckpoint);
+    impl_reader_func!(fn journal_checkpoint() -> u64);
     // block info
-    impl_reader_func!(fn block_chain_id() -> u64, BlockChainId);
-    impl_reader_func!(fn block_coinbase() -> Address, BlockCoinbase);
-    impl_reader_func!(fn block_timestamp() -> u64, BlockTimestamp);
-    impl_reader_func!(fn block_number() -> u64, BlockNumber);
-    impl_reader_func!(fn block_difficulty() -> u64, BlockDifficulty);
-    impl_reader_func!(fn block_gas_limit() -> u64, BlockGasLimit);
-    impl_reader_func!(fn block_base_fee() -> U256, BlockBaseFee);
+    impl_reader_func!(fn block_chain_id() -> u64);
+    impl_reader_func!(fn block_coinbase() -> Address);
+    impl_reader_func!(fn block_timestamp() -> u64);
+    impl_reader_func!(fn block_number() -> u64);
+    impl_reader_func!(fn block_difficulty() -> u64);
+    impl_reader_func!(fn block_gas_limit() -> u64);
+    impl_reader_func!(fn block_base_fee() -> U256);
     // tx info
-    impl_reader_func!(fn tx_gas_limit() -> u64, TxGasLimit);
-    impl_reader_func!(fn tx_nonce() -> u64, TxNonce);
-    impl_reader_func!(fn tx_gas_price() -> U256, TxGasPrice);
-    impl_reader_func!(fn tx_gas_priority_fee() -> Option<U256>, TxGasPriorityFee);
-    impl_reader_func!(fn tx_caller() -> Address, TxCaller);
-    impl_reader_func!(fn tx_access_list() -> Vec<(Address, Vec<U256>)>, TxAccessList);
+    impl_reader_func!(fn tx_gas_limit() -> u64);
+    impl_reader_func!(fn tx_nonce() -> u64);
+    impl_reader_func!(fn tx_gas_price() -> U256);
+    impl_reader_func!(fn tx_gas_priority_fee() -> Option<U256>);
+    impl_reader_func!(fn tx_caller() -> Address);
+    impl_reader_func!(fn tx_access_list() -> Vec<(Address, Vec<U256>)>);
     // contract info
-    impl_reader_func!(fn contract_gas_limit() -> u64, ContractGasLimit);
-    impl_reader_func!(fn contract_address() -> Address, ContractAddress);
-    impl_reader_func!(fn contract_caller() -> Address, ContractCaller);
-    impl_reader_func!(fn contract_value() -> U256, ContractValue);
-    impl_reader_func!(fn contract_is_static() -> bool, ContractIsStatic);
-    impl_reader_func!(@dynamic fn contract_input() -> Bytes, ContractInput);
+    impl_reader_func!(fn contract_gas_limit() -> u64);
+    impl_reader_func!(fn contract_address() -> Address);
+    impl_reader_func!(fn contract_caller() -> Address);
+    impl_reader_func!(fn contract_value() -> U256);
+    impl_reader_func!(fn contract_is_static() -> bool);
+    impl_reader_func!(fn contract_input() -> Bytes);
+
+    #[inline(always)]
+    pub fn contract_input_size() -> u32 {
+        Self::input()
+            .map(|input| input.contract_input_size())
+            .unwrap_or_else(Self::halt_on_error)
+    }
+
+    /// Offset and length of the contract input (calldata) in the input
+    #[inline(always)]
+    pub fn contract_input_location() -> (u32, u32) {
+        Self::input()
+            .map(|input| {
+                let (offset, length) = input.contract_input_location();
+                (offset as u32, length as u32)
+            })
+            .unwrap_or_else(Self::halt_on_error)
+    }
 
     pub fn fast_return_and_exit<R: Into<Bytes>>(&self, return_data: R, exit_code: i32) {
         LowLevelSDK::sys_write(return_data.into().as_ref());
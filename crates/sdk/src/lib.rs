@@ -4,11 +4,15 @@
 extern crate alloc;
 extern crate core;
 extern crate lol_alloc;
+// generated code refers to `fluentbase_sdk`, it's required to use `#[contract]` inside the crate
+extern crate self as fluentbase_sdk;
 
 pub struct LowLevelSDK;
 
+pub mod contract;
 pub mod evm;
 mod sdk;
+pub mod storage;
 
 pub use sdk::LowLevelAPI;
 
@@ -21,6 +25,8 @@ mod rwasm;
 mod types;
 pub use types::*;
 
+pub use fluentbase_codec_derive::derive_keccak256_id;
+pub use fluentbase_sdk_derive::{constructor, contract, fallback, method};
 pub use fluentbase_types::{Bytes20, Bytes32};
 
 #[cfg(not(feature = "std"))]
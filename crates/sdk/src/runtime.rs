@@ -5,17 +5,47 @@ use fluentbase_runtime::types::InMemoryTrieDb;
 use fluentbase_runtime::zktrie::ZkTrieStateDb;
 use fluentbase_runtime::{
     instruction::{
-        crypto_ecrecover::CryptoEcrecover, crypto_keccak256::CryptoKeccak256,
-        crypto_poseidon::CryptoPoseidon, crypto_poseidon2::CryptoPoseidon2,
-        jzkt_checkpoint::JzktCheckpoint, jzkt_commit::JzktCommit,
-        jzkt_compute_root::JzktComputeRoot, jzkt_emit_log::JzktEmitLog, jzkt_get::JzktGet,
-        jzkt_open::JzktOpen, jzkt_preimage_copy::JzktPreimageCopy,
-        jzkt_preimage_size::JzktPreimageSize, jzkt_remove::JzktRemove, jzkt_rollback::JzktRollback,
-        jzkt_update::JzktUpdate, jzkt_update_preimage::JzktUpdatePreimage,
-        sys_exec_hash::SysExecHash, sys_forward_output::SysForwardOutput, sys_fuel::SysFuel,
-        sys_halt::SysHalt, sys_input_size::SysInputSize, sys_output_size::SysOutputSize,
-        sys_read::SysRead, sys_read_output::SysReadOutput, sys_state::SysState,
-        sys_write::SysWrite, wasm_to_rwasm::WasmToRwasm, wasm_to_rwasm_size::WasmToRwasmSize,
+        crypto_blake2f::CryptoBlake2f,
+        crypto_bls12381::{
+            CryptoBls12381G1Add, CryptoBls12381G1Msm, CryptoBls12381G2Add, CryptoBls12381G2Msm,
+            CryptoBls12381MapFp2ToG2, CryptoBls12381MapFpToG1, CryptoBls12381Pairing,
+        },
+        crypto_bn128::{CryptoBn128Add, CryptoBn128Mul, CryptoBn128Pairing},
+        crypto_ecrecover::CryptoEcrecover,
+        crypto_ed25519_verify::CryptoEd25519Verify,
+        crypto_keccak256::CryptoKeccak256,
+        crypto_modexp::CryptoModexp,
+        crypto_poseidon::CryptoPoseidon,
+        crypto_poseidon2::CryptoPoseidon2,
+        crypto_ripemd160::CryptoRipemd160,
+        crypto_secp256r1_verify::CryptoSecp256r1Verify,
+        crypto_sha256::CryptoSha256,
+        jzkt_checkpoint::JzktCheckpoint,
+        jzkt_checkpoint_commit::JzktCheckpointCommit,
+        jzkt_commit::JzktCommit,
+        jzkt_compute_root::JzktComputeRoot,
+        jzkt_emit_log::JzktEmitLog,
+        jzkt_get::JzktGet,
+        jzkt_open::JzktOpen,
+        jzkt_preimage_copy::JzktPreimageCopy,
+        jzkt_preimage_size::JzktPreimageSize,
+        jzkt_remove::JzktRemove,
+        jzkt_rollback::JzktRollback,
+        jzkt_update::JzktUpdate,
+        jzkt_update_preimage::JzktUpdatePreimage,
+        jzkt_update_storage_slot::JzktUpdateStorageSlot,
+        sys_exec_hash::SysExecHash,
+        sys_forward_output::SysForwardOutput,
+        sys_fuel::SysFuel,
+        sys_halt::SysHalt,
+        sys_input_size::SysInputSize,
+        sys_output_size::SysOutputSize,
+        sys_read::SysRead,
+        sys_read_output::SysReadOutput,
+        sys_state::SysState,
+        sys_write::SysWrite,
+        wasm_to_rwasm::WasmToRwasm,
+        wasm_to_rwasm_size::WasmToRwasmSize,
     },
     DefaultEmptyRuntimeDatabase, RuntimeContext,
 };
@@ -53,6 +83,19 @@ where
     })
 }
 
+fn write_bls12381_result<const N: usize>(
+    result: Result<[u8; N], ExitCode>,
+    output_ptr: *mut u8,
+) -> i32 {
+    match result {
+        Ok(output) => {
+            unsafe { ptr::copy(output.as_ptr(), output_ptr, N) };
+            ExitCode::Ok.into_i32()
+        }
+        Err(err) => err.into_i32(),
+    }
+}
+
 impl LowLevelAPI for LowLevelSDK {
     fn crypto_keccak256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8) {
         let result = CryptoKeccak256::fn_impl(unsafe {
@@ -102,6 +145,140 @@ impl LowLevelAPI for LowLevelSDK {
         output.copy_from_slice(&result);
     }
 
+    fn crypto_sha256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8) {
+        let result = CryptoSha256::fn_impl(unsafe {
+            &*ptr::slice_from_raw_parts(data_offset, data_len as usize)
+        });
+        unsafe {
+            ptr::copy(result.as_ptr(), output32_offset, 32);
+        }
+    }
+
+    fn crypto_ripemd160(data_offset: *const u8, data_len: u32, output20_offset: *mut u8) {
+        let result = CryptoRipemd160::fn_impl(unsafe {
+            &*ptr::slice_from_raw_parts(data_offset, data_len as usize)
+        });
+        unsafe {
+            ptr::copy(result.as_ptr(), output20_offset, 20);
+        }
+    }
+
+    fn crypto_blake2f(
+        rounds: u32,
+        h64_ptr: *mut u8,
+        m128_ptr: *const u8,
+        t16_ptr: *const u8,
+        f: bool,
+    ) {
+        let h = unsafe { &*ptr::slice_from_raw_parts(h64_ptr, 64) };
+        let m = unsafe { &*ptr::slice_from_raw_parts(m128_ptr, 128) };
+        let t = unsafe { &*ptr::slice_from_raw_parts(t16_ptr, 16) };
+        let result = CryptoBlake2f::fn_impl(rounds, h, m, t, f);
+        unsafe {
+            ptr::copy(result.as_ptr(), h64_ptr, 64);
+        }
+    }
+
+    fn crypto_bn128_add(p1_ptr: *const u8, p2_ptr: *const u8, output64_ptr: *mut u8) -> i32 {
+        let p1 = unsafe { &*ptr::slice_from_raw_parts(p1_ptr, 64) };
+        let p2 = unsafe { &*ptr::slice_from_raw_parts(p2_ptr, 64) };
+        match CryptoBn128Add::fn_impl(p1, p2) {
+            Ok(result) => {
+                unsafe { ptr::copy(result.as_ptr(), output64_ptr, 64) };
+                ExitCode::Ok.into_i32()
+            }
+            Err(err) => err.into_i32(),
+        }
+    }
+
+    fn crypto_bn128_mul(p_ptr: *const u8, scalar32_ptr: *const u8, output64_ptr: *mut u8) -> i32 {
+        let p = unsafe { &*ptr::slice_from_raw_parts(p_ptr, 64) };
+        let scalar = unsafe { &*ptr::slice_from_raw_parts(scalar32_ptr, 32) };
+        match CryptoBn128Mul::fn_impl(p, scalar) {
+            Ok(result) => {
+                unsafe { ptr::copy(result.as_ptr(), output64_ptr, 64) };
+                ExitCode::Ok.into_i32()
+            }
+            Err(err) => err.into_i32(),
+        }
+    }
+
+    fn crypto_bn128_pairing(pairs_ptr: *const u8, pairs_len: u32, output32_ptr: *mut u8) -> i32 {
+        let pairs = unsafe { &*ptr::slice_from_raw_parts(pairs_ptr, pairs_len as usize) };
+        match CryptoBn128Pairing::fn_impl(pairs) {
+            Ok(success) => {
+                let mut output = [0u8; 32];
+                output[31] = success as u8;
+                unsafe { ptr::copy(output.as_ptr(), output32_ptr, 32) };
+                ExitCode::Ok.into_i32()
+            }
+            Err(err) => err.into_i32(),
+        }
+    }
+
+    fn crypto_modexp(base: &[u8], exponent: &[u8], modulus: &[u8], output_ptr: *mut u8) {
+        let result = CryptoModexp::fn_impl(base, exponent, modulus);
+        unsafe {
+            ptr::copy(result.as_ptr(), output_ptr, result.len());
+        }
+    }
+
+    fn crypto_secp256r1_verify(
+        digest32_ptr: *const u8,
+        sig64_ptr: *const u8,
+        pk64_ptr: *const u8,
+    ) -> bool {
+        let digest = unsafe { &*ptr::slice_from_raw_parts(digest32_ptr, 32) };
+        let sig = unsafe { &*ptr::slice_from_raw_parts(sig64_ptr, 64) };
+        let pk = unsafe { &*ptr::slice_from_raw_parts(pk64_ptr, 64) };
+        CryptoSecp256r1Verify::fn_impl(digest, sig, pk)
+    }
+
+    fn crypto_ed25519_verify(msg: &[u8], sig64_ptr: *const u8, pk32_ptr: *const u8) -> bool {
+        let sig = unsafe { &*ptr::slice_from_raw_parts(sig64_ptr, 64) };
+        let pk = unsafe { &*ptr::slice_from_raw_parts(pk32_ptr, 32) };
+        CryptoEd25519Verify::fn_impl(msg, sig, pk)
+    }
+
+    fn crypto_bls12381_g1_add(p1_ptr: *const u8, p2_ptr: *const u8, output128_ptr: *mut u8) -> i32 {
+        let p1 = unsafe { &*ptr::slice_from_raw_parts(p1_ptr, 128) };
+        let p2 = unsafe { &*ptr::slice_from_raw_parts(p2_ptr, 128) };
+        write_bls12381_result(CryptoBls12381G1Add::fn_impl(p1, p2), output128_ptr)
+    }
+
+    fn crypto_bls12381_g1_msm(pairs: &[u8], output128_ptr: *mut u8) -> i32 {
+        write_bls12381_result(CryptoBls12381G1Msm::fn_impl(pairs), output128_ptr)
+    }
+
+    fn crypto_bls12381_g2_add(p1_ptr: *const u8, p2_ptr: *const u8, output256_ptr: *mut u8) -> i32 {
+        let p1 = unsafe { &*ptr::slice_from_raw_parts(p1_ptr, 256) };
+        let p2 = unsafe { &*ptr::slice_from_raw_parts(p2_ptr, 256) };
+        write_bls12381_result(CryptoBls12381G2Add::fn_impl(p1, p2), output256_ptr)
+    }
+
+    fn crypto_bls12381_g2_msm(pairs: &[u8], output256_ptr: *mut u8) -> i32 {
+        write_bls12381_result(CryptoBls12381G2Msm::fn_impl(pairs), output256_ptr)
+    }
+
+    fn crypto_bls12381_pairing(pairs: &[u8], output32_ptr: *mut u8) -> i32 {
+        let result = CryptoBls12381Pairing::fn_impl(pairs).map(|success| {
+            let mut output = [0u8; 32];
+            output[31] = success as u8;
+            output
+        });
+        write_bls12381_result(result, output32_ptr)
+    }
+
+    fn crypto_bls12381_map_fp_to_g1(fp64_ptr: *const u8, output128_ptr: *mut u8) -> i32 {
+        let fp = unsafe { &*ptr::slice_from_raw_parts(fp64_ptr, 64) };
+        write_bls12381_result(CryptoBls12381MapFpToG1::fn_impl(fp), output128_ptr)
+    }
+
+    fn crypto_bls12381_map_fp2_to_g2(fp2_128_ptr: *const u8, output256_ptr: *mut u8) -> i32 {
+        let fp2 = unsafe { &*ptr::slice_from_raw_parts(fp2_128_ptr, 128) };
+        write_bls12381_result(CryptoBls12381MapFp2ToG2::fn_impl(fp2), output256_ptr)
+    }
+
     fn sys_read(target: &mut [u8], offset: u32) {
         let result =
             with_context(|ctx| SysRead::fn_impl(ctx, offset, target.len() as u32).unwrap());
@@ -217,6 +394,16 @@ impl LowLevelAPI for LowLevelSDK {
         let preimage = unsafe { &*ptr::slice_from_raw_parts(preimage_ptr, preimage_len as usize) };
         with_context_mut(|ctx| JzktUpdatePreimage::fn_impl(ctx, key, field, preimage).unwrap())
     }
+    fn jzkt_update_storage_slot(
+        key32_ptr: *const u8,
+        address20_ptr: *const u8,
+        slot32_ptr: *const u8,
+    ) {
+        let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
+        let address = unsafe { &*ptr::slice_from_raw_parts(address20_ptr, 20) };
+        let slot = unsafe { &*ptr::slice_from_raw_parts(slot32_ptr, 32) };
+        with_context_mut(|ctx| JzktUpdateStorageSlot::fn_impl(ctx, key, address, slot));
+    }
     fn jzkt_remove(key32_ptr: *const u8) {
         let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
         with_context_mut(|ctx| JzktRemove::fn_impl(ctx, key).unwrap())
@@ -255,6 +442,9 @@ impl LowLevelAPI for LowLevelSDK {
     fn jzkt_rollback(checkpoint: u64) {
         with_context_mut(|ctx| JzktRollback::fn_impl(ctx, JournalCheckpoint::from_u64(checkpoint)));
     }
+    fn jzkt_checkpoint_commit() {
+        with_context_mut(|ctx| JzktCheckpointCommit::fn_impl(ctx));
+    }
     fn jzkt_preimage_size(key32_ptr: *const u8) -> u32 {
         let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
         return with_context_mut(|ctx| JzktPreimageSize::fn_impl(ctx, key).unwrap());
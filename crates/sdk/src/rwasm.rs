@@ -1,9 +1,14 @@
 use crate::{
     bindings::{
-        _crypto_ecrecover, _crypto_keccak256, _crypto_poseidon, _crypto_poseidon2, _debug_log,
-        _jzkt_checkpoint, _jzkt_commit, _jzkt_compute_root, _jzkt_emit_log, _jzkt_get, _jzkt_open,
-        _jzkt_preimage_copy, _jzkt_preimage_size, _jzkt_remove, _jzkt_rollback, _jzkt_update,
-        _jzkt_update_preimage, _sys_exec_hash, _sys_forward_output, _sys_fuel, _sys_halt,
+        _crypto_blake2f, _crypto_bls12381_g1_add, _crypto_bls12381_g1_msm, _crypto_bls12381_g2_add,
+        _crypto_bls12381_g2_msm, _crypto_bls12381_map_fp2_to_g2, _crypto_bls12381_map_fp_to_g1,
+        _crypto_bls12381_pairing, _crypto_bn128_add, _crypto_bn128_mul, _crypto_bn128_pairing,
+        _crypto_ecrecover, _crypto_ed25519_verify, _crypto_keccak256, _crypto_modexp,
+        _crypto_poseidon, _crypto_poseidon2, _crypto_ripemd160, _crypto_secp256r1_verify,
+        _crypto_sha256, _debug_log, _jzkt_checkpoint, _jzkt_checkpoint_commit, _jzkt_commit,
+        _jzkt_compute_root, _jzkt_emit_log, _jzkt_get, _jzkt_open, _jzkt_preimage_copy,
+        _jzkt_preimage_size, _jzkt_remove, _jzkt_rollback, _jzkt_update, _jzkt_update_preimage,
+        _jzkt_update_storage_slot, _sys_exec_hash, _sys_forward_output, _sys_fuel, _sys_halt,
         _sys_input_size, _sys_output_size, _sys_read, _sys_read_output, _sys_state, _sys_write,
         _wasm_to_rwasm, _wasm_to_rwasm_size,
     },
@@ -109,6 +114,106 @@ impl LowLevelAPI for LowLevelSDK {
         unsafe { _crypto_ecrecover(digest32_ptr, sig64_ptr, output65_ptr, rec_id as u32) }
     }
 
+    #[inline(always)]
+    fn crypto_sha256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8) {
+        unsafe { _crypto_sha256(data_offset, data_len, output32_offset) }
+    }
+
+    #[inline(always)]
+    fn crypto_ripemd160(data_offset: *const u8, data_len: u32, output20_offset: *mut u8) {
+        unsafe { _crypto_ripemd160(data_offset, data_len, output20_offset) }
+    }
+
+    #[inline(always)]
+    fn crypto_blake2f(
+        rounds: u32,
+        h64_ptr: *mut u8,
+        m128_ptr: *const u8,
+        t16_ptr: *const u8,
+        f: bool,
+    ) {
+        unsafe { _crypto_blake2f(rounds, h64_ptr, m128_ptr, t16_ptr, f as u32) }
+    }
+
+    #[inline(always)]
+    fn crypto_bn128_add(p1_ptr: *const u8, p2_ptr: *const u8, output64_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bn128_add(p1_ptr, p2_ptr, output64_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bn128_mul(p_ptr: *const u8, scalar32_ptr: *const u8, output64_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bn128_mul(p_ptr, scalar32_ptr, output64_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bn128_pairing(pairs_ptr: *const u8, pairs_len: u32, output32_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bn128_pairing(pairs_ptr, pairs_len, output32_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_modexp(base: &[u8], exponent: &[u8], modulus: &[u8], output_ptr: *mut u8) {
+        unsafe {
+            _crypto_modexp(
+                base.as_ptr(),
+                base.len() as u32,
+                exponent.as_ptr(),
+                exponent.len() as u32,
+                modulus.as_ptr(),
+                modulus.len() as u32,
+                output_ptr,
+            )
+        }
+    }
+
+    #[inline(always)]
+    fn crypto_secp256r1_verify(
+        digest32_ptr: *const u8,
+        sig64_ptr: *const u8,
+        pk64_ptr: *const u8,
+    ) -> bool {
+        unsafe { _crypto_secp256r1_verify(digest32_ptr, sig64_ptr, pk64_ptr) != 0 }
+    }
+
+    #[inline(always)]
+    fn crypto_ed25519_verify(msg: &[u8], sig64_ptr: *const u8, pk32_ptr: *const u8) -> bool {
+        unsafe { _crypto_ed25519_verify(msg.as_ptr(), msg.len() as u32, sig64_ptr, pk32_ptr) != 0 }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_g1_add(p1_ptr: *const u8, p2_ptr: *const u8, output128_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_g1_add(p1_ptr, p2_ptr, output128_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_g1_msm(pairs: &[u8], output128_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_g1_msm(pairs.as_ptr(), pairs.len() as u32, output128_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_g2_add(p1_ptr: *const u8, p2_ptr: *const u8, output256_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_g2_add(p1_ptr, p2_ptr, output256_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_g2_msm(pairs: &[u8], output256_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_g2_msm(pairs.as_ptr(), pairs.len() as u32, output256_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_pairing(pairs: &[u8], output32_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_pairing(pairs.as_ptr(), pairs.len() as u32, output32_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_map_fp_to_g1(fp64_ptr: *const u8, output128_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_map_fp_to_g1(fp64_ptr, output128_ptr) }
+    }
+
+    #[inline(always)]
+    fn crypto_bls12381_map_fp2_to_g2(fp2_128_ptr: *const u8, output256_ptr: *mut u8) -> i32 {
+        unsafe { _crypto_bls12381_map_fp2_to_g2(fp2_128_ptr, output256_ptr) }
+    }
+
     #[inline(always)]
     fn jzkt_open(root32_ptr: *const u8) {
         unsafe { _jzkt_open(root32_ptr) }
@@ -137,6 +242,14 @@ impl LowLevelAPI for LowLevelSDK {
         unsafe { _jzkt_update_preimage(key32_ptr, field, preimage_ptr, preimage_len) }
     }
     #[inline(always)]
+    fn jzkt_update_storage_slot(
+        key32_ptr: *const u8,
+        address20_ptr: *const u8,
+        slot32_ptr: *const u8,
+    ) {
+        unsafe { _jzkt_update_storage_slot(key32_ptr, address20_ptr, slot32_ptr) }
+    }
+    #[inline(always)]
     fn jzkt_remove(key32_offset: *const u8) {
         unsafe { _jzkt_remove(key32_offset) }
     }
@@ -171,6 +284,10 @@ impl LowLevelAPI for LowLevelSDK {
         unsafe { _jzkt_rollback(checkpoint) }
     }
     #[inline(always)]
+    fn jzkt_checkpoint_commit() {
+        unsafe { _jzkt_checkpoint_commit() }
+    }
+    #[inline(always)]
     fn jzkt_preimage_size(hash32_ptr: *const u8) -> u32 {
         unsafe { _jzkt_preimage_size(hash32_ptr) }
     }
@@ -13,6 +13,32 @@ pub trait LowLevelAPI {
         output65_ptr: *mut u8,
         rec_id: u8,
     );
+    fn crypto_sha256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8);
+    fn crypto_ripemd160(data_offset: *const u8, data_len: u32, output20_offset: *mut u8);
+    fn crypto_blake2f(
+        rounds: u32,
+        h64_ptr: *mut u8,
+        m128_ptr: *const u8,
+        t16_ptr: *const u8,
+        f: bool,
+    );
+    fn crypto_bn128_add(p1_ptr: *const u8, p2_ptr: *const u8, output64_ptr: *mut u8) -> i32;
+    fn crypto_bn128_mul(p_ptr: *const u8, scalar32_ptr: *const u8, output64_ptr: *mut u8) -> i32;
+    fn crypto_bn128_pairing(pairs_ptr: *const u8, pairs_len: u32, output32_ptr: *mut u8) -> i32;
+    fn crypto_modexp(base: &[u8], exponent: &[u8], modulus: &[u8], output_ptr: *mut u8);
+    fn crypto_secp256r1_verify(
+        digest32_ptr: *const u8,
+        sig64_ptr: *const u8,
+        pk64_ptr: *const u8,
+    ) -> bool;
+    fn crypto_ed25519_verify(msg: &[u8], sig64_ptr: *const u8, pk32_ptr: *const u8) -> bool;
+    fn crypto_bls12381_g1_add(p1_ptr: *const u8, p2_ptr: *const u8, output128_ptr: *mut u8) -> i32;
+    fn crypto_bls12381_g1_msm(pairs: &[u8], output128_ptr: *mut u8) -> i32;
+    fn crypto_bls12381_g2_add(p1_ptr: *const u8, p2_ptr: *const u8, output256_ptr: *mut u8) -> i32;
+    fn crypto_bls12381_g2_msm(pairs: &[u8], output256_ptr: *mut u8) -> i32;
+    fn crypto_bls12381_pairing(pairs: &[u8], output32_ptr: *mut u8) -> i32;
+    fn crypto_bls12381_map_fp_to_g1(fp64_ptr: *const u8, output128_ptr: *mut u8) -> i32;
+    fn crypto_bls12381_map_fp2_to_g2(fp2_128_ptr: *const u8, output256_ptr: *mut u8) -> i32;
 
     fn sys_read(target: &mut [u8], offset: u32);
     fn sys_input_size() -> u32;
@@ -44,6 +70,11 @@ pub trait LowLevelAPI {
         preimage_ptr: *const u8,
         preimage_len: u32,
     ) -> bool;
+    fn jzkt_update_storage_slot(
+        key32_ptr: *const u8,
+        address20_ptr: *const u8,
+        slot32_ptr: *const u8,
+    );
     fn jzkt_remove(key32_offset: *const u8);
     fn jzkt_compute_root(output32_offset: *mut u8);
     fn jzkt_emit_log(
@@ -55,6 +86,7 @@ pub trait LowLevelAPI {
     );
     fn jzkt_commit(root32_offset: *mut u8);
     fn jzkt_rollback(checkpoint: u64);
+    fn jzkt_checkpoint_commit();
     fn jzkt_preimage_size(hash32_ptr: *const u8) -> u32;
     fn jzkt_preimage_copy(hash32_ptr: *const u8, preimage_ptr: *mut u8);
     fn wasm_to_rwasm_size(input_ptr: *const u8, input_len: u32) -> i32;
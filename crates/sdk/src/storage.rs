@@ -0,0 +1,1005 @@
+//! Typed contract storage.
+//!
+//! Slots are derived the same way as in Solidity, so WASM and EVM contracts can share storage
+//! layouts: `StorageValue` is located at its slot, value of `StorageMap` for the key `k` is located
+//! at `keccak256(k . slot)`, length of `StorageVec` is stored at its slot and elements start at
+//! `keccak256(slot)`.
+//!
+//! Values are stored in EVM format as well: value types (integers, bool, `Address`, `FixedBytes`)
+//! are stored right-aligned in the slot, `Bytes` and `String` use Solidity short/long layout, and
+//! elements of `StorageVec` are packed into one slot while they fit. Solidity packing of
+//! consecutive state variables isn't reproduced, every `StorageValue` occupies its own slot.
+//! Structs and enums with `#[derive(Codec)]` can be stored after `impl_storage_codec!`, they're
+//! encoded with `Codec` into a single `bytes` value, so to share a Solidity struct layout store its
+//! fields as separate items.
+use crate::{evm::ExecutionContext, Bytes32, LowLevelAPI, LowLevelSDK};
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use fluentbase_codec::{solidity::SolidityCodec, Encoder};
+pub use fluentbase_types::U256;
+use fluentbase_types::{Address, Bytes, FixedBytes, Uint, B256};
+
+const WORD_SIZE: usize = 32;
+
+const DOMAIN: Bytes32 = [0u8; 32];
+
+/// Calculates a key of the contract storage slot in the state trie
+#[inline(always)]
+pub fn calc_storage_key(address: &Address, slot32_le_ptr: *const u8) -> [u8; 32] {
+    let mut slot0: [u8; 32] = [0u8; 32];
+    let mut slot1: [u8; 32] = [0u8; 32];
+    // split slot32 into two 16 byte values (slot is always 32 bytes)
+    unsafe {
+        core::ptr::copy(slot32_le_ptr.offset(0), slot0.as_mut_ptr(), 16);
+        core::ptr::copy(slot32_le_ptr.offset(16), slot1.as_mut_ptr(), 16);
+    }
+    // pad address to 32 bytes value (11 bytes to avoid 254 overflow)
+    let mut address32: [u8; 32] = [0u8; 32];
+    address32[11..31].copy_from_slice(address.as_slice());
+    // compute a storage key, where formula is `p(address, p(slot_0, slot_1))`
+    let mut storage_key: [u8; 32] = [0u8; 32];
+    LowLevelSDK::crypto_poseidon2(
+        slot0.as_ptr(),
+        slot1.as_ptr(),
+        DOMAIN.as_ptr(),
+        storage_key.as_mut_ptr(),
+    );
+    LowLevelSDK::crypto_poseidon2(
+        address32.as_ptr(),
+        storage_key.as_ptr(),
+        DOMAIN.as_ptr(),
+        storage_key.as_mut_ptr(),
+    );
+    storage_key
+}
+
+/// Storage of 32-byte words, words are little-endian (the same as `U256::as_le_slice`)
+pub trait StorageBackend {
+    fn sload(slot: &U256) -> Bytes32;
+
+    fn sstore(slot: &U256, value: &Bytes32);
+}
+
+/// Storage of the current contract in the state trie, it's shared with EVM `SLOAD`/`SSTORE`
+pub struct JzktStorage;
+
+impl StorageBackend for JzktStorage {
+    fn sload(slot: &U256) -> Bytes32 {
+        let address = ExecutionContext::contract_address();
+        let storage_key = calc_storage_key(&address, slot.as_le_slice().as_ptr());
+        let mut value = Bytes32::default();
+        LowLevelSDK::jzkt_get(storage_key.as_ptr(), 0, value.as_mut_ptr());
+        value
+    }
+
+    fn sstore(slot: &U256, value: &Bytes32) {
+        let address = ExecutionContext::contract_address();
+        let slot = slot.as_le_slice().as_ptr();
+        let storage_key = calc_storage_key(&address, slot);
+        // storage values are not compressed
+        LowLevelSDK::jzkt_update(storage_key.as_ptr(), 0, value, WORD_SIZE as u32);
+        // the same as `SSTORE`, address and slot of the key are recorded for the state diff
+        LowLevelSDK::jzkt_update_storage_slot(storage_key.as_ptr(), address.as_ptr(), slot);
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static MEMORY_STORAGE: core::cell::RefCell<std::collections::HashMap<U256, Bytes32>> =
+        Default::default();
+}
+
+/// In-memory storage for unit tests, every thread has its own storage
+#[cfg(feature = "std")]
+pub struct MemoryStorage;
+
+#[cfg(feature = "std")]
+impl MemoryStorage {
+    pub fn reset() {
+        MEMORY_STORAGE.with(|storage| storage.borrow_mut().clear());
+    }
+}
+
+#[cfg(feature = "std")]
+impl StorageBackend for MemoryStorage {
+    fn sload(slot: &U256) -> Bytes32 {
+        MEMORY_STORAGE.with(|storage| storage.borrow().get(slot).copied().unwrap_or_default())
+    }
+
+    fn sstore(slot: &U256, value: &Bytes32) {
+        MEMORY_STORAGE.with(|storage| storage.borrow_mut().insert(*slot, *value));
+    }
+}
+
+/// Storage items use the state trie unless another backend is set explicitly, f.e.
+/// `StorageValue<T, MemoryStorage>` in unit tests
+pub type DefaultStorage = JzktStorage;
+
+fn keccak256(data: &[u8]) -> U256 {
+    let mut hash = Bytes32::default();
+    LowLevelSDK::crypto_keccak256(data.as_ptr(), data.len() as u32, hash.as_mut_ptr());
+    U256::from_be_bytes(hash)
+}
+
+fn load_word<S: StorageBackend>(slot: &U256) -> U256 {
+    U256::from_le_bytes(S::sload(slot))
+}
+
+fn store_word<S: StorageBackend>(slot: &U256, word: U256) {
+    S::sstore(slot, &word.to_le_bytes())
+}
+
+/// Slot of the data of `bytes`, `string` and array elements, `keccak256(slot)`
+fn data_slot(slot: &U256) -> U256 {
+    keccak256(&slot.to_be_bytes::<WORD_SIZE>())
+}
+
+/// Mask of the lowest `size` bytes
+fn value_mask(size: usize) -> U256 {
+    if size >= WORD_SIZE {
+        U256::MAX
+    } else {
+        (U256::from(1) << (size * 8)) - U256::from(1)
+    }
+}
+
+/// Replaces `size` bytes of the slot located at `offset` bytes from the lowest byte
+fn update_word<S: StorageBackend>(slot: &U256, offset: usize, size: usize, value: U256) {
+    let mask = value_mask(size) << (offset * 8);
+    let word = if mask == U256::MAX {
+        U256::ZERO
+    } else {
+        load_word::<S>(slot) & !mask
+    };
+    store_word::<S>(slot, word | ((value << (offset * 8)) & mask));
+}
+
+/// Length of the long `bytes` stored in its slot, it's clamped to `usize`, since the slot can
+/// contain any value (f.e. written by an EVM contract)
+fn bytes_length(word: U256) -> usize {
+    (word >> 1usize).saturating_to::<usize>()
+}
+
+/// Number of data slots of `bytes` with the given value of its slot
+fn bytes_data_slots(word: U256) -> usize {
+    if word.bit(0) {
+        bytes_length(word).div_ceil(WORD_SIZE)
+    } else {
+        0
+    }
+}
+
+/// Loads `bytes` stored the same way as in Solidity: data shorter than 32 bytes is stored in the
+/// slot itself with `length * 2` in the lowest byte, otherwise the slot contains `length * 2 + 1`
+/// and the data is stored at `keccak256(slot)`
+fn load_bytes<S: StorageBackend>(slot: &U256) -> Vec<u8> {
+    let word = load_word::<S>(slot);
+    if !word.bit(0) {
+        let length = word.byte(0) as usize / 2;
+        return word.to_be_bytes::<WORD_SIZE>()[..length].to_vec();
+    }
+    // length isn't trusted, so the data isn't preallocated
+    let length = bytes_length(word);
+    let data_slot = data_slot(slot);
+    let mut result = Vec::new();
+    for i in 0..length.div_ceil(WORD_SIZE) {
+        let word = load_word::<S>(&(data_slot + U256::from(i)));
+        result.extend_from_slice(&word.to_be_bytes::<WORD_SIZE>());
+    }
+    result.truncate(length);
+    result
+}
+
+/// Stores `bytes` and zeroes data slots of the previous value that aren't used anymore
+fn store_bytes<S: StorageBackend>(slot: &U256, data: &[u8]) {
+    let prev_data_slots = bytes_data_slots(load_word::<S>(slot));
+    let data_slot = data_slot(slot);
+    let data_slots = if data.len() < WORD_SIZE {
+        let mut word = [0u8; WORD_SIZE];
+        word[..data.len()].copy_from_slice(data);
+        word[WORD_SIZE - 1] = (data.len() * 2) as u8;
+        store_word::<S>(slot, U256::from_be_bytes(word));
+        0
+    } else {
+        store_word::<S>(slot, U256::from(data.len() * 2 + 1));
+        for (i, chunk) in data.chunks(WORD_SIZE).enumerate() {
+            let mut word = [0u8; WORD_SIZE];
+            word[..chunk.len()].copy_from_slice(chunk);
+            store_word::<S>(&(data_slot + U256::from(i)), U256::from_be_bytes(word));
+        }
+        data.len().div_ceil(WORD_SIZE)
+    };
+    for i in data_slots..prev_data_slots {
+        store_word::<S>(&(data_slot + U256::from(i)), U256::ZERO);
+    }
+}
+
+/// Value type, it's stored right-aligned in the slot the same as in Solidity (f.e. `address` is
+/// stored as `uint160`)
+pub trait StorageWord: Sized {
+    /// Number of bytes occupied by the value
+    const SIZE: usize;
+
+    fn to_word(&self) -> U256;
+
+    /// Decodes the value from the lowest `SIZE` bytes of the word, other bytes are zero
+    fn from_word(word: U256) -> Self;
+}
+
+impl StorageWord for bool {
+    const SIZE: usize = 1;
+
+    fn to_word(&self) -> U256 {
+        U256::from(*self as u8)
+    }
+
+    fn from_word(word: U256) -> Self {
+        !word.is_zero()
+    }
+}
+
+macro_rules! impl_storage_word_int {
+    ($($typ:ty => $unsigned:ty),*) => {
+        $(
+            impl StorageWord for $typ {
+                const SIZE: usize = core::mem::size_of::<$typ>();
+
+                fn to_word(&self) -> U256 {
+                    // signed integers are stored in two's complement truncated to the size
+                    U256::from(*self as $unsigned)
+                }
+
+                fn from_word(word: U256) -> Self {
+                    word.to::<$unsigned>() as $typ
+                }
+            }
+        )*
+    };
+}
+
+impl_storage_word_int!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128
+);
+
+impl StorageWord for Address {
+    const SIZE: usize = Address::len_bytes();
+
+    fn to_word(&self) -> U256 {
+        U256::from_be_slice(self.as_slice())
+    }
+
+    fn from_word(word: U256) -> Self {
+        Address::from_slice(&word.to_be_bytes::<WORD_SIZE>()[WORD_SIZE - Address::len_bytes()..])
+    }
+}
+
+/// Unsigned integers up to 256 bits
+impl<const BITS: usize, const LIMBS: usize> StorageWord for Uint<BITS, LIMBS> {
+    const SIZE: usize = Self::BYTES;
+
+    fn to_word(&self) -> U256 {
+        U256::from_le_slice(&self.as_le_bytes())
+    }
+
+    fn from_word(word: U256) -> Self {
+        Self::from_le_slice(&word.as_le_bytes()[..Self::BYTES])
+    }
+}
+
+/// Solidity `bytesN`, bytes are stored in the lowest `N` bytes of the slot
+impl<const N: usize> StorageWord for FixedBytes<N> {
+    const SIZE: usize = N;
+
+    fn to_word(&self) -> U256 {
+        U256::from_be_slice(self.as_slice())
+    }
+
+    fn from_word(word: U256) -> Self {
+        FixedBytes::from_slice(&word.to_be_bytes::<WORD_SIZE>()[WORD_SIZE - N..])
+    }
+}
+
+/// Value stored in the contract storage with the same layout as in Solidity, so storage written
+/// by EVM contracts can be read by WASM contracts and vice versa
+pub trait StorageCodec: Sized {
+    /// Number of bytes occupied in the slot, elements of `StorageVec` are packed into one slot
+    /// while they fit
+    const SIZE: usize;
+
+    /// Loads the value located at `offset` bytes from the lowest byte of the slot
+    fn load<S: StorageBackend>(slot: &U256, offset: usize) -> Self;
+
+    fn store<S: StorageBackend>(&self, slot: &U256, offset: usize);
+
+    /// Zeroes the value, including the data of `bytes` and `string`
+    fn clear<S: StorageBackend>(slot: &U256, offset: usize);
+}
+
+impl<T: StorageWord> StorageCodec for T {
+    const SIZE: usize = T::SIZE;
+
+    fn load<S: StorageBackend>(slot: &U256, offset: usize) -> Self {
+        T::from_word((load_word::<S>(slot) >> (offset * 8)) & value_mask(T::SIZE))
+    }
+
+    fn store<S: StorageBackend>(&self, slot: &U256, offset: usize) {
+        update_word::<S>(slot, offset, T::SIZE, self.to_word())
+    }
+
+    fn clear<S: StorageBackend>(slot: &U256, offset: usize) {
+        update_word::<S>(slot, offset, T::SIZE, U256::ZERO)
+    }
+}
+
+impl StorageCodec for Bytes {
+    const SIZE: usize = WORD_SIZE;
+
+    fn load<S: StorageBackend>(slot: &U256, _offset: usize) -> Self {
+        load_bytes::<S>(slot).into()
+    }
+
+    fn store<S: StorageBackend>(&self, slot: &U256, _offset: usize) {
+        store_bytes::<S>(slot, self)
+    }
+
+    fn clear<S: StorageBackend>(slot: &U256, _offset: usize) {
+        store_bytes::<S>(slot, &[])
+    }
+}
+
+/// Solidity doesn't validate `string`, so invalid UTF-8 is loaded with replacement characters,
+/// use `Bytes` to load raw data
+impl StorageCodec for String {
+    const SIZE: usize = WORD_SIZE;
+
+    fn load<S: StorageBackend>(slot: &U256, _offset: usize) -> Self {
+        match String::from_utf8(load_bytes::<S>(slot)) {
+            Ok(value) => value,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        }
+    }
+
+    fn store<S: StorageBackend>(&self, slot: &U256, _offset: usize) {
+        store_bytes::<S>(slot, self.as_bytes())
+    }
+
+    fn clear<S: StorageBackend>(slot: &U256, _offset: usize) {
+        store_bytes::<S>(slot, &[])
+    }
+}
+
+/// Loads a value encoded with `Codec` and stored as `bytes`, missing or malformed data is loaded
+/// as the default value
+pub fn load_encoded<T: Encoder<T> + Default, S: StorageBackend>(slot: &U256) -> T {
+    T::try_decode(&load_bytes::<S>(slot)).unwrap_or_default()
+}
+
+/// Stores a value encoded with `Codec` as `bytes`
+pub fn store_encoded<T: Encoder<T>, S: StorageBackend>(slot: &U256, value: &T) {
+    store_bytes::<S>(slot, &value.encode_to_vec(0))
+}
+
+/// Zeroes a value stored with `store_encoded`
+pub fn clear_encoded<S: StorageBackend>(slot: &U256) {
+    store_bytes::<S>(slot, &[])
+}
+
+/// Implements `StorageCodec` for types with `#[derive(Codec)]`, the value is encoded with `Codec`
+/// and stored as `bytes` (see `load_encoded`):
+///
+/// ```ignore
+/// #[derive(Default, Codec)]
+/// struct Config {
+///     owner: Address,
+///     limit: u64,
+/// }
+///
+/// impl_storage_codec!(Config);
+///
+/// const CONFIG: StorageValue<Config> = StorageValue::new(U256::ZERO);
+/// ```
+#[macro_export]
+macro_rules! impl_storage_codec {
+    ($($typ:ty),* $(,)?) => {
+        $(
+            impl $crate::storage::StorageCodec for $typ {
+                const SIZE: usize = 32;
+
+                fn load<S: $crate::storage::StorageBackend>(
+                    slot: &$crate::storage::U256,
+                    _offset: usize,
+                ) -> Self {
+                    $crate::storage::load_encoded::<Self, S>(slot)
+                }
+
+                fn store<S: $crate::storage::StorageBackend>(
+                    &self,
+                    slot: &$crate::storage::U256,
+                    _offset: usize,
+                ) {
+                    $crate::storage::store_encoded::<Self, S>(slot, self)
+                }
+
+                fn clear<S: $crate::storage::StorageBackend>(
+                    slot: &$crate::storage::U256,
+                    _offset: usize,
+                ) {
+                    $crate::storage::clear_encoded::<S>(slot)
+                }
+            }
+        )*
+    };
+}
+
+/// Storage item located at a slot, items can be nested into `StorageMap`
+pub trait StorageItem {
+    fn from_slot(slot: U256) -> Self;
+}
+
+/// Key of `StorageMap`, it's hashed the same way as a key of Solidity mapping: value types are
+/// padded to 32 bytes, `Bytes` and `String` are used as is
+pub trait StorageKey {
+    fn append_key(&self, out: &mut Vec<u8>);
+}
+
+macro_rules! impl_storage_key {
+    ($($typ:ty),*) => {
+        $(
+            impl StorageKey for $typ {
+                fn append_key(&self, out: &mut Vec<u8>) {
+                    self.sol_encode(out);
+                }
+            }
+        )*
+    };
+}
+
+impl_storage_key!(bool, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, Address, U256, B256);
+
+impl StorageKey for Bytes {
+    fn append_key(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+}
+
+impl StorageKey for String {
+    fn append_key(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+/// Single value, the same as Solidity state variable
+pub struct StorageValue<T, S = DefaultStorage> {
+    slot: U256,
+    _phantom: PhantomData<(T, S)>,
+}
+
+impl<T, S> StorageValue<T, S> {
+    pub const fn new(slot: U256) -> Self {
+        Self {
+            slot,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn slot(&self) -> U256 {
+        self.slot
+    }
+}
+
+impl<T: StorageCodec, S: StorageBackend> StorageValue<T, S> {
+    pub fn get(&self) -> T {
+        T::load::<S>(&self.slot, 0)
+    }
+
+    pub fn set(&self, value: &T) {
+        value.store::<S>(&self.slot, 0)
+    }
+
+    pub fn clear(&self) {
+        T::clear::<S>(&self.slot, 0)
+    }
+}
+
+impl<T, S> StorageItem for StorageValue<T, S> {
+    fn from_slot(slot: U256) -> Self {
+        Self::new(slot)
+    }
+}
+
+/// Mapping from `K` to the value `V`, or to the nested item (f.e.
+/// `StorageMap<Address, StorageMap<Address, U256>>`)
+pub struct StorageMap<K, V, S = DefaultStorage> {
+    slot: U256,
+    _phantom: PhantomData<(K, V, S)>,
+}
+
+impl<K: StorageKey, V, S> StorageMap<K, V, S> {
+    pub const fn new(slot: U256) -> Self {
+        Self {
+            slot,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Slot of the value, `keccak256(key . slot)`
+    pub fn slot_of(&self, key: &K) -> U256 {
+        let mut data = Vec::with_capacity(WORD_SIZE * 2);
+        key.append_key(&mut data);
+        data.extend_from_slice(&self.slot.to_be_bytes::<WORD_SIZE>());
+        keccak256(&data)
+    }
+}
+
+impl<K: StorageKey, V: StorageCodec, S: StorageBackend> StorageMap<K, V, S> {
+    pub fn entry(&self, key: &K) -> StorageValue<V, S> {
+        StorageValue::new(self.slot_of(key))
+    }
+
+    pub fn get(&self, key: &K) -> V {
+        self.entry(key).get()
+    }
+
+    pub fn set(&self, key: &K, value: &V) {
+        self.entry(key).set(value)
+    }
+
+    pub fn remove(&self, key: &K) {
+        self.entry(key).clear()
+    }
+}
+
+impl<K: StorageKey, V: StorageItem, S> StorageMap<K, V, S> {
+    /// Returns nested item for the key
+    pub fn at(&self, key: &K) -> V {
+        V::from_slot(self.slot_of(key))
+    }
+}
+
+impl<K: StorageKey, V, S> StorageItem for StorageMap<K, V, S> {
+    fn from_slot(slot: U256) -> Self {
+        Self::new(slot)
+    }
+}
+
+/// Dynamic array, the same as Solidity `T[]`
+pub struct StorageVec<T, S = DefaultStorage> {
+    slot: U256,
+    _phantom: PhantomData<(T, S)>,
+}
+
+impl<T, S> StorageVec<T, S> {
+    pub const fn new(slot: U256) -> Self {
+        Self {
+            slot,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: StorageCodec, S: StorageBackend> StorageVec<T, S> {
+    /// Number of elements packed into one slot
+    const ELEMENTS_PER_SLOT: usize = WORD_SIZE / T::SIZE;
+
+    /// Length stored in the slot, it's kept as `U256`, since the slot can contain any value (f.e.
+    /// written by an EVM contract)
+    fn raw_len(&self) -> U256 {
+        load_word::<S>(&self.slot)
+    }
+
+    /// Number of elements, clamped to `usize`
+    pub fn len(&self) -> usize {
+        self.raw_len().saturating_to::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.raw_len().is_zero()
+    }
+
+    fn set_len(&self, length: U256) {
+        store_word::<S>(&self.slot, length);
+    }
+
+    fn element_slot(&self, index: U256) -> U256 {
+        data_slot(&self.slot) + index / U256::from(Self::ELEMENTS_PER_SLOT)
+    }
+
+    fn element_offset(index: U256) -> usize {
+        (index % U256::from(Self::ELEMENTS_PER_SLOT)).to::<usize>() * T::SIZE
+    }
+
+    /// Slot of the element, `keccak256(slot) + index / elements_per_slot`, elements are packed the
+    /// same as in Solidity (f.e. 4 elements of `u64` per slot)
+    pub fn slot_of(&self, index: usize) -> U256 {
+        self.element_slot(U256::from(index))
+    }
+
+    /// Offset of the element in its slot in bytes, starting from the lowest byte
+    pub fn offset_of(&self, index: usize) -> usize {
+        Self::element_offset(U256::from(index))
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        if U256::from(index) >= self.raw_len() {
+            return None;
+        }
+        Some(T::load::<S>(&self.slot_of(index), self.offset_of(index)))
+    }
+
+    /// Replaces element at `index`, panics if the index is out of bounds
+    pub fn set(&self, index: usize, value: &T) {
+        let length = self.raw_len();
+        if U256::from(index) >= length {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                length, index
+            );
+        }
+        value.store::<S>(&self.slot_of(index), self.offset_of(index))
+    }
+
+    pub fn push(&self, value: &T) {
+        let length = self.raw_len();
+        value.store::<S>(&self.element_slot(length), Self::element_offset(length));
+        self.set_len(length + U256::from(1));
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let length = self.raw_len();
+        if length.is_zero() {
+            return None;
+        }
+        let index = length - U256::from(1);
+        let (slot, offset) = (self.element_slot(index), Self::element_offset(index));
+        let value = T::load::<S>(&slot, offset);
+        T::clear::<S>(&slot, offset);
+        self.set_len(index);
+        Some(value)
+    }
+
+    /// Removes all elements and zeroes their slots, the same as Solidity `delete`
+    pub fn clear(&self) {
+        let length = self.len();
+        if Self::ELEMENTS_PER_SLOT > 1 {
+            // packed elements have no data outside of their slots
+            let data_slot = data_slot(&self.slot);
+            for i in 0..length.div_ceil(Self::ELEMENTS_PER_SLOT) {
+                store_word::<S>(&(data_slot + U256::from(i)), U256::ZERO);
+            }
+        } else {
+            for index in 0..length {
+                T::clear::<S>(&self.slot_of(index), 0);
+            }
+        }
+        self.set_len(U256::ZERO)
+    }
+}
+
+impl<T, S> StorageItem for StorageVec<T, S> {
+    fn from_slot(slot: U256) -> Self {
+        Self::new(slot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::evm::ContractInput;
+    use alloc::vec;
+    use fluentbase_codec::Encoder;
+    use fluentbase_codec_derive::Codec;
+    use fluentbase_types::{address, b256};
+
+    const ZERO_SLOT: U256 = U256::ZERO;
+    const ONE_SLOT: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+    fn sstore(slot: U256, word: B256) {
+        MemoryStorage::sstore(&slot, &U256::from_be_bytes(word.0).to_le_bytes());
+    }
+
+    fn sload(slot: U256) -> B256 {
+        B256::from(U256::from_le_bytes(MemoryStorage::sload(&slot)).to_be_bytes::<32>())
+    }
+
+    #[test]
+    fn test_storage_value() {
+        let value = StorageValue::<U256, MemoryStorage>::new(ONE_SLOT);
+        assert_eq!(value.get(), U256::ZERO);
+        value.set(&U256::from(0x1234));
+        assert_eq!(value.get(), U256::from(0x1234));
+        assert_eq!(
+            MemoryStorage::sload(&ONE_SLOT),
+            U256::from(0x1234).to_le_bytes::<32>()
+        );
+        value.clear();
+        assert_eq!(value.get(), U256::ZERO);
+        let negative = StorageValue::<i16, MemoryStorage>::new(ONE_SLOT);
+        negative.set(&-2);
+        assert_eq!(negative.get(), -2);
+        assert_eq!(
+            sload(ONE_SLOT),
+            b256!("000000000000000000000000000000000000000000000000000000000000fffe")
+        );
+        // data of the long value is stored at `keccak256(slot)`
+        let bytes = StorageValue::<Bytes, MemoryStorage>::new(ZERO_SLOT);
+        assert_eq!(bytes.get(), Bytes::new());
+        let data = Bytes::from(vec![7u8; 100]);
+        bytes.set(&data);
+        assert_eq!(bytes.get(), data);
+        let data_slot = data_slot(&ZERO_SLOT);
+        assert_eq!(sload(data_slot + U256::from(3)), {
+            let mut word = B256::ZERO;
+            word[..4].copy_from_slice(&[7u8; 4]);
+            word
+        });
+        bytes.set(&Bytes::from_static(b"abc"));
+        assert_eq!(bytes.get(), Bytes::from_static(b"abc"));
+        // data slots of the previous value are zeroed
+        for i in 0..4 {
+            assert_eq!(sload(data_slot + U256::from(i)), B256::ZERO);
+        }
+        bytes.set(&data);
+        bytes.clear();
+        assert_eq!(bytes.get(), Bytes::new());
+        for i in 0..5 {
+            assert_eq!(sload(data_slot + U256::from(i)), B256::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_solidity_layout() {
+        // contract Token {
+        //     address owner;    // slot 0
+        //     string name;      // slot 1
+        //     string symbol;    // slot 2
+        //     uint64[] values;  // slot 3
+        // }
+        let owner = address!("1000000000000000000000000000000000000001");
+        let name = String::from("Token");
+        let symbol = String::from("Hello, World! Hello, World! Hello, World");
+        let words = [
+            (
+                U256::from(0),
+                b256!("0000000000000000000000001000000000000000000000000000000000000001"),
+            ),
+            // short string, `length * 2` in the lowest byte
+            (
+                U256::from(1),
+                b256!("546f6b656e00000000000000000000000000000000000000000000000000000a"),
+            ),
+            // long string, `length * 2 + 1` in the slot and data at `keccak256(uint256(2))`
+            (
+                U256::from(2),
+                b256!("0000000000000000000000000000000000000000000000000000000000000051"),
+            ),
+            (
+                U256::from_be_bytes(
+                    b256!("405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace").0,
+                ),
+                b256!("48656c6c6f2c20576f726c64212048656c6c6f2c20576f726c64212048656c6c"),
+            ),
+            (
+                U256::from_be_bytes(
+                    b256!("405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5acf").0,
+                ),
+                b256!("6f2c20576f726c64000000000000000000000000000000000000000000000000"),
+            ),
+            // 4 elements of `uint64` are packed into one slot at `keccak256(uint256(3))`
+            (
+                U256::from(3),
+                b256!("0000000000000000000000000000000000000000000000000000000000000005"),
+            ),
+            (
+                U256::from_be_bytes(
+                    b256!("c2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85b").0,
+                ),
+                b256!("0000000000000004000000000000000300000000000000020000000000000001"),
+            ),
+            (
+                U256::from_be_bytes(
+                    b256!("c2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85c").0,
+                ),
+                b256!("0000000000000000000000000000000000000000000000000000000000000005"),
+            ),
+        ];
+        // storage written by Solidity
+        for (slot, word) in words {
+            sstore(slot, word);
+        }
+        assert_eq!(
+            StorageValue::<Address, MemoryStorage>::new(U256::from(0)).get(),
+            owner
+        );
+        assert_eq!(
+            StorageValue::<String, MemoryStorage>::new(U256::from(1)).get(),
+            name
+        );
+        assert_eq!(
+            StorageValue::<String, MemoryStorage>::new(U256::from(2)).get(),
+            symbol
+        );
+        let values = StorageVec::<u64, MemoryStorage>::new(U256::from(3));
+        assert_eq!(values.len(), 5);
+        assert_eq!(values.get(1), Some(2));
+        assert_eq!(values.get(4), Some(5));
+        // the same storage written by WASM
+        MemoryStorage::reset();
+        StorageValue::<Address, MemoryStorage>::new(U256::from(0)).set(&owner);
+        StorageValue::<String, MemoryStorage>::new(U256::from(1)).set(&name);
+        StorageValue::<String, MemoryStorage>::new(U256::from(2)).set(&symbol);
+        for value in 1..=5 {
+            values.push(&value);
+        }
+        for (slot, word) in words {
+            assert_eq!(sload(slot), word);
+        }
+    }
+
+    #[test]
+    fn test_storage_map() {
+        let balances = StorageMap::<Address, U256, MemoryStorage>::new(ZERO_SLOT);
+        // keccak256(abi.encode(address(0), uint256(0)))
+        assert_eq!(
+            balances.slot_of(&Address::ZERO),
+            U256::from_be_bytes(
+                b256!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5").0
+            )
+        );
+        let owner = address!("1000000000000000000000000000000000000001");
+        let spender = address!("2000000000000000000000000000000000000002");
+        balances.set(&owner, &U256::from(100));
+        assert_eq!(balances.get(&owner), U256::from(100));
+        assert_eq!(balances.get(&spender), U256::ZERO);
+        balances.remove(&owner);
+        assert_eq!(balances.get(&owner), U256::ZERO);
+        // nested mapping is located at `keccak256(spender . keccak256(owner . slot))`
+        let allowances =
+            StorageMap::<Address, StorageMap<Address, U256, MemoryStorage>, MemoryStorage>::new(
+                ONE_SLOT,
+            );
+        allowances.at(&owner).set(&spender, &U256::from(5));
+        assert_eq!(allowances.at(&owner).get(&spender), U256::from(5));
+        assert_eq!(allowances.at(&spender).get(&owner), U256::ZERO);
+        assert_eq!(
+            allowances.at(&owner).slot_of(&spender),
+            StorageMap::<Address, U256, MemoryStorage>::new(allowances.slot_of(&owner))
+                .slot_of(&spender)
+        );
+        let names = StorageMap::<String, Bytes, MemoryStorage>::new(U256::from(2));
+        names.set(&String::from("name"), &Bytes::from_static(b"Token"));
+        assert_eq!(
+            names.get(&String::from("name")),
+            Bytes::from_static(b"Token")
+        );
+    }
+
+    #[test]
+    fn test_storage_vec() {
+        let values = StorageVec::<u64, MemoryStorage>::new(ZERO_SLOT);
+        assert!(values.is_empty());
+        assert_eq!(values.get(0), None);
+        values.push(&1);
+        values.push(&2);
+        values.set(0, &3);
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get(0), Some(3));
+        // keccak256(uint256(0))
+        assert_eq!(
+            values.slot_of(0),
+            U256::from_be_bytes(
+                b256!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563").0
+            )
+        );
+        assert_eq!(values.slot_of(1), values.slot_of(0));
+        assert_eq!(values.offset_of(1), 8);
+        assert_eq!(values.pop(), Some(2));
+        // popped element is zeroed
+        assert_eq!(
+            sload(values.slot_of(0)),
+            b256!("0000000000000000000000000000000000000000000000000000000000000003")
+        );
+        assert_eq!(values.pop(), Some(3));
+        assert_eq!(values.pop(), None);
+        let items = StorageVec::<Bytes, MemoryStorage>::new(ONE_SLOT);
+        items.push(&Bytes::from(vec![1u8; 40]));
+        items.push(&Bytes::new());
+        assert_eq!(items.get(0), Some(Bytes::from(vec![1u8; 40])));
+        assert_eq!(items.get(1), Some(Bytes::new()));
+        assert_eq!(items.slot_of(1), items.slot_of(0) + U256::from(1));
+        // elements and their data are zeroed
+        items.clear();
+        assert_eq!(items.len(), 0);
+        assert_eq!(sload(items.slot_of(0)), B256::ZERO);
+        assert_eq!(sload(data_slot(&items.slot_of(0))), B256::ZERO);
+        assert_eq!(
+            sload(data_slot(&items.slot_of(0)) + U256::from(1)),
+            B256::ZERO
+        );
+        let packed = StorageVec::<u64, MemoryStorage>::new(U256::from(2));
+        for value in 1..=5 {
+            packed.push(&value);
+        }
+        packed.clear();
+        assert_eq!(sload(packed.slot_of(0)), B256::ZERO);
+        assert_eq!(sload(packed.slot_of(4)), B256::ZERO);
+    }
+
+    #[derive(Default, Debug, Clone, PartialEq, Codec)]
+    struct Config {
+        owner: Address,
+        limit: u64,
+        tags: Vec<u32>,
+    }
+
+    crate::impl_storage_codec!(Config);
+
+    #[test]
+    fn test_storage_codec_value() {
+        let config = StorageValue::<Config, MemoryStorage>::new(ZERO_SLOT);
+        assert_eq!(config.get(), Config::default());
+        let value = Config {
+            owner: address!("1000000000000000000000000000000000000001"),
+            limit: 100,
+            tags: vec![1, 2, 3],
+        };
+        config.set(&value);
+        assert_eq!(config.get(), value);
+        // encoded value is stored as `bytes`
+        assert_eq!(
+            StorageValue::<Bytes, MemoryStorage>::new(ZERO_SLOT).get(),
+            Bytes::from(value.encode_to_vec(0))
+        );
+        config.clear();
+        assert_eq!(config.get(), Config::default());
+        assert_eq!(sload(data_slot(&ZERO_SLOT)), B256::ZERO);
+        let configs = StorageMap::<Address, Config, MemoryStorage>::new(ONE_SLOT);
+        configs.set(&value.owner, &value);
+        assert_eq!(configs.get(&value.owner), value);
+        assert_eq!(configs.get(&Address::ZERO), Config::default());
+    }
+
+    #[test]
+    fn test_words_written_by_evm() {
+        // Solidity doesn't validate `string`
+        sstore(
+            ZERO_SLOT,
+            b256!("ff00000000000000000000000000000000000000000000000000000000000002"),
+        );
+        assert_eq!(
+            StorageValue::<String, MemoryStorage>::new(ZERO_SLOT).get(),
+            "\u{fffd}"
+        );
+        // length that doesn't fit into `usize` is clamped, but elements are still located by
+        // the full length
+        sstore(
+            ONE_SLOT,
+            b256!("8000000000000000000000000000000000000000000000000000000000000000"),
+        );
+        let values = StorageVec::<u64, MemoryStorage>::new(ONE_SLOT);
+        assert_eq!(values.len(), usize::MAX);
+        assert_eq!(values.get(usize::MAX), Some(0));
+        values.push(&7);
+        assert_eq!(
+            sload(ONE_SLOT),
+            b256!("8000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(values.pop(), Some(7));
+        assert_eq!(
+            sload(ONE_SLOT),
+            b256!("8000000000000000000000000000000000000000000000000000000000000000")
+        );
+    }
+
+    #[test]
+    fn test_jzkt_storage() {
+        let contract_input = ContractInput {
+            contract_address: address!("0000000000000000000000000000000000000001"),
+            ..Default::default()
+        };
+        LowLevelSDK::with_test_input(contract_input.encode_to_vec(0));
+        let value = StorageValue::<U256, JzktStorage>::new(ONE_SLOT);
+        value.set(&U256::from(100));
+        assert_eq!(value.get(), U256::from(100));
+    }
+}
@@ -1,2 +1,25 @@
 pub const STATE_MAIN: u32 = 0;
 pub const STATE_DEPLOY: u32 = 1;
+
+/// Number of fields
+pub const JZKT_ACCOUNT_FIELDS_COUNT: u32 = 6;
+pub const JZKT_STORAGE_FIELDS_COUNT: u32 = 1;
+
+pub const JZKT_ACCOUNT_BALANCE_FIELD: u32 = 0;
+pub const JZKT_ACCOUNT_NONCE_FIELD: u32 = 1;
+pub const JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD: u32 = 2;
+pub const JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD: u32 = 3;
+pub const JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD: u32 = 4;
+pub const JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD: u32 = 5;
+
+/// Discount (per mille) for BLS12-381 multi-scalar multiplication by the number of pairs
+/// (EIP-2537)
+pub const BLS12381_MSM_DISCOUNT_TABLE: [u64; 128] = [
+    1200, 888, 764, 641, 594, 547, 500, 453, 438, 423, 408, 394, 379, 364, 349, 334, 330, 326, 322,
+    318, 314, 310, 306, 302, 298, 294, 289, 285, 281, 277, 273, 269, 268, 266, 265, 263, 262, 260,
+    259, 257, 256, 254, 253, 251, 250, 248, 247, 245, 244, 242, 241, 239, 238, 236, 235, 233, 232,
+    231, 229, 228, 226, 225, 223, 222, 221, 220, 219, 219, 218, 217, 216, 216, 215, 214, 213, 213,
+    212, 211, 211, 210, 209, 208, 208, 207, 206, 205, 205, 204, 203, 202, 202, 201, 200, 199, 199,
+    198, 197, 196, 196, 195, 194, 193, 193, 192, 191, 191, 190, 189, 188, 188, 187, 186, 185, 185,
+    184, 183, 182, 182, 181, 180, 179, 179, 178, 177, 176, 176, 175, 174,
+];
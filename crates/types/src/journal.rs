@@ -1,6 +1,9 @@
-use crate::ExitCode;
+use crate::{
+    ExitCode, JZKT_ACCOUNT_BALANCE_FIELD, JZKT_ACCOUNT_FIELDS_COUNT, JZKT_ACCOUNT_NONCE_FIELD,
+    JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
+};
 use alloc::vec::Vec;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use auto_impl::auto_impl;
 
 #[derive(Debug, Clone)]
@@ -51,12 +54,15 @@ impl JournalEvent {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct JournalCheckpoint(pub u32, pub u32);
+/// Journal length, logs length and depth of the checkpoint stack at the moment the checkpoint
+/// was taken, the depth tells apart nested checkpoints made at the same journal position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JournalCheckpoint(pub u32, pub u32, pub u32);
 
+/// Conversion from the revm journal checkpoint that tracks the depth by itself
 impl From<(u32, u32)> for JournalCheckpoint {
     fn from(value: (u32, u32)) -> Self {
-        Self(value.0, value.1)
+        Self(value.0, value.1, 0)
     }
 }
 impl Into<(u32, u32)> for JournalCheckpoint {
@@ -66,12 +72,24 @@ impl Into<(u32, u32)> for JournalCheckpoint {
 }
 
 impl JournalCheckpoint {
+    /// Max logs length and depth that fit into the `u64` representation passed through the
+    /// `_jzkt_checkpoint` and `_jzkt_rollback` host functions
+    pub const MAX_LOGS: u32 = (1 << 20) - 1;
+    pub const MAX_DEPTH: u32 = (1 << 12) - 1;
+
     pub fn from_u64(value: u64) -> Self {
-        Self((value >> 32) as u32, value as u32)
+        Self(
+            (value >> 32) as u32,
+            (value >> 12) as u32 & Self::MAX_LOGS,
+            value as u32 & Self::MAX_DEPTH,
+        )
     }
 
     pub fn to_u64(&self) -> u64 {
-        (self.0 as u64) << 32 | self.1 as u64
+        debug_assert!(self.1 <= Self::MAX_LOGS && self.2 <= Self::MAX_DEPTH);
+        (self.0 as u64) << 32
+            | ((self.1 & Self::MAX_LOGS) as u64) << 12
+            | (self.2 & Self::MAX_DEPTH) as u64
     }
 
     pub fn state(&self) -> usize {
@@ -81,6 +99,10 @@ impl JournalCheckpoint {
     pub fn logs(&self) -> usize {
         self.1 as usize
     }
+
+    pub fn depth(&self) -> usize {
+        self.2 as usize
+    }
 }
 
 pub struct JournalLog {
@@ -89,6 +111,114 @@ pub struct JournalLog {
     pub data: Bytes,
 }
 
+/// Account fields that are tracked by the state diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDiffInfo {
+    pub balance: U256,
+    pub nonce: u64,
+    pub source_code_hash: B256,
+    pub rwasm_code_hash: B256,
+}
+
+impl AccountDiffInfo {
+    pub fn from_fields(fields: &[[u8; 32]]) -> Result<Self, ExitCode> {
+        if fields.len() != JZKT_ACCOUNT_FIELDS_COUNT as usize {
+            return Err(ExitCode::PersistentStorageError);
+        }
+        let nonce = &fields[JZKT_ACCOUNT_NONCE_FIELD as usize];
+        Ok(Self {
+            balance: U256::from_le_bytes(fields[JZKT_ACCOUNT_BALANCE_FIELD as usize]),
+            nonce: u64::from_le_bytes(nonce[..8].try_into().unwrap()),
+            source_code_hash: B256::from(fields[JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD as usize]),
+            rwasm_code_hash: B256::from(fields[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize]),
+        })
+    }
+}
+
+/// Account state before and after the commit, `None` means that account doesn't exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountStateDiff {
+    pub address: Address,
+    pub before: Option<AccountDiffInfo>,
+    pub after: Option<AccountDiffInfo>,
+}
+
+impl AccountStateDiff {
+    pub fn is_created(&self) -> bool {
+        self.before.is_none() && self.after.is_some()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.before.is_some() && self.after.is_none()
+    }
+}
+
+/// Storage slot value before and after the commit, `None` means that slot doesn't exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageStateDiff {
+    pub address: Address,
+    pub slot: U256,
+    pub before: Option<[u8; 32]>,
+    pub after: Option<[u8; 32]>,
+}
+
+/// All state changes made by the commit, entries are sorted by their trie keys
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    pub accounts: Vec<AccountStateDiff>,
+    pub storage: Vec<StorageStateDiff>,
+}
+
+impl StateDiff {
+    /// Adds account change, fields must be in the account leaf format
+    pub fn add_account_change(
+        &mut self,
+        address: Address,
+        before: Option<&[[u8; 32]]>,
+        after: Option<&[[u8; 32]]>,
+    ) -> Result<(), ExitCode> {
+        if before == after {
+            return Ok(());
+        }
+        self.accounts.push(AccountStateDiff {
+            address,
+            before: before.map(AccountDiffInfo::from_fields).transpose()?,
+            after: after.map(AccountDiffInfo::from_fields).transpose()?,
+        });
+        Ok(())
+    }
+
+    pub fn add_storage_change(
+        &mut self,
+        address: Address,
+        slot: U256,
+        before: Option<[u8; 32]>,
+        after: Option<[u8; 32]>,
+    ) {
+        if before == after {
+            return;
+        }
+        self.storage.push(StorageStateDiff {
+            address,
+            slot,
+            before,
+            after,
+        });
+    }
+
+    pub fn created_accounts(&self) -> impl Iterator<Item = &AccountStateDiff> {
+        self.accounts.iter().filter(|v| v.is_created())
+    }
+
+    pub fn removed_accounts(&self) -> impl Iterator<Item = &AccountStateDiff> {
+        self.accounts.iter().filter(|v| v.is_removed())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.storage.is_empty()
+    }
+}
+
 #[auto_impl(&, Rc, Arc, Box)]
 pub trait IJournaledTrie {
     fn checkpoint(&self) -> JournalCheckpoint;
@@ -97,8 +227,17 @@ pub trait IJournaledTrie {
     fn remove(&self, key: &[u8; 32]);
     fn compute_root(&self) -> [u8; 32];
     fn emit_log(&self, address: Address, topics: Vec<B256>, data: Bytes);
+    /// Merges all changes made since the last checkpoint into the parent checkpoint
+    fn checkpoint_commit(&self);
+    /// Reverts all changes made since the checkpoint and discards this checkpoint
+    fn checkpoint_revert(&self, checkpoint: JournalCheckpoint);
+    /// Writes all journaled changes into the trie, must be called at the end of transaction
     fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode>;
-    fn rollback(&self, checkpoint: JournalCheckpoint);
+    /// Same as `commit`, but also returns all state changes that were written into the trie
+    fn commit_with_diff(&self) -> Result<([u8; 32], Vec<JournalLog>, StateDiff), ExitCode>;
+    /// Remembers contract address and slot the storage key is derived from, the trie stores
+    /// only hashed keys, so it's the only way to report storage changes in the state diff
+    fn update_storage_slot(&self, key: &[u8; 32], address: &Address, slot: &U256);
     fn update_preimage(&self, key: &[u8; 32], field: u32, preimage: &[u8]) -> bool;
     fn preimage(&self, hash: &[u8; 32]) -> Vec<u8>;
     fn preimage_size(&self, hash: &[u8; 32]) -> u32;
@@ -134,14 +273,26 @@ impl IJournaledTrie for EmptyJournalTrie {
         todo!()
     }
 
-    fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
+    fn checkpoint_commit(&self) {
+        // there are no changes to merge into the parent checkpoint
+    }
+
+    fn checkpoint_revert(&self, checkpoint: JournalCheckpoint) {
         todo!()
     }
 
-    fn rollback(&self, checkpoint: JournalCheckpoint) {
+    fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
         todo!()
     }
 
+    fn commit_with_diff(&self) -> Result<([u8; 32], Vec<JournalLog>, StateDiff), ExitCode> {
+        unreachable!("empty journal can't be committed, so there is no state diff")
+    }
+
+    fn update_storage_slot(&self, key: &[u8; 32], address: &Address, slot: &U256) {
+        // there is no state diff to report storage changes in
+    }
+
     fn update_preimage(&self, key: &[u8; 32], field: u32, preimage: &[u8]) -> bool {
         todo!()
     }
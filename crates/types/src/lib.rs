@@ -18,11 +18,18 @@ mod journal;
 mod linker;
 mod types;
 
-pub use alloy_primitives::{address, b256, bloom, bytes, fixed_bytes, Address, Bytes, B256, U256};
+pub use alloy_primitives::{
+    address, b256, bloom, bytes, fixed_bytes, Address, Bytes, FixedBytes, Uint, B256, U256,
+};
 
 pub const KECCAK_EMPTY: B256 =
     b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+/// Poseidon hash of the empty code, it's little-endian as all field elements in the state
 pub const POSEIDON_EMPTY: F254 =
+    b256!("6448b64684ee39a823d5fe5fd52431dc81e4817bf2c3ea3cab9e239efbf59820");
+/// Value of `POSEIDON_EMPTY` before code hashes were switched to the latest Poseidon scheme,
+/// accounts without code that are already stored in the state may still have it
+pub const LEGACY_POSEIDON_EMPTY: F254 =
     b256!("2098f5fb9e239eab3ceac3f27b81e481dc3124d55ffed523a839ee8446b64864");
 
 pub type F254 = B256;
@@ -9,11 +9,27 @@ macro_rules! import_func {
     };
 }
 
-const SHARED_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 22] = [
+const SHARED_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 38] = [
     import_func!("_crypto_keccak256", CRYPTO_KECCAK256),
     import_func!("_crypto_poseidon", CRYPTO_KECCAK256),
     import_func!("_crypto_poseidon2", CRYPTO_POSEIDON2),
     import_func!("_crypto_ecrecover", CRYPTO_ECRECOVER),
+    import_func!("_crypto_sha256", CRYPTO_SHA256),
+    import_func!("_crypto_ripemd160", CRYPTO_RIPEMD160),
+    import_func!("_crypto_blake2f", CRYPTO_BLAKE2F),
+    import_func!("_crypto_bn128_add", CRYPTO_BN128_ADD),
+    import_func!("_crypto_bn128_mul", CRYPTO_BN128_MUL),
+    import_func!("_crypto_bn128_pairing", CRYPTO_BN128_PAIRING),
+    import_func!("_crypto_modexp", CRYPTO_MODEXP),
+    import_func!("_crypto_secp256r1_verify", CRYPTO_SECP256R1_VERIFY),
+    import_func!("_crypto_ed25519_verify", CRYPTO_ED25519_VERIFY),
+    import_func!("_crypto_bls12381_g1_add", CRYPTO_BLS12381_G1_ADD),
+    import_func!("_crypto_bls12381_g1_msm", CRYPTO_BLS12381_G1_MSM),
+    import_func!("_crypto_bls12381_g2_add", CRYPTO_BLS12381_G2_ADD),
+    import_func!("_crypto_bls12381_g2_msm", CRYPTO_BLS12381_G2_MSM),
+    import_func!("_crypto_bls12381_pairing", CRYPTO_BLS12381_PAIRING),
+    import_func!("_crypto_bls12381_map_fp_to_g1", CRYPTO_BLS12381_MAP_FP_TO_G1),
+    import_func!("_crypto_bls12381_map_fp2_to_g2", CRYPTO_BLS12381_MAP_FP2_TO_G2),
     import_func!("_sys_halt", SYS_HALT),
     import_func!("_sys_write", SYS_WRITE),
     import_func!("_sys_input_size", SYS_INPUT_SIZE),
@@ -29,11 +45,13 @@ const SHARED_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 22] = [
     import_func!("_jzkt_get", JZKT_GET),
     // import_func!("_jzkt_update", JZKT_UPDATE),
     // import_func!("_jzkt_update_preimage", JZKT_UPDATE_PREIMAGE),
+    // import_func!("_jzkt_update_storage_slot", JZKT_UPDATE_STORAGE_SLOT),
     // import_func!("_jzkt_remove", JZKT_REMOVE),
     import_func!("_jzkt_compute_root", JZKT_COMPUTE_ROOT),
     import_func!("_jzkt_emit_log", JZKT_EMIT_LOG),
     // import_func!("_jzkt_commit", JZKT_COMMIT),
     // import_func!("_jzkt_rollback", JZKT_ROLLBACK),
+    // import_func!("_jzkt_checkpoint_commit", JZKT_CHECKPOINT_COMMIT),
     import_func!("_jzkt_preimage_size", JZKT_PREIMAGE_SIZE),
     import_func!("_jzkt_preimage_copy", JZKT_PREIMAGE_COPY),
     import_func!("_wasm_to_rwasm_size", WASM_TO_RWASM_SIZE),
@@ -41,15 +59,31 @@ const SHARED_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 22] = [
     import_func!("_debug_log", DEBUG_LOG),
 ];
 
-pub fn create_shared_import_linker<F: From<[(&'static str, &'static str, u32, u32); 22]>>() -> F {
+pub fn create_shared_import_linker<F: From<[(&'static str, &'static str, u32, u32); 38]>>() -> F {
     F::from(SHARED_IMPORT_LINKER)
 }
 
-const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 29] = [
+const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 47] = [
     import_func!("_crypto_keccak256", CRYPTO_KECCAK256),
     import_func!("_crypto_poseidon", CRYPTO_KECCAK256),
     import_func!("_crypto_poseidon2", CRYPTO_POSEIDON2),
     import_func!("_crypto_ecrecover", CRYPTO_ECRECOVER),
+    import_func!("_crypto_sha256", CRYPTO_SHA256),
+    import_func!("_crypto_ripemd160", CRYPTO_RIPEMD160),
+    import_func!("_crypto_blake2f", CRYPTO_BLAKE2F),
+    import_func!("_crypto_bn128_add", CRYPTO_BN128_ADD),
+    import_func!("_crypto_bn128_mul", CRYPTO_BN128_MUL),
+    import_func!("_crypto_bn128_pairing", CRYPTO_BN128_PAIRING),
+    import_func!("_crypto_modexp", CRYPTO_MODEXP),
+    import_func!("_crypto_secp256r1_verify", CRYPTO_SECP256R1_VERIFY),
+    import_func!("_crypto_ed25519_verify", CRYPTO_ED25519_VERIFY),
+    import_func!("_crypto_bls12381_g1_add", CRYPTO_BLS12381_G1_ADD),
+    import_func!("_crypto_bls12381_g1_msm", CRYPTO_BLS12381_G1_MSM),
+    import_func!("_crypto_bls12381_g2_add", CRYPTO_BLS12381_G2_ADD),
+    import_func!("_crypto_bls12381_g2_msm", CRYPTO_BLS12381_G2_MSM),
+    import_func!("_crypto_bls12381_pairing", CRYPTO_BLS12381_PAIRING),
+    import_func!("_crypto_bls12381_map_fp_to_g1", CRYPTO_BLS12381_MAP_FP_TO_G1),
+    import_func!("_crypto_bls12381_map_fp2_to_g2", CRYPTO_BLS12381_MAP_FP2_TO_G2),
     import_func!("_sys_halt", SYS_HALT),
     import_func!("_sys_write", SYS_WRITE),
     import_func!("_sys_input_size", SYS_INPUT_SIZE),
@@ -65,11 +99,13 @@ const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 29] = [
     import_func!("_jzkt_get", JZKT_GET),
     import_func!("_jzkt_update", JZKT_UPDATE),
     import_func!("_jzkt_update_preimage", JZKT_UPDATE_PREIMAGE),
+    import_func!("_jzkt_update_storage_slot", JZKT_UPDATE_STORAGE_SLOT),
     import_func!("_jzkt_remove", JZKT_REMOVE),
     import_func!("_jzkt_compute_root", JZKT_COMPUTE_ROOT),
     import_func!("_jzkt_emit_log", JZKT_EMIT_LOG),
     import_func!("_jzkt_commit", JZKT_COMMIT),
     import_func!("_jzkt_rollback", JZKT_ROLLBACK),
+    import_func!("_jzkt_checkpoint_commit", JZKT_CHECKPOINT_COMMIT),
     import_func!("_jzkt_preimage_size", JZKT_PREIMAGE_SIZE),
     import_func!("_jzkt_preimage_copy", JZKT_PREIMAGE_COPY),
     import_func!("_wasm_to_rwasm_size", WASM_TO_RWASM_SIZE),
@@ -77,7 +113,7 @@ const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 29] = [
     import_func!("_debug_log", DEBUG_LOG),
 ];
 
-pub fn create_sovereign_import_linker<F: From<[(&'static str, &'static str, u32, u32); 29]>>() -> F
+pub fn create_sovereign_import_linker<F: From<[(&'static str, &'static str, u32, u32); 47]>>() -> F
 {
     F::from(SOVEREIGN_IMPORT_LINKER)
 }
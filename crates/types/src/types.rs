@@ -50,6 +50,12 @@ pub enum ExitCode {
     InvalidEfOpcode = -1031,
     InvalidJump = -1032,
     NotActivatedEIP = -1033,
+    Bn128FieldPointNotAMember = -1034,
+    Bn128AffineGFailedToCreate = -1035,
+    Bn128PairLength = -1036,
+    Bls12381InvalidInputLength = -1037,
+    Bls12381InvalidFieldElement = -1038,
+    Bls12381InvalidPoint = -1039,
     // trap error codes
     UnreachableCodeReached = -2006,
     MemoryOutOfBounds = -2007,
@@ -117,6 +123,12 @@ impl fmt::Display for ExitCode {
             ExitCode::InvalidEfOpcode => write!(f, "InvalidEfOpcode"),
             ExitCode::InvalidJump => write!(f, "InvalidJump"),
             ExitCode::NotActivatedEIP => write!(f, "NotActivatedEIP"),
+            ExitCode::Bn128FieldPointNotAMember => write!(f, "Bn128FieldPointNotAMember"),
+            ExitCode::Bn128AffineGFailedToCreate => write!(f, "Bn128AffineGFailedToCreate"),
+            ExitCode::Bn128PairLength => write!(f, "Bn128PairLength"),
+            ExitCode::Bls12381InvalidInputLength => write!(f, "Bls12381InvalidInputLength"),
+            ExitCode::Bls12381InvalidFieldElement => write!(f, "Bls12381InvalidFieldElement"),
+            ExitCode::Bls12381InvalidPoint => write!(f, "Bls12381InvalidPoint"),
             ExitCode::StackUnderflow => write!(f, "StackUnderflow"),
         }
     }
@@ -217,6 +229,22 @@ pub enum SysFuncIdx {
     CRYPTO_POSEIDON = 0x0102,
     CRYPTO_POSEIDON2 = 0x0103,
     CRYPTO_ECRECOVER = 0x0104,
+    CRYPTO_SHA256 = 0x0105,
+    CRYPTO_RIPEMD160 = 0x0106,
+    CRYPTO_BLAKE2F = 0x0107,
+    CRYPTO_BN128_ADD = 0x0108,
+    CRYPTO_BN128_MUL = 0x0109,
+    CRYPTO_BN128_PAIRING = 0x010A,
+    CRYPTO_MODEXP = 0x010B,
+    CRYPTO_SECP256R1_VERIFY = 0x010C,
+    CRYPTO_ED25519_VERIFY = 0x010D,
+    CRYPTO_BLS12381_G1_ADD = 0x010E,
+    CRYPTO_BLS12381_G1_MSM = 0x010F,
+    CRYPTO_BLS12381_G2_ADD = 0x0110,
+    CRYPTO_BLS12381_G2_MSM = 0x0111,
+    CRYPTO_BLS12381_PAIRING = 0x0112,
+    CRYPTO_BLS12381_MAP_FP_TO_G1 = 0x0113,
+    CRYPTO_BLS12381_MAP_FP2_TO_G2 = 0x0114,
 
     // SYS host
     SYS_HALT = 0x0001,
@@ -241,6 +269,8 @@ pub enum SysFuncIdx {
     JZKT_EMIT_LOG = 0x0708,
     JZKT_COMMIT = 0x0709,
     JZKT_ROLLBACK = 0x070A,
+    JZKT_CHECKPOINT_COMMIT = 0x070B,
+    JZKT_UPDATE_STORAGE_SLOT = 0x070C,
     JZKT_PREIMAGE_SIZE = 0x070D,
     JZKT_PREIMAGE_COPY = 0x070E,
 
@@ -263,12 +293,27 @@ impl SysFuncIdx {
             SysFuncIdx::CRYPTO_POSEIDON => 1,
             SysFuncIdx::CRYPTO_POSEIDON2 => 1,
             SysFuncIdx::CRYPTO_ECRECOVER => 1,
+            // match gas costs of the bn128 precompiles (EIP-1108)
+            SysFuncIdx::CRYPTO_BN128_ADD => 150,
+            SysFuncIdx::CRYPTO_BN128_MUL => 6_000,
+            // matches gas cost of the P256VERIFY precompile (RIP-7212)
+            SysFuncIdx::CRYPTO_SECP256R1_VERIFY => 3_450,
+            // base cost of Ed25519 verification, there is no EVM precompile for it, but it's
+            // cheaper than ECDSA verification, the message hashing is charged per word
+            SysFuncIdx::CRYPTO_ED25519_VERIFY => 2_000,
+            // match gas costs of the BLS12-381 precompiles (EIP-2537)
+            SysFuncIdx::CRYPTO_BLS12381_G1_ADD => 500,
+            SysFuncIdx::CRYPTO_BLS12381_G2_ADD => 800,
+            SysFuncIdx::CRYPTO_BLS12381_MAP_FP_TO_G1 => 5_500,
+            SysFuncIdx::CRYPTO_BLS12381_MAP_FP2_TO_G2 => 75_000,
             SysFuncIdx::JZKT_OPEN => 1,
             SysFuncIdx::JZKT_UPDATE => 1,
             SysFuncIdx::JZKT_GET => 1,
             SysFuncIdx::JZKT_COMPUTE_ROOT => 1,
             SysFuncIdx::JZKT_ROLLBACK => 1,
             SysFuncIdx::JZKT_COMMIT => 1,
+            SysFuncIdx::JZKT_CHECKPOINT_COMMIT => 1,
+            SysFuncIdx::JZKT_UPDATE_STORAGE_SLOT => 1,
             _ => 1, //unreachable!("not configured fuel for opcode: {:?}", self),
         }
     }
@@ -284,6 +329,22 @@ impl From<u32> for SysFuncIdx {
             0x0102 => Self::CRYPTO_POSEIDON,
             0x0103 => Self::CRYPTO_POSEIDON2,
             0x0104 => Self::CRYPTO_ECRECOVER,
+            0x0105 => Self::CRYPTO_SHA256,
+            0x0106 => Self::CRYPTO_RIPEMD160,
+            0x0107 => Self::CRYPTO_BLAKE2F,
+            0x0108 => Self::CRYPTO_BN128_ADD,
+            0x0109 => Self::CRYPTO_BN128_MUL,
+            0x010A => Self::CRYPTO_BN128_PAIRING,
+            0x010B => Self::CRYPTO_MODEXP,
+            0x010C => Self::CRYPTO_SECP256R1_VERIFY,
+            0x010D => Self::CRYPTO_ED25519_VERIFY,
+            0x010E => Self::CRYPTO_BLS12381_G1_ADD,
+            0x010F => Self::CRYPTO_BLS12381_G1_MSM,
+            0x0110 => Self::CRYPTO_BLS12381_G2_ADD,
+            0x0111 => Self::CRYPTO_BLS12381_G2_MSM,
+            0x0112 => Self::CRYPTO_BLS12381_PAIRING,
+            0x0113 => Self::CRYPTO_BLS12381_MAP_FP_TO_G1,
+            0x0114 => Self::CRYPTO_BLS12381_MAP_FP2_TO_G2,
 
             // SYS host
             0x0001 => Self::SYS_HALT,
@@ -307,6 +368,8 @@ impl From<u32> for SysFuncIdx {
             0x0708 => Self::JZKT_EMIT_LOG,
             0x0709 => Self::JZKT_COMMIT,
             0x070A => Self::JZKT_ROLLBACK,
+            0x070B => Self::JZKT_CHECKPOINT_COMMIT,
+            0x070C => Self::JZKT_UPDATE_STORAGE_SLOT,
             0x070D => Self::JZKT_PREIMAGE_SIZE,
             0x070E => Self::JZKT_PREIMAGE_COPY,
 
@@ -1,4 +1,5 @@
 use crate::{fr_from_big_endian, fr_from_usize, Fr, HashScheme, HASH_DOMAIN_BYTE32};
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Ord, PartialOrd, Copy)]
 pub struct Byte32([u8; 32]);
@@ -68,6 +69,6 @@ impl Byte32 {
         let mut out = vec![0_u8; len * 32];
         out[len * 32 - data.len()..].copy_from_slice(data);
         let ptr = out.as_ptr() as *const Byte32;
-        unsafe { std::slice::from_raw_parts(ptr, len) }.to_owned()
+        unsafe { core::slice::from_raw_parts(ptr, len) }.to_owned()
     }
 }
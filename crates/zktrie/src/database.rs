@@ -1,5 +1,5 @@
 use crate::{Error, Fr, Hash, HashScheme, Node};
-use std::{collections::BTreeMap, prelude::v1::*, sync::Arc};
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
 
 pub trait Database {
     type Node;
@@ -1,6 +1,6 @@
 use crate::{fr_from_little_endian, fr_to_little_endian, reverse_byte_order, Byte32, Fr};
-use fluentbase_poseidon::hash_with_domain;
-use std::{prelude::v1::*, sync::Arc};
+use alloc::{string::String, sync::Arc};
+use fluentbase_poseidon::HashVersion;
 
 pub const HASH_DOMAIN_ELEMS_BASE: usize = 256;
 pub const HASH_DOMAIN_BYTE32: usize = 2 * HASH_DOMAIN_ELEMS_BASE;
@@ -24,16 +24,27 @@ impl HashScheme for () {
     }
 }
 
+/// Poseidon with domain separation of leaves, branches and preimages (the latest version)
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PoseidonHash;
 impl HashScheme for PoseidonHash {
+    const VERSION: HashVersion = HashVersion::V2;
+}
+
+/// Legacy Poseidon that ignores domains, it's needed to read tries that were built before
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoseidonHashV1;
+impl HashScheme for PoseidonHashV1 {
+    const VERSION: HashVersion = HashVersion::V1;
+}
+
+pub trait HashScheme: PartialEq + Clone + core::fmt::Debug + Send + Sync {
+    const VERSION: HashVersion = HashVersion::LATEST;
+
     fn hash_scheme(arr: &[Fr], domain: &Fr) -> Fr {
-        hash_with_domain(arr, domain)
+        Self::VERSION.hash_with_domain(arr, domain)
     }
 }
-pub trait HashScheme: PartialEq + Clone + std::fmt::Debug {
-    fn hash_scheme(arr: &[Fr], domain: &Fr) -> Fr;
-}
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Copy, PartialOrd, Ord)]
 pub struct Hash([u8; 32]);
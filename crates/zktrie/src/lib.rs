@@ -1,3 +1,6 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 extern crate core;
 
 pub use halo2curves::bn256::Fr;
@@ -5,6 +8,8 @@ pub use halo2curves::bn256::Fr;
 mod util;
 pub use util::*;
 
+mod parallel;
+
 mod hash;
 pub use hash::*;
 #[cfg(test)]
@@ -10,7 +10,7 @@ use crate::{
     HASH_BYTE_LEN,
 };
 use core::marker::PhantomData;
-use std::{prelude::v1::*, sync::Arc};
+use alloc::{string::String, sync::Arc, vec, vec::Vec};
 
 #[derive(Debug, Eq)]
 pub struct Node<H: HashScheme> {
@@ -237,7 +237,7 @@ impl<H: HashScheme> Node<H> {
         lazy_static::lazy_static! {
             static ref EMPTY: Arc<Node<()>> = Arc::new(<Node<()>>::new_empty());
         }
-        unsafe { std::mem::transmute(EMPTY.clone()) }
+        unsafe { core::mem::transmute(EMPTY.clone()) }
     }
 
     pub fn new_empty() -> Self {
@@ -386,7 +386,7 @@ impl LeafNode {
 
     pub fn data(&self) -> &[u8] {
         let ptr = self.value_preimage.as_ptr() as *const u8;
-        unsafe { std::slice::from_raw_parts(ptr, self.value_preimage.len() * 32) }
+        unsafe { core::slice::from_raw_parts(ptr, self.value_preimage.len() * 32) }
     }
 
     pub fn ty() -> u8 {
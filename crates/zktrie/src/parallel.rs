@@ -0,0 +1,38 @@
+use alloc::vec::Vec;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Minimal amount of entries in a subtree to hash its children concurrently, smaller subtrees
+/// are cheaper to hash in the current thread
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_ENTRIES: usize = 64;
+
+/// Runs both closures, concurrently if `parallel` feature is enabled and there are enough
+/// entries to process. Results are always the same as for the sequential call.
+pub(crate) fn join<A, B, RA, RB>(_size: usize, a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "parallel")]
+    if _size >= PARALLEL_MIN_ENTRIES {
+        return rayon::join(a, b);
+    }
+    (a(), b())
+}
+
+/// Maps all items preserving their order, concurrently if `parallel` feature is enabled
+pub(crate) fn map_collect<T, R, F>(items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    if items.len() >= PARALLEL_MIN_ENTRIES {
+        return items.into_par_iter().map(f).collect();
+    }
+    items.into_iter().map(f).collect()
+}
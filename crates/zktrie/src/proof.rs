@@ -1,6 +1,4 @@
-use std::prelude::v1::*;
-
-use crate::{Error, HashScheme, Node};
+use crate::{test_bit, to_secure_key, Error, Hash, HashScheme, Node, NodeValue};
 
 lazy_static::lazy_static! {
     pub static ref MAGIC_HASH: &'static [u8] = b"THIS IS THE MAGIC INDEX FOR ZKTRIE";
@@ -13,3 +11,50 @@ pub fn decode_smt_proofs<H: HashScheme>(buf: &[u8]) -> Result<Option<Node<H>>, E
     }
     Ok(Some(<Node<H>>::from_bytes(buf)?))
 }
+
+// VerifyProof checks the proof generated by `ZkTrie::proof` against the root. For the existing
+// key it returns the leaf node, and `None` if the proof proves that the key doesn't exist (the
+// path ends with an empty node or with a leaf that has another key).
+pub fn verify_proof<H: HashScheme, P: AsRef<[u8]>>(
+    root: &Hash,
+    key: &[u8],
+    proof: &[P],
+) -> Result<Option<Node<H>>, Error> {
+    let k = to_secure_key::<H>(key)?;
+    let node_key: Hash = k.into();
+    let mut expected_hash = *root;
+    let mut proof = proof.iter();
+    let mut lvl = 0;
+    let result = loop {
+        let node = proof
+            .next()
+            .map(|buf| decode_smt_proofs::<H>(buf.as_ref()))
+            .transpose()?
+            .flatten()
+            .ok_or(Error::InvalidProof)?;
+        if node.hash() != &expected_hash {
+            return Err(Error::InvalidProof);
+        }
+        match node.value() {
+            NodeValue::Empty => break None,
+            NodeValue::Leaf(leaf) if leaf.key == node_key => break Some(node),
+            NodeValue::Leaf(_) => break None,
+            NodeValue::Branch(branch) => {
+                if lvl >= node_key.raw_bytes().len() * 8 {
+                    return Err(Error::ReachedMaxLevel);
+                }
+                expected_hash = if test_bit(node_key.raw_bytes(), lvl) {
+                    *branch.right.hash()
+                } else {
+                    *branch.left.hash()
+                };
+                lvl += 1;
+            }
+        }
+    };
+    // proof must be terminated with magic bytes right after the last node
+    match proof.next() {
+        Some(buf) if MAGIC_SMT_BYTES.eq(buf.as_ref()) && proof.next().is_none() => Ok(result),
+        _ => Err(Error::InvalidProof),
+    }
+}
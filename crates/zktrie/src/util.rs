@@ -1,6 +1,6 @@
 use crate::{Byte32, Fr, Hash, HashScheme, HASH_DOMAIN_BYTE32, HASH_DOMAIN_ELEMS_BASE};
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
-use std::prelude::v1::*;
+use alloc::{string::String, vec::Vec};
 use uint::construct_uint;
 
 construct_uint! {
@@ -137,4 +137,5 @@ pub enum Error {
     InvalidNodeFound(u8),
     NotInField(String),
     ExpectedLeafNode,
+    InvalidProof,
 }
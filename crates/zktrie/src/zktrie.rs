@@ -1,4 +1,5 @@
 use crate::{
+    parallel,
     test_bit,
     to_secure_key,
     BranchHash,
@@ -16,7 +17,7 @@ use crate::{
     ZERO_HASH,
 };
 use core::marker::PhantomData;
-use std::{prelude::v1::*, sync::Arc};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec, vec::Vec};
 
 #[derive(Clone)]
 pub struct ZkTrie<H: HashScheme> {
@@ -25,6 +26,21 @@ pub struct ZkTrie<H: HashScheme> {
     phantom: PhantomData<H>,
 }
 
+struct BatchEntry<H: HashScheme> {
+    key: Hash,
+    path: Vec<bool>,
+    leaf: Option<Node<H>>,
+}
+
+enum BatchSubtree<H: HashScheme> {
+    // subtree without changes
+    Existing(BranchHash),
+    // subtree that is built from scratch out of the entries starting from the level
+    New(usize, Vec<BatchEntry<H>>),
+    // branch with changed children and amount of entries below it
+    Branch(usize, Box<BatchSubtree<H>>, Box<BatchSubtree<H>>),
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum TrieData<H: HashScheme> {
     NotFound,
@@ -218,6 +234,189 @@ impl<H: HashScheme> ZkTrie<H> {
         Ok(())
     }
 
+    // UpdateBatch applies a set of insertions (`Some`) and deletions (`None`) to the trie. Unlike
+    // sequential updates every interior node that is shared between the changed paths is rehashed
+    // only once, but resulting trie is identical. If the same key appears several times then the
+    // last change wins.
+    pub fn update_batch<'a, D, I>(&mut self, db: &mut D, entries: I) -> Result<(), Error>
+    where
+        D: PreimageDatabase<Node = Node<H>>,
+        I: IntoIterator<Item = (&'a [u8], Option<(u32, Vec<Byte32>)>)>,
+    {
+        let mut sorted_entries = BTreeMap::new();
+        for (key, value) in entries.into_iter() {
+            let k = to_secure_key::<H>(key)?;
+            let key_hash: Hash = k.into();
+            if value.is_some() {
+                self.update_preimage(db, key, &k);
+            }
+            sorted_entries.insert(key_hash.bytes(), (key_hash, value));
+        }
+        if sorted_entries.is_empty() {
+            return Ok(());
+        }
+        // leaves are hashed independently, so it can be done concurrently
+        let max_level = self.max_level;
+        let entries = parallel::map_collect(
+            sorted_entries.into_values().collect(),
+            |(key, value)| -> Result<BatchEntry<H>, Error> {
+                let leaf = match value {
+                    Some((v_flag, v_preimage)) => {
+                        Some(<Node<H>>::new_leaf(key, v_flag, v_preimage, None)?)
+                    }
+                    None => None,
+                };
+                Ok(BatchEntry {
+                    path: get_path(max_level, key.raw_bytes()),
+                    key,
+                    leaf,
+                })
+            },
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+        let root = self.root;
+        let root = match self.get_node(db, &root)? {
+            Some(n) if n.is_branch() => BranchHash::Branch(root),
+            Some(_) => BranchHash::Ternimal(root),
+            None => return Err(Error::NodeNotFound((0, root))),
+        };
+        // all reads from the database are done before hashing, then new nodes are written in
+        // the same order as they're created by the sequential algorithm
+        let subtree = self.batch_plan(db, root, 0, entries)?;
+        let (new_root, nodes) = Self::batch_hash(max_level, subtree)?;
+        for node in nodes {
+            if node.is_leaf() {
+                self.update_node(db, node)?;
+                continue;
+            }
+            match self.add_node(db, &node) {
+                Err(Error::NodeKeyAlreadyExists) | Ok(_) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.root = *new_root.hash();
+        Ok(())
+    }
+
+    // batchPlan walks existing nodes along the changed paths and finds subtrees that must be
+    // rehashed, nothing is hashed or written here
+    fn batch_plan<D>(
+        &self,
+        db: &D,
+        curr_node: BranchHash,
+        lvl: usize,
+        mut entries: Vec<BatchEntry<H>>,
+    ) -> Result<BatchSubtree<H>, Error>
+    where
+        D: Database<Node = Node<H>>,
+    {
+        if entries.is_empty() {
+            return Ok(BatchSubtree::Existing(curr_node));
+        }
+        if lvl > self.max_level - 1 {
+            return Err(Error::ReachedMaxLevel);
+        }
+        let n = self
+            .get_node(db, curr_node.hash())?
+            .ok_or(Error::NodeNotFound((lvl, *curr_node.hash())))?;
+        match n.value() {
+            NodeValue::Empty => Ok(BatchSubtree::New(lvl, entries)),
+            NodeValue::Leaf(old_leaf) => {
+                // keep old leaf unless it's overridden by one of the entries
+                if !entries.iter().any(|e| e.key == old_leaf.key) {
+                    entries.push(BatchEntry {
+                        path: get_path(self.max_level, old_leaf.key.raw_bytes()),
+                        key: old_leaf.key,
+                        leaf: Some(n.as_ref().clone()),
+                    });
+                }
+                Ok(BatchSubtree::New(lvl, entries))
+            }
+            NodeValue::Branch(branch) => {
+                let size = entries.len();
+                let (right, left): (Vec<_>, Vec<_>) =
+                    entries.into_iter().partition(|e| e.path[lvl]);
+                let left = self.batch_plan(db, branch.left.clone(), lvl + 1, left)?;
+                let right = self.batch_plan(db, branch.right.clone(), lvl + 1, right)?;
+                Ok(BatchSubtree::Branch(size, Box::new(left), Box::new(right)))
+            }
+        }
+    }
+
+    // batchHash computes hashes of the planned subtree and returns all new nodes, independent
+    // subtrees are hashed concurrently if `parallel` feature is enabled
+    fn batch_hash(
+        max_level: usize,
+        subtree: BatchSubtree<H>,
+    ) -> Result<(BranchHash, Vec<Node<H>>), Error> {
+        match subtree {
+            BatchSubtree::Existing(hash) => Ok((hash, Vec::new())),
+            BatchSubtree::New(lvl, entries) => Self::batch_build_subtree(max_level, lvl, entries),
+            BatchSubtree::Branch(size, left, right) => {
+                let (left, right) = parallel::join(
+                    size,
+                    || Self::batch_hash(max_level, *left),
+                    || Self::batch_hash(max_level, *right),
+                );
+                Self::batch_parent_node(left?, right?)
+            }
+        }
+    }
+
+    // batchBuildSubtree builds a subtree from scratch that contains all inserted leaves, leaves
+    // are placed at the first level where their paths diverge
+    fn batch_build_subtree(
+        max_level: usize,
+        lvl: usize,
+        entries: Vec<BatchEntry<H>>,
+    ) -> Result<(BranchHash, Vec<Node<H>>), Error> {
+        let mut leaves = entries
+            .into_iter()
+            .filter(|e| e.leaf.is_some())
+            .collect::<Vec<_>>();
+        match leaves.len() {
+            0 => Ok((BranchHash::empty(), Vec::new())),
+            1 => {
+                let leaf = leaves.pop().unwrap().leaf.unwrap();
+                Ok((BranchHash::Ternimal(*leaf.hash()), vec![leaf]))
+            }
+            size => {
+                if lvl > max_level - 2 {
+                    return Err(Error::ReachedMaxLevel);
+                }
+                let (right, left): (Vec<_>, Vec<_>) =
+                    leaves.into_iter().partition(|e| e.path[lvl]);
+                let (left, right) = parallel::join(
+                    size,
+                    || Self::batch_build_subtree(max_level, lvl + 1, left),
+                    || Self::batch_build_subtree(max_level, lvl + 1, right),
+                );
+                Self::batch_parent_node(left?, right?)
+            }
+        }
+    }
+
+    // batchParentNode creates parent for two subtrees, if one of subtrees is empty and another
+    // one is a leaf then the leaf goes up instead (the same way as deletion does)
+    fn batch_parent_node(
+        (left, mut left_nodes): (BranchHash, Vec<Node<H>>),
+        (right, right_nodes): (BranchHash, Vec<Node<H>>),
+    ) -> Result<(BranchHash, Vec<Node<H>>), Error> {
+        let is_empty = |v: &BranchHash| v == &BranchHash::empty();
+        left_nodes.extend(right_nodes);
+        match (&left, &right) {
+            (BranchHash::Ternimal(_), r) if is_empty(r) => Ok((left, left_nodes)),
+            (l, BranchHash::Ternimal(_)) if is_empty(l) => Ok((right, left_nodes)),
+            _ => {
+                let new_parent_node = <Node<H>>::new_branch(left, right)?;
+                let hash = *new_parent_node.hash();
+                left_nodes.push(new_parent_node);
+                Ok((BranchHash::Branch(hash), left_nodes))
+            }
+        }
+    }
+
     fn update_preimage<D: PreimageDatabase>(
         &mut self,
         db: &mut D,
@@ -505,6 +704,33 @@ impl<H: HashScheme> ZkTrie<H> {
         Ok(())
     }
 
+    // WalkLeaves visits every leaf node of the trie in depth-first order (left subtree goes
+    // first), it's used to iterate over the whole state, f.e. for snapshot export.
+    pub fn walk_leaves<D, F>(&self, db: &D, mut visit_leaf: F) -> Result<(), Error>
+    where
+        D: Database<Node = Node<H>>,
+        F: FnMut(&D, Arc<Node<H>>) -> Result<(), Error>,
+    {
+        let mut stack = vec![(0, self.root)];
+        while let Some((lvl, hash)) = stack.pop() {
+            let n = self
+                .get_node(db, &hash)?
+                .ok_or(Error::NodeNotFound((lvl, hash)))?;
+            match n.value() {
+                NodeValue::Empty => {}
+                NodeValue::Leaf(_) => visit_leaf(db, n)?,
+                NodeValue::Branch(branch) => {
+                    if lvl >= self.max_level {
+                        return Err(Error::ReachedMaxLevel);
+                    }
+                    stack.push((lvl + 1, *branch.right.hash()));
+                    stack.push((lvl + 1, *branch.left.hash()));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn proof<D>(&self, db: &D, key: &[u8]) -> Result<Vec<Vec<u8>>, Error>
     where
         D: Database<Node = Node<H>>,
@@ -1,8 +1,10 @@
-use std::prelude::v1::*;
-
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use core::cell::RefCell;
-use std::collections::{BTreeMap, BTreeSet};
-use std::sync::Arc;
 
 use crate::{
     node_bytes, Byte32, Database, Error, Hash, HashScheme, Node, NodeValue, PreimageDatabase,
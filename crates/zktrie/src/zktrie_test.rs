@@ -1,6 +1,6 @@
 use crate::{
-    byte32_test::TestHash, copy_truncated, decode_smt_proofs, to_secure_key, Byte32, Database,
-    Error, Hash, MemDB, Node, NodeValue, PoseidonHash, TrieData, ZkTrie, ZERO_HASH,
+    byte32_test::TestHash, copy_truncated, decode_smt_proofs, to_secure_key, verify_proof, Byte32,
+    Database, Error, Hash, MemDB, Node, NodeValue, PoseidonHashV1, TrieData, ZkTrie, ZERO_HASH,
 };
 use std::{collections::BTreeMap, sync::Arc};
 
@@ -557,9 +557,234 @@ fn test_zktrie_prove_and_prove_with_deletion() {
     }
 }
 
+#[test]
+fn test_zktrie_verify_proof() {
+    let make_key = |s: &str| {
+        let mut key = vec![0_u8; 32];
+        copy_truncated(&mut key, s.as_bytes());
+        key
+    };
+    let mut db = MemDB::new();
+    let db = &mut db;
+    let mut trie = <ZkTrie<TestHash>>::new(248, Hash::default());
+
+    // non-existence in empty trie
+    let missing_key = make_key("missing");
+    let proof = trie.proof(db, &missing_key).unwrap();
+    let result = verify_proof::<TestHash, _>(trie.hash(), &missing_key, &proof).unwrap();
+    assert!(result.is_none());
+
+    let keys = &["key1", "key2", "key3", "key4", "key5"];
+    for (i, key_str) in keys.iter().enumerate() {
+        trie.update(
+            db,
+            &make_key(key_str),
+            1,
+            vec![byte32_from_byte(i as u8 + 1)],
+        )
+        .unwrap();
+    }
+    let root = *trie.hash();
+
+    // existence
+    for (i, key_str) in keys.iter().enumerate() {
+        let key = make_key(key_str);
+        let proof = trie.proof(db, &key).unwrap();
+        let node = verify_proof::<TestHash, _>(&root, &key, &proof)
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.data(), byte32_from_byte(i as u8 + 1).bytes());
+    }
+
+    // non-existence
+    let proof = trie.proof(db, &missing_key).unwrap();
+    let result = verify_proof::<TestHash, _>(&root, &missing_key, &proof).unwrap();
+    assert!(result.is_none());
+
+    // proof doesn't match the root
+    let key = make_key(keys[0]);
+    let mut proof = trie.proof(db, &key).unwrap();
+    assert_eq!(
+        verify_proof::<TestHash, _>(&Hash::default(), &key, &proof),
+        Err(Error::InvalidProof)
+    );
+    // proof for another key
+    assert!(verify_proof::<TestHash, _>(&root, &make_key(keys[1]), &proof).is_err());
+    // missing terminator
+    proof.pop();
+    assert_eq!(
+        verify_proof::<TestHash, _>(&root, &key, &proof),
+        Err(Error::InvalidProof)
+    );
+    // tampered leaf
+    let leaf = proof.last_mut().unwrap();
+    *leaf.last_mut().unwrap() ^= 1;
+    proof.push(crate::MAGIC_SMT_BYTES.to_vec());
+    assert!(verify_proof::<TestHash, _>(&root, &key, &proof).is_err());
+}
+
+#[test]
+fn test_zktrie_walk_leaves() {
+    let mut db = MemDB::new();
+    let db = &mut db;
+    let mut trie = <ZkTrie<TestHash>>::new(248, Hash::default());
+
+    // empty trie has no leaves
+    let mut leaves = Vec::new();
+    trie.walk_leaves(db, |_, node| {
+        leaves.push(node);
+        Ok(())
+    })
+    .unwrap();
+    assert!(leaves.is_empty());
+
+    let keys = &["key1", "key2", "key3", "key4", "key5"];
+    for (i, key_str) in keys.iter().enumerate() {
+        let mut key = vec![0_u8; 32];
+        copy_truncated(&mut key, key_str.as_bytes());
+        trie.update(db, &key, i as u32 + 1, vec![byte32_from_byte(i as u8 + 1)])
+            .unwrap();
+    }
+
+    let mut values = BTreeMap::new();
+    trie.walk_leaves(db, |_, node| {
+        let leaf = node.leaf().unwrap();
+        values.insert(leaf.compressed_flags, node.data().to_vec());
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(values.len(), keys.len());
+    for i in 0..keys.len() {
+        assert_eq!(
+            values.get(&(i as u32 + 1)).unwrap(),
+            &byte32_from_byte(i as u8 + 1).bytes().to_vec()
+        );
+    }
+}
+
+#[test]
+fn test_zktrie_update_batch() {
+    let make_key = |i: usize| {
+        let mut key = vec![0_u8; 32];
+        copy_truncated(&mut key, format!("key{}", i).as_bytes());
+        key
+    };
+    let mut db1 = MemDB::new();
+    let mut trie1 = <ZkTrie<TestHash>>::new(248, Hash::default());
+    let mut db2 = MemDB::new();
+    let mut trie2 = <ZkTrie<TestHash>>::new(248, Hash::default());
+
+    // batch with deletions only doesn't affect empty trie
+    trie2
+        .update_batch(&mut db2, vec![(make_key(0).as_slice(), None)])
+        .unwrap();
+    assert_eq!(trie2.hash(), ZERO_HASH.as_ref());
+
+    // insert into empty trie
+    let keys = (0..50).map(make_key).collect::<Vec<_>>();
+    for (i, key) in keys.iter().enumerate() {
+        trie1
+            .update(&mut db1, key, 1, vec![byte32_from_byte(i as u8)])
+            .unwrap();
+    }
+    trie2
+        .update_batch(
+            &mut db2,
+            keys.iter()
+                .enumerate()
+                .map(|(i, key)| (key.as_slice(), Some((1, vec![byte32_from_byte(i as u8)])))),
+        )
+        .unwrap();
+    assert_eq!(trie1.hash(), trie2.hash());
+
+    // mix of updates, deletions, insertions, deletions of missing keys and duplicates
+    let mut batch = Vec::new();
+    for (i, key) in keys.iter().enumerate() {
+        if i % 3 == 0 {
+            trie1.delete(&mut db1, key).unwrap();
+            batch.push((key.as_slice(), None));
+        } else if i % 3 == 1 {
+            trie1
+                .update(&mut db1, key, 2, vec![byte32_from_byte(100 + i as u8)])
+                .unwrap();
+            batch.push((key.as_slice(), Some((2, vec![byte32_from_byte(1)]))));
+            batch.push((
+                key.as_slice(),
+                Some((2, vec![byte32_from_byte(100 + i as u8)])),
+            ));
+        }
+    }
+    let new_keys = (50..60).map(make_key).collect::<Vec<_>>();
+    for key in new_keys.iter() {
+        trie1
+            .update(&mut db1, key, 1, vec![byte32_from_byte(7)])
+            .unwrap();
+        batch.push((key.as_slice(), Some((1, vec![byte32_from_byte(7)]))));
+    }
+    let missing_key = make_key(1000);
+    batch.push((missing_key.as_slice(), None));
+    trie2.update_batch(&mut db2, batch).unwrap();
+    assert_eq!(trie1.hash(), trie2.hash());
+    for key in keys.iter().chain(new_keys.iter()) {
+        assert_eq!(
+            trie1.get_data(&db1, key).unwrap(),
+            trie2.get_data(&db2, key).unwrap()
+        );
+    }
+
+    // remove everything
+    let all_keys = keys.iter().chain(new_keys.iter()).collect::<Vec<_>>();
+    trie2
+        .update_batch(&mut db2, all_keys.iter().map(|key| (key.as_slice(), None)))
+        .unwrap();
+    assert_eq!(trie2.hash(), ZERO_HASH.as_ref());
+}
+
+#[test]
+fn test_zktrie_update_batch_large() {
+    // big enough to hash subtrees concurrently with `parallel` feature
+    let keys = (0..1000)
+        .map(|i: u32| {
+            let mut key = vec![0_u8; 32];
+            key[..4].copy_from_slice(&i.to_be_bytes());
+            key
+        })
+        .collect::<Vec<_>>();
+    let mut db1 = MemDB::new();
+    let mut trie1 = <ZkTrie<TestHash>>::new(248, Hash::default());
+    for (i, key) in keys.iter().enumerate() {
+        trie1
+            .update(&mut db1, key, 1, vec![byte32_from_byte(i as u8)])
+            .unwrap();
+    }
+    let mut db2 = MemDB::new();
+    let mut trie2 = <ZkTrie<TestHash>>::new(248, Hash::default());
+    trie2
+        .update_batch(
+            &mut db2,
+            keys.iter()
+                .enumerate()
+                .map(|(i, key)| (key.as_slice(), Some((1, vec![byte32_from_byte(i as u8)])))),
+        )
+        .unwrap();
+    assert_eq!(trie1.hash(), trie2.hash());
+    // update of the existing trie
+    for key in keys.iter().step_by(3) {
+        trie1.delete(&mut db1, key).unwrap();
+    }
+    trie2
+        .update_batch(
+            &mut db2,
+            keys.iter().step_by(3).map(|key| (key.as_slice(), None)),
+        )
+        .unwrap();
+    assert_eq!(trie1.hash(), trie2.hash());
+}
+
 #[test]
 fn test_zktrie_statedb() {
-    type H = PoseidonHash;
+    // expected proof is computed with the legacy scheme
+    type H = PoseidonHashV1;
     let mut db = <MemDB<H>>::new();
     let db = &mut db;
 